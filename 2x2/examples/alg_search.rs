@@ -0,0 +1,94 @@
+//! 目標の効果を持つ手順を探すコマンドラインツール
+//!
+//! 例: `<R,U,F>` で10手以内、1層目を保ったまま UFR と UBR を入れ替える手順
+//!
+//! ```text
+//! cargo run --release --example alg_search -- --faces RUF --max 10 --preserve D --swap UFR,UBR
+//! ```
+
+use rubiks_cube_2x2::cube::{Corner, Face};
+use rubiks_cube_2x2::search::{self, SearchOptions, SearchTarget};
+
+const USAGE: &str = "使い方: alg_search [--faces RUF] [--max 手数] [--limit 件数] \
+                     [--preserve 面の文字]... [--swap コーナー,コーナー]...";
+
+fn parse_face(c: char) -> Result<Face, String> {
+    match c.to_ascii_uppercase() {
+        'U' => Ok(Face::Up),
+        'D' => Ok(Face::Down),
+        'L' => Ok(Face::Left),
+        'R' => Ok(Face::Right),
+        'F' => Ok(Face::Front),
+        'B' => Ok(Face::Back),
+        _ => Err(format!("面 '{c}' を解釈できません")),
+    }
+}
+
+fn parse_args() -> Result<(SearchTarget, SearchOptions), String> {
+    let mut target = SearchTarget::any();
+    let mut options = SearchOptions::default();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("{flag} の値がありません"))
+        };
+        match flag.as_str() {
+            "--faces" => {
+                let faces = value()?
+                    .chars()
+                    .map(parse_face)
+                    .collect::<Result<Vec<_>, _>>()?;
+                options.moves = SearchOptions::with_faces(&faces).moves;
+            }
+            "--max" => options.max_length = value()?.parse().map_err(|e| format!("{e}"))?,
+            "--limit" => options.max_results = value()?.parse().map_err(|e| format!("{e}"))?,
+            "--preserve" => {
+                for c in value()?.chars() {
+                    target = target.preserve_layer(parse_face(c)?);
+                }
+            }
+            "--swap" => {
+                let value = value()?;
+                let corners = value
+                    .split(',')
+                    .map(|s| s.parse::<Corner>().map_err(|e| e.to_string()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let [a, b] = corners[..] else {
+                    return Err(format!(
+                        "--swap には2つのコーナーを指定してください: {value}"
+                    ));
+                };
+                target = target.swap_corners(a, b);
+            }
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ => return Err(format!("不明なオプション: {flag}\n{USAGE}")),
+        }
+    }
+    Ok((target, options))
+}
+
+fn main() {
+    let (target, options) = match parse_args() {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{message}");
+            std::process::exit(2);
+        }
+    };
+
+    let results = search::search(&target, &options);
+    if results.is_empty() {
+        println!("{}手以内の手順は見つかりませんでした", options.max_length);
+        return;
+    }
+    for algorithm in &results {
+        println!(
+            "{:>2}手  回しにくさ {:>3}  {}",
+            algorithm.len(),
+            search::ergonomic_cost(algorithm),
+            algorithm
+        );
+    }
+}
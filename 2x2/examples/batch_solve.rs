@@ -0,0 +1,115 @@
+//! 局面ファイルをまとめて解き、CSV/TSVのレポートを書き出すコマンドラインツール
+//!
+//! 例: `cubes/` のすべての局面を2スレッドで解き、TSVで保存する
+//!
+//! ```text
+//! cargo run --release --example batch_solve -- cubes --jobs 2 --output report.tsv
+//! ```
+//!
+//! ディレクトリを指定すると、その中のファイルを名前順に読み込みます。
+//! `---` の行で区切った、複数の局面を含むファイルも読み込めます。
+
+use rubiks_cube_2x2::batch::{self, BatchOptions, ReportFormat};
+use std::path::{Path, PathBuf};
+
+const USAGE: &str = "使い方: batch_solve ファイルまたはディレクトリ... [--jobs 数] \
+                     [--max-depth 手数] [--orientation] [--format csv|tsv] [--output ファイル]";
+
+struct Args {
+    paths: Vec<PathBuf>,
+    options: BatchOptions,
+    format: Option<ReportFormat>,
+    output: Option<PathBuf>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut parsed = Args {
+        paths: Vec::new(),
+        options: BatchOptions::default(),
+        format: None,
+        output: None,
+    };
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{arg} の値がありません"));
+        match arg.as_str() {
+            "--jobs" => parsed.options.jobs = value()?.parse().map_err(|e| format!("{e}"))?,
+            "--max-depth" => {
+                parsed.options.max_depth = value()?.parse().map_err(|e| format!("{e}"))?;
+            }
+            "--orientation" => parsed.options.ignore_orientation = false,
+            "--format" => parsed.format = Some(value()?.parse().map_err(|e| format!("{e}"))?),
+            "--output" | "-o" => parsed.output = Some(PathBuf::from(value()?)),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            flag if flag.starts_with('-') => {
+                return Err(format!("不明なオプション: {flag}\n{USAGE}"));
+            }
+            path => parsed.paths.push(PathBuf::from(path)),
+        }
+    }
+    if parsed.paths.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    Ok(parsed)
+}
+
+/// ディレクトリはその中のファイル（名前順）に展開する
+fn expand(path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return vec![path.to_path_buf()];
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file())
+        .collect();
+    files.sort();
+    files
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{message}");
+            std::process::exit(2);
+        }
+    };
+
+    let inputs: Vec<_> = args
+        .paths
+        .iter()
+        .flat_map(|path| expand(path))
+        .flat_map(|file| batch::read_positions(&file))
+        .collect();
+    eprintln!("{}局面を解きます", inputs.len());
+    let results = batch::solve_batch(&inputs, &args.options);
+
+    let format = args.format.unwrap_or_else(|| {
+        args.output
+            .as_deref()
+            .map_or(ReportFormat::Csv, ReportFormat::from_path)
+    });
+    let written = match &args.output {
+        Some(path) => std::fs::File::create(path)
+            .map_err(Into::into)
+            .and_then(|file| batch::write_report(&results, format, std::io::BufWriter::new(file))),
+        None => batch::write_report(&results, format, std::io::stdout().lock()),
+    };
+    if let Err(e) = written {
+        eprintln!("レポートを書き出せません: {e}");
+        std::process::exit(1);
+    }
+
+    let invalid = results.iter().filter(|r| !r.valid).count();
+    let unsolved = results
+        .iter()
+        .filter(|r| r.valid && r.solution.is_none())
+        .count();
+    eprintln!(
+        "完了: {}局面（無効 {}、解なし {}）",
+        results.len(),
+        invalid,
+        unsolved
+    );
+}
@@ -0,0 +1,58 @@
+//! 書き出し用のコマンドラインツールで共有する引数の解釈
+#![allow(dead_code)]
+
+use rubiks_cube_2x2::algorithm::Algorithm;
+use rubiks_cube_2x2::batch;
+use rubiks_cube_2x2::cube::{ColorScheme, Cube, Face};
+use std::path::Path;
+
+/// 面の文字（U・D・L・R・F・B、大文字小文字を問わない）
+pub fn parse_face(s: &str) -> Result<Face, String> {
+    match s.to_ascii_uppercase().as_str() {
+        "U" => Ok(Face::Up),
+        "D" => Ok(Face::Down),
+        "L" => Ok(Face::Left),
+        "R" => Ok(Face::Right),
+        "F" => Ok(Face::Front),
+        "B" => Ok(Face::Back),
+        _ => Err(format!("面 '{s}' を解釈できません")),
+    }
+}
+
+/// `幅x高さ` の形の大きさ
+pub fn parse_size(s: &str) -> Result<(u32, u32), String> {
+    let (width, height) = s
+        .split_once('x')
+        .ok_or_else(|| format!("大きさ '{s}' は 幅x高さ で指定してください"))?;
+    Ok((
+        width.parse().map_err(|e| format!("{e}"))?,
+        height.parse().map_err(|e| format!("{e}"))?,
+    ))
+}
+
+/// 配色の名前（standard・japanese）
+pub fn parse_scheme(s: &str) -> Result<ColorScheme, String> {
+    match s {
+        "standard" => Ok(ColorScheme::standard()),
+        "japanese" => Ok(ColorScheme::japanese()),
+        _ => Err(format!("配色 '{s}' はありません（standard・japanese）")),
+    }
+}
+
+/// 度で書いた角度（ラジアンにして返す）
+pub fn parse_degrees(s: &str) -> Result<f32, String> {
+    s.parse::<f32>()
+        .map(f32::to_radians)
+        .map_err(|e| format!("{e}"))
+}
+
+/// 局面ファイルのパスならその局面、そうでなければ完成状態に手順を回した局面
+pub fn load_cube(target: &str) -> Result<Cube, String> {
+    if Path::new(target).is_file() {
+        let text = std::fs::read_to_string(target).map_err(|e| e.to_string())?;
+        return batch::parse_position(&text).map_err(|e| e.to_string());
+    }
+    Algorithm::parse(target)
+        .map(|alg| alg.to_cube())
+        .map_err(|e| e.to_string())
+}
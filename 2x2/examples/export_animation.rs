@@ -0,0 +1,134 @@
+//! 手順を回すアニメーションをアニメーションPNG（APNG）で書き出すコマンドラインツール（ディスプレイ不要）
+//!
+//! ```text
+//! # 完成状態からSuneを回す（1秒30コマ、1手0.4秒）
+//! cargo run --release --example export_animation -- "R U R' U R U2 R'" --fps 30 --move-duration 0.4 -o sune.png
+//! # スクランブルした局面から解法を回す
+//! cargo run --release --example export_animation -- "U R2 F'" --setup "F R2 U'" -o solve.png
+//! # 手順ファイル（開始局面とステップ）を回す
+//! cargo run --release --example export_animation -- --alg solution.alg -o solution.png
+//! ```
+
+mod common;
+
+use common::{load_cube, parse_degrees, parse_scheme, parse_size};
+use rubiks_cube_2x2::alg_file::AlgFile;
+use rubiks_cube_2x2::algorithm::Algorithm;
+use rubiks_cube_2x2::cube::Cube;
+use rubiks_cube_2x2::geometry::View3D;
+use rubiks_cube_2x2::raster::{self, PlaybackOptions, RasterOptions};
+use std::path::PathBuf;
+
+const USAGE: &str = "使い方: export_animation 手順 [--setup 手順または局面ファイル] --output ファイル
+       export_animation --alg 手順ファイル --output ファイル
+オプション: [--size 幅x高さ] [--yaw 度] [--pitch 度] [--zoom 倍率] [--scheme standard|japanese]
+           [--fps コマ数] [--move-duration 秒] [--pause 秒] [--loops 回数] [--no-arrows] [--transparent]";
+
+struct Args {
+    moves: Option<String>,
+    setup: Option<String>,
+    alg_file: Option<PathBuf>,
+    view: View3D,
+    options: RasterOptions,
+    playback: PlaybackOptions,
+    output: PathBuf,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut positional = Vec::new();
+    let mut setup = None;
+    let mut alg_file = None;
+    let mut view = View3D::default();
+    let mut options = RasterOptions {
+        width: 400,
+        height: 400,
+        ..RasterOptions::default()
+    };
+    let mut playback = PlaybackOptions::default();
+    let mut output = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{arg} の値がありません"));
+        match arg.as_str() {
+            "--setup" => setup = Some(value()?),
+            "--alg" => alg_file = Some(PathBuf::from(value()?)),
+            "--size" => (options.width, options.height) = parse_size(&value()?)?,
+            "--yaw" => view.yaw = parse_degrees(&value()?)?,
+            "--pitch" => view.pitch = parse_degrees(&value()?)?,
+            "--zoom" => view.scale = value()?.parse().map_err(|e| format!("{e}"))?,
+            "--scheme" => options.scheme = parse_scheme(&value()?)?,
+            "--fps" => playback.frame_rate = value()?.parse().map_err(|e| format!("{e}"))?,
+            "--move-duration" => {
+                playback.move_duration = value()?.parse().map_err(|e| format!("{e}"))?;
+            }
+            "--pause" => playback.pause = value()?.parse().map_err(|e| format!("{e}"))?,
+            "--loops" => playback.loops = value()?.parse().map_err(|e| format!("{e}"))?,
+            "--no-arrows" => options.arrows = false,
+            "--transparent" => options.background = [0, 0, 0, 0],
+            "--output" | "-o" => output = Some(PathBuf::from(value()?)),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            flag if flag.starts_with("--") => {
+                return Err(format!("不明なオプション: {flag}\n{USAGE}"));
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let moves = match (positional.len(), &alg_file) {
+        (0, Some(_)) => None,
+        (1, None) => positional.pop(),
+        _ => return Err(USAGE.to_string()),
+    };
+    Ok(Args {
+        moves,
+        setup,
+        alg_file,
+        view,
+        options,
+        playback,
+        output: output.ok_or_else(|| format!("--output を指定してください\n{USAGE}"))?,
+    })
+}
+
+/// 開始局面と回す手順
+fn load_playback(args: &Args) -> Result<(Cube, Algorithm), String> {
+    if let Some(path) = &args.alg_file {
+        let file = AlgFile::load(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let start = match &args.setup {
+            Some(setup) => load_cube(setup)?,
+            None => file.start_cube().unwrap_or_default(),
+        };
+        return Ok((start, file.algorithm()));
+    }
+    let start = match &args.setup {
+        Some(setup) => load_cube(setup)?,
+        None => Cube::new(),
+    };
+    let moves = args.moves.as_deref().unwrap_or_default();
+    Ok((start, Algorithm::parse(moves).map_err(|e| e.to_string())?))
+}
+
+fn main() {
+    let result = parse_args().and_then(|args| {
+        let (start, algorithm) = load_playback(&args)?;
+        let frames = raster::render_playback(
+            &start,
+            &algorithm.moves,
+            &args.view,
+            &args.options,
+            &args.playback,
+        );
+        raster::save_apng(&frames, &args.playback, &args.output)
+            .map_err(|e| format!("{} に書き込めません: {e}", args.output.display()))?;
+        eprintln!(
+            "{} コマを {} に書き出しました",
+            frames.len(),
+            args.output.display()
+        );
+        Ok(())
+    });
+    if let Err(message) = result {
+        eprintln!("{message}");
+        std::process::exit(2);
+    }
+}
@@ -0,0 +1,63 @@
+//! 局面を3Dモデル（OBJ/MTL）で書き出すコマンドラインツール
+//!
+//! ```text
+//! # スクランブルした局面（1辺50mm、向きの矢印付き）。cube.obj と cube.mtl を書き出す
+//! cargo run --example export_obj -- "R U R' U'" --size 50 --arrows -o cube.obj
+//! # 局面ファイルから
+//! cargo run --example export_obj -- cubes/cube_ex001.txt -o ex001.obj
+//! ```
+
+mod common;
+
+use common::{load_cube, parse_scheme};
+use rubiks_cube_2x2::model::{self, ModelOptions};
+use std::path::PathBuf;
+
+const USAGE: &str = "使い方: export_obj 手順または局面ファイル --output ファイル.obj
+オプション: [--size 1辺の長さ] [--scheme standard|japanese] [--arrows]";
+
+struct Args {
+    target: String,
+    options: ModelOptions,
+    output: PathBuf,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut positional = Vec::new();
+    let mut options = ModelOptions::default();
+    let mut output = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{arg} の値がありません"));
+        match arg.as_str() {
+            "--size" => options.size = value()?.parse().map_err(|e| format!("{e}"))?,
+            "--scheme" => options.scheme = parse_scheme(&value()?)?,
+            "--arrows" => options.arrows = true,
+            "--output" | "-o" => output = Some(PathBuf::from(value()?)),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            flag if flag.starts_with("--") => {
+                return Err(format!("不明なオプション: {flag}\n{USAGE}"));
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let [target] = <[String; 1]>::try_from(positional).map_err(|_| USAGE.to_string())?;
+    Ok(Args {
+        target,
+        options,
+        output: output.ok_or_else(|| format!("--output を指定してください\n{USAGE}"))?,
+    })
+}
+
+fn main() {
+    let result = parse_args().and_then(|args| {
+        let cube = load_cube(&args.target)?;
+        model::save_obj(&cube, &args.options, &args.output)
+            .map_err(|e| format!("{} に書き込めません: {e}", args.output.display()))
+    });
+    if let Err(message) = result {
+        eprintln!("{message}");
+        std::process::exit(2);
+    }
+}
@@ -0,0 +1,118 @@
+//! 展開図と手順図をSVGで書き出すコマンドラインツール
+//!
+//! ```text
+//! # スクランブルした局面（または局面ファイル）の展開図
+//! cargo run --example export_svg -- net "R U R' U'" --highlight F -o net.svg
+//! # 1つの手順の上の層の図
+//! cargo run --example export_svg -- alg "R U R' U R U2 R'" -o sune.svg
+//! # 同梱のケース表のセットの一覧表
+//! cargo run --example export_svg -- sheet CLL --columns 6 -o cll.svg
+//! ```
+
+mod common;
+
+use common::{load_cube, parse_face};
+use rubiks_cube_2x2::algorithm::Algorithm;
+use rubiks_cube_2x2::cases::{self, CaseSet};
+use rubiks_cube_2x2::svg::{self, LastLayerSvgOptions, NetSvgOptions};
+use std::path::PathBuf;
+
+const USAGE: &str = "使い方: export_svg net 手順または局面ファイル [--highlight 面] [--no-arrows]
+       export_svg alg 手順 [--no-movement]
+       export_svg sheet セット [--columns 列数] [--no-movement]
+共通オプション: [--cell 大きさ] [--output ファイル]";
+
+struct Args {
+    command: String,
+    target: String,
+    net: NetSvgOptions,
+    last_layer: LastLayerSvgOptions,
+    columns: usize,
+    output: Option<PathBuf>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut positional = Vec::new();
+    let mut parsed = Args {
+        command: String::new(),
+        target: String::new(),
+        net: NetSvgOptions::default(),
+        last_layer: LastLayerSvgOptions::default(),
+        columns: 4,
+        output: None,
+    };
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{arg} の値がありません"));
+        match arg.as_str() {
+            "--highlight" => parsed.net.highlight_face = Some(parse_face(&value()?)?),
+            "--no-arrows" => parsed.net.arrows = false,
+            "--no-movement" => parsed.last_layer.movement = false,
+            "--columns" => parsed.columns = value()?.parse().map_err(|e| format!("{e}"))?,
+            "--cell" => {
+                let cell: f32 = value()?.parse().map_err(|e| format!("{e}"))?;
+                parsed.net.cell_size = cell;
+                parsed.last_layer.cell_size = cell;
+            }
+            "--output" | "-o" => parsed.output = Some(PathBuf::from(value()?)),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            flag if flag.starts_with("--") => {
+                return Err(format!("不明なオプション: {flag}\n{USAGE}"));
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let [command, target] = <[String; 2]>::try_from(positional).map_err(|_| USAGE.to_string())?;
+    parsed.command = command;
+    parsed.target = target;
+    Ok(parsed)
+}
+
+fn render(args: &Args) -> Result<String, String> {
+    match args.command.as_str() {
+        "net" => Ok(svg::net_svg(&load_cube(&args.target)?, &args.net)),
+        "alg" => {
+            let algorithm = Algorithm::parse(&args.target).map_err(|e| e.to_string())?;
+            Ok(svg::last_layer_svg(&algorithm, &args.last_layer))
+        }
+        "sheet" => {
+            let set = CaseSet::ALL
+                .into_iter()
+                .find(|set| set.name().eq_ignore_ascii_case(&args.target))
+                .ok_or_else(|| {
+                    let names: Vec<&str> = CaseSet::ALL.iter().map(|s| s.name()).collect();
+                    format!(
+                        "セット '{}' はありません（{}）",
+                        args.target,
+                        names.join("・")
+                    )
+                })?;
+            let entries: Vec<(&str, &Algorithm)> = cases::cases()
+                .iter()
+                .filter(|case| case.set == set)
+                .map(|case| (case.name.as_str(), &case.algorithm))
+                .collect();
+            Ok(svg::alg_sheet_svg(&entries, args.columns, &args.last_layer))
+        }
+        other => Err(format!("不明なコマンド: {other}\n{USAGE}")),
+    }
+}
+
+fn main() {
+    let result = parse_args().and_then(|args| {
+        let svg = render(&args)?;
+        match &args.output {
+            Some(path) => std::fs::write(path, svg)
+                .map_err(|e| format!("{} に書き込めません: {e}", path.display())),
+            None => {
+                print!("{svg}");
+                Ok(())
+            }
+        }
+    });
+    if let Err(message) = result {
+        eprintln!("{message}");
+        std::process::exit(2);
+    }
+}
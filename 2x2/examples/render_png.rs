@@ -0,0 +1,81 @@
+//! 局面の3D表示と展開図をPNGで書き出すコマンドラインツール（ディスプレイ不要）
+//!
+//! ```text
+//! # スクランブルした局面の3D表示
+//! cargo run --example render_png -- 3d "R U R' U'" --size 400x400 -o cube.png
+//! # 局面ファイルの展開図（背景は透明）
+//! cargo run --example render_png -- net cubes/cube_ex001.txt --transparent -o net.png
+//! ```
+
+mod common;
+
+use common::{load_cube, parse_degrees, parse_face, parse_scheme, parse_size};
+use rubiks_cube_2x2::geometry::View3D;
+use rubiks_cube_2x2::raster::{self, RasterOptions};
+use std::path::PathBuf;
+
+const USAGE: &str = "使い方: render_png 3d|net 手順または局面ファイル --output ファイル
+オプション: [--size 幅x高さ] [--yaw 度] [--pitch 度] [--zoom 倍率] [--scheme standard|japanese]
+           [--highlight 面] [--no-arrows] [--transparent]";
+
+struct Args {
+    command: String,
+    target: String,
+    view: View3D,
+    options: RasterOptions,
+    output: PathBuf,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut positional = Vec::new();
+    let mut view = View3D::default();
+    let mut options = RasterOptions::default();
+    let mut output = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{arg} の値がありません"));
+        match arg.as_str() {
+            "--size" => (options.width, options.height) = parse_size(&value()?)?,
+            "--yaw" => view.yaw = parse_degrees(&value()?)?,
+            "--pitch" => view.pitch = parse_degrees(&value()?)?,
+            "--zoom" => view.scale = value()?.parse().map_err(|e| format!("{e}"))?,
+            "--scheme" => options.scheme = parse_scheme(&value()?)?,
+            "--highlight" => options.highlight_face = Some(parse_face(&value()?)?),
+            "--no-arrows" => options.arrows = false,
+            "--transparent" => options.background = [0, 0, 0, 0],
+            "--output" | "-o" => output = Some(PathBuf::from(value()?)),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            flag if flag.starts_with("--") => {
+                return Err(format!("不明なオプション: {flag}\n{USAGE}"));
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let [command, target] = <[String; 2]>::try_from(positional).map_err(|_| USAGE.to_string())?;
+    Ok(Args {
+        command,
+        target,
+        view,
+        options,
+        output: output.ok_or_else(|| format!("--output を指定してください\n{USAGE}"))?,
+    })
+}
+
+fn main() {
+    let result = parse_args().and_then(|args| {
+        let cube = load_cube(&args.target)?;
+        let image = match args.command.as_str() {
+            "3d" => raster::render_3d(&cube, &args.view, &args.options),
+            "net" => raster::render_net(&cube, &args.options),
+            other => return Err(format!("不明なコマンド: {other}\n{USAGE}")),
+        };
+        image
+            .save_png(&args.output)
+            .map_err(|e| format!("{} に書き込めません: {e}", args.output.display()))
+    });
+    if let Err(message) = result {
+        eprintln!("{message}");
+        std::process::exit(2);
+    }
+}
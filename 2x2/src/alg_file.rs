@@ -0,0 +1,235 @@
+use crate::algorithm::Algorithm;
+use crate::cube::io::{content_lines, single_line};
+use crate::cube::Cube;
+use crate::error::{first_column, parse_error, Result};
+use std::path::Path;
+
+/// 手順ファイルの先頭行
+pub const ALG_FILE_HEADER: &str = "2x2alg v1";
+
+/// 手順ファイルの拡張子
+pub const ALG_FILE_EXTENSION: &str = "alg";
+
+/// 手順の並びを始める行
+const MOVES_MARKER: &str = "moves:";
+
+/// 回転記号の説明（エラーの表示用）
+const EXPECTED_MOVES: &str = "回転記号（例: `R U R' U'`）";
+
+/// 手順ファイルの1ステップ（1行分の手順とコメント）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlgStep {
+    pub algorithm: Algorithm,
+    /// 行末の `#` 以降に書いたコメント
+    pub comment: Option<String>,
+}
+
+/// 手順ファイル（解法・スクランブル・練習用の手順）
+///
+/// ```text
+/// 2x2alg v1
+/// title: Sune
+/// setup: R U2 R' U' R U' R'
+/// moves:
+/// R U R' U   # 右手で3手
+/// R U2 R'    # 仕上げ
+/// ```
+///
+/// `setup:`（`scramble:` とも書ける）は完成状態から手順の開始局面を作る手順で、省略できます。
+/// `moves:` の後は1行が1ステップで、`#` 以降はそのステップのコメントです。
+/// `#` で始まる行と空行は無視します。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AlgFile {
+    pub title: String,
+    /// 開始局面を作る手順
+    pub setup: Option<Algorithm>,
+    pub steps: Vec<AlgStep>,
+}
+
+impl AlgFile {
+    /// 題名だけの空の手順ファイルを作成
+    #[must_use]
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// ステップを追加したもの
+    #[must_use]
+    pub fn with_step(mut self, algorithm: Algorithm, comment: Option<&str>) -> Self {
+        self.steps.push(AlgStep {
+            algorithm,
+            comment: comment.map(str::to_string),
+        });
+        self
+    }
+
+    /// すべてのステップをつなげた手順
+    #[must_use]
+    pub fn algorithm(&self) -> Algorithm {
+        Algorithm::new(
+            self.steps
+                .iter()
+                .flat_map(|step| step.algorithm.moves.iter().copied())
+                .collect(),
+        )
+    }
+
+    /// 手順の `index` 手目（0始まり）を含むステップと、その番号（0始まり）
+    #[must_use]
+    pub fn step_at(&self, index: usize) -> Option<(usize, &AlgStep)> {
+        let mut end = 0;
+        self.steps.iter().enumerate().find(|(_, step)| {
+            end += step.algorithm.len();
+            index < end
+        })
+    }
+
+    /// 開始局面（完成状態に `setup` を適用した局面、`setup` がなければ `None`）
+    #[must_use]
+    pub fn start_cube(&self) -> Option<Cube> {
+        self.setup.as_ref().map(Algorithm::to_cube)
+    }
+
+    /// 手順ファイルの文字列を読み込みます。
+    ///
+    /// 先頭行 `2x2alg v1` は省略できます。書式の誤りは行と列の位置を持つ
+    /// `CubeError::Parse` になります。
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut file = Self::default();
+        let mut in_moves = false;
+        let mut lines = content_lines(s).peekable();
+
+        if lines
+            .peek()
+            .is_some_and(|(_, line)| line.trim_start().starts_with("2x2alg"))
+        {
+            let (number, line) = lines.next().unwrap_or_default();
+            if line.trim() != ALG_FILE_HEADER {
+                return Err(parse_error(
+                    number,
+                    first_column(line),
+                    &format!("`{ALG_FILE_HEADER}`"),
+                    format!("`{}`", line.trim()),
+                ));
+            }
+        }
+
+        for (number, line) in lines {
+            if in_moves {
+                let (moves, comment) = match line.split_once('#') {
+                    Some((moves, comment)) => (moves, Some(comment.trim().to_string())),
+                    None => (line, None),
+                };
+                file.steps.push(AlgStep {
+                    algorithm: parse_moves(number, moves, 0)?,
+                    comment: comment.filter(|c| !c.is_empty()),
+                });
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(parse_error(
+                    number,
+                    first_column(line),
+                    "`キー: 値` または `moves:`",
+                    format!("`{}`", line.trim()),
+                ));
+            };
+            // 値の始まる列（キーと `:` の後）
+            let offset = key.chars().count() + 1;
+            match key.trim() {
+                "title" => file.title = value.trim().to_string(),
+                "setup" | "scramble" => file.setup = Some(parse_moves(number, value, offset)?),
+                "moves" => {
+                    in_moves = true;
+                    // `moves: R U R'` のように同じ行に書いた手順は1ステップとする
+                    if !value.trim().is_empty() {
+                        file.steps.push(AlgStep {
+                            algorithm: parse_moves(number, value, offset)?,
+                            comment: None,
+                        });
+                    }
+                }
+                other => {
+                    return Err(parse_error(
+                        number,
+                        first_column(line),
+                        "`title:`・`setup:`・`moves:` のいずれか",
+                        format!("`{other}:`"),
+                    ));
+                }
+            }
+        }
+
+        if !in_moves {
+            return Err(parse_error(
+                s.lines().count() + 1,
+                1,
+                &format!("`{MOVES_MARKER}`"),
+                "ファイルの終わり",
+            ));
+        }
+        Ok(file)
+    }
+
+    /// 手順ファイルの文字列に変換します。
+    ///
+    /// 題名とコメントの改行は空白に置き換えます。
+    #[must_use]
+    pub fn to_file_format(&self) -> String {
+        let mut result = format!("{ALG_FILE_HEADER}\n");
+        if !self.title.is_empty() {
+            result.push_str(&format!("title: {}\n", single_line(&self.title)));
+        }
+        if let Some(setup) = &self.setup {
+            result.push_str(&format!("setup: {setup}\n"));
+        }
+        result.push_str(MOVES_MARKER);
+        result.push('\n');
+        for step in &self.steps {
+            result.push_str(&step.algorithm.to_string());
+            if let Some(comment) = &step.comment {
+                result.push_str(&format!("  # {}", single_line(comment)));
+            }
+            result.push('\n');
+        }
+        result
+    }
+
+    /// ファイルから読み込みます。
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// ファイルに保存します。
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_file_format())?;
+        Ok(())
+    }
+}
+
+/// 回転記号を読む（`offset` は `text` が行の何文字目の後から始まるか）
+///
+/// 読めない記号があれば、その記号の位置のエラーになります。
+fn parse_moves(line: usize, text: &str, offset: usize) -> Result<Algorithm> {
+    Algorithm::parse(text).map_err(|_| {
+        // 空白で区切った記号を1つずつ読み、最初に読めないものの位置を示す
+        let mut column = offset + 1;
+        let mut found = format!("`{}`", text.trim());
+        let mut rest = text;
+        while let Some(start) = rest.find(|c: char| !c.is_whitespace()) {
+            column += rest[..start].chars().count();
+            let token = rest[start..].split_whitespace().next().unwrap_or_default();
+            if Algorithm::parse(token).is_err() {
+                found = format!("`{token}`");
+                break;
+            }
+            column += token.chars().count();
+            rest = &rest[start + token.len()..];
+        }
+        parse_error(line, column, EXPECTED_MOVES, found)
+    })
+}
@@ -0,0 +1,540 @@
+use crate::cube::symmetry::{self, Symmetry};
+use crate::cube::{Corner, Cube, Face, Move};
+use crate::error::{CubeError, Result};
+
+/// U面の調整（AUF）の候補（調整なしを先頭に）
+const AUF_MOVES: [Option<Move>; 4] = [None, Some(Move::U), Some(Move::U2), Some(Move::Up)];
+
+/// 周期を探す上限（2x2の局面の周期はこれを超えない）
+const MAX_ORDER: usize = 1260;
+
+/// 回転操作の手順
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Algorithm {
+    pub moves: Vec<Move>,
+}
+
+impl Algorithm {
+    /// 回転操作の列から手順を作成
+    #[must_use]
+    pub fn new(moves: Vec<Move>) -> Self {
+        Self { moves }
+    }
+
+    /// 空白区切りの記号（例: `R U R' U'`）から手順を作成します。
+    ///
+    /// 空白のない表記（例: `RUR'U'`）や、括弧・カンマも受け付けます。
+    /// 記号は空白・括弧・カンマで終わるので、離れた `'`・`2`（例: `R '`）はエラーになります。
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut moves = Vec::new();
+        let mut token = String::new();
+        for c in s.chars() {
+            match c {
+                'R' | 'L' | 'U' | 'D' | 'F' | 'B' => {
+                    if !token.is_empty() {
+                        moves.push(token.parse()?);
+                    }
+                    token = c.to_string();
+                }
+                c if c.is_whitespace() || matches!(c, '(' | ')' | '[' | ']' | ',') => {
+                    if !token.is_empty() {
+                        moves.push(std::mem::take(&mut token).parse()?);
+                    }
+                }
+                _ => token.push(c),
+            }
+        }
+        if !token.is_empty() {
+            moves.push(token.parse()?);
+        }
+        Ok(Self { moves })
+    }
+
+    /// 手数（HTM）
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// 空の手順か
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// キューブに手順を適用
+    pub fn apply_to(&self, cube: &mut Cube) {
+        for &mv in &self.moves {
+            cube.apply_move(mv);
+        }
+    }
+
+    /// 完成状態に手順を適用した局面
+    #[must_use]
+    pub fn to_cube(&self) -> Cube {
+        Cube::from_moves(&self.moves)
+    }
+
+    /// 逆手順（効果を打ち消す手順）
+    #[must_use]
+    pub fn inverse(&self) -> Algorithm {
+        Self::new(self.moves.iter().rev().map(|mv| mv.inverse()).collect())
+    }
+
+    /// 指定した面を鏡として反転した手順（例: M面で `R U R'` は `L' U' L`）
+    #[must_use]
+    pub fn mirror(&self, plane: MirrorPlane) -> Algorithm {
+        let (a, b) = plane.swapped_faces();
+        self.map_moves(|mv| {
+            let face = match mv.face() {
+                f if f == a => b,
+                f if f == b => a,
+                f => f,
+            };
+            // 鏡に映すと回転の向きが逆になる
+            mv.inverse().with_face(face)
+        })
+    }
+
+    /// 各面を `rotation` で移った先の面に置き換えた手順（例: y で `R U R'` は `F U F'`）
+    ///
+    /// 持ち替える前に回していた面を、持ち替えた後に同じように回す手順になります。
+    #[must_use]
+    pub fn rotated(&self, rotation: Rotation) -> Algorithm {
+        let map = rotation.face_map();
+        self.map_moves(|mv| mv.with_face(map[mv.face().index()]))
+    }
+
+    /// 面の置き換え（`map[face.index()]` が `face` の置き換え先）を適用した手順
+    ///
+    /// 回転の向きはそのまま保ちます。置き換えが1対1でない場合はエラーになります。
+    pub fn relabel(&self, map: &[Face; 6]) -> Result<Algorithm> {
+        for face in Face::ALL {
+            if !map.contains(&face) {
+                return Err(CubeError::InvalidFormat(format!(
+                    "面の置き換えに{}がありません（1対1である必要があります）",
+                    face.label()
+                )));
+            }
+        }
+        Ok(self.map_moves(|mv| mv.with_face(map[mv.face().index()])))
+    }
+
+    /// 各回転操作を置き換えた手順
+    fn map_moves(&self, f: impl Fn(Move) -> Move) -> Algorithm {
+        Self::new(self.moves.iter().map(|&mv| f(mv)).collect())
+    }
+
+    /// 手順の周期（何回繰り返すと色の配置が元に戻るか）
+    ///
+    /// ステッカーの矢印は比較しません（矢印まで戻るには周期の倍数の回数が必要な場合があります）。
+    #[must_use]
+    pub fn order(&self) -> usize {
+        let effect = self.to_cube();
+        let solved = Cube::new().normalized();
+        let mut cube = effect.clone();
+        for n in 1..=MAX_ORDER {
+            if cube.normalized() == solved {
+                return n;
+            }
+            cube = &cube * &effect;
+        }
+        unreachable!("2x2の局面の周期は{MAX_ORDER}以下です")
+    }
+
+    /// 手順がコーナーをどう動かすかを解析します。
+    #[must_use]
+    pub fn analyze(&self) -> AlgorithmAnalysis {
+        let state = self
+            .to_cube()
+            .corner_state()
+            .expect("回転操作だけで作った局面のコーナーは常に有効です");
+
+        // ピースがどのスロットに移ったか
+        let mut destination = [Corner::Ufr; 8];
+        for slot in Corner::ALL {
+            destination[state.pieces[slot.index()].index()] = slot;
+        }
+
+        let mut cycles = Vec::new();
+        let mut twisted = Vec::new();
+        let mut visited = [false; 8];
+        for start in Corner::ALL {
+            if visited[start.index()] {
+                continue;
+            }
+            let mut corners = Vec::new();
+            let mut twist = 0;
+            let mut current = start;
+            while !visited[current.index()] {
+                visited[current.index()] = true;
+                corners.push(current);
+                current = destination[current.index()];
+                twist = (twist + state.twists[current.index()]) % 3;
+            }
+            match corners.len() {
+                1 if twist != 0 => twisted.push(TwistedCorner {
+                    corner: start,
+                    twist,
+                }),
+                1 => {}
+                _ => cycles.push(CornerCycle { corners, twist }),
+            }
+        }
+
+        // ステッカーの元の位置が変わったもの
+        let mut stickers = Vec::new();
+        for slot in Corner::ALL {
+            let piece = state.pieces[slot.index()];
+            let twist = state.twists[slot.index()] as usize;
+            for (pos, &index) in slot.stickers().iter().enumerate() {
+                if piece.stickers()[(pos + 3 - twist) % 3] != index {
+                    stickers.push(index);
+                }
+            }
+        }
+        stickers.sort_unstable();
+
+        AlgorithmAnalysis {
+            order: self.order(),
+            cycles,
+            twisted,
+            stickers,
+        }
+    }
+
+    /// 2つの手順が同じ効果（局面）を持つかを、オプションで指定した変換を許して判定します。
+    ///
+    /// 同じとみなせる場合は、`other` をこの手順に一致させる変換を返します。
+    /// 変換の候補は、調整なし・回転・鏡像・逆手順の少ないものから順に試します。
+    #[must_use]
+    pub fn equivalent(
+        &self,
+        other: &Algorithm,
+        options: &EquivalenceOptions,
+    ) -> Option<Equivalence> {
+        let target = self.to_cube().normalized();
+        let target_key = options
+            .rotation
+            .then(|| symmetry::min_reorientation(&target));
+
+        let symmetries: Vec<&'static Symmetry> = Symmetry::all()
+            .iter()
+            .filter(|s| {
+                let rotation_ok = !s.has_rotation() || options.rotation;
+                let mirror_ok = !s.is_mirror() || options.mirror;
+                rotation_ok && mirror_ok
+            })
+            .collect();
+        let inversions: &[bool] = if options.inverse {
+            &[false, true]
+        } else {
+            &[false]
+        };
+        let aufs: &[Option<Move>] = if options.auf {
+            &AUF_MOVES
+        } else {
+            &AUF_MOVES[..1]
+        };
+
+        let other_cube = other.to_cube();
+        for &inverse in inversions {
+            let base = if inverse {
+                other_cube.inverse().ok()?
+            } else {
+                other_cube.clone()
+            };
+            for &symmetry in &symmetries {
+                let transformed = symmetry.conjugate(&base);
+                for &pre_auf in aufs {
+                    for &post_auf in aufs {
+                        let pre = Cube::from_moves(pre_auf.as_slice());
+                        let post = Cube::from_moves(post_auf.as_slice());
+                        let candidate = (&(&pre * &transformed) * &post).normalized();
+                        let matches = match &target_key {
+                            Some(key) => symmetry::min_reorientation(&candidate) == *key,
+                            None => candidate == target,
+                        };
+                        if matches {
+                            return Some(Equivalence {
+                                pre_auf,
+                                post_auf,
+                                symmetry,
+                                inverse,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+impl std::fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let notation: Vec<String> = self.moves.iter().map(|mv| mv.to_string()).collect();
+        write!(f, "{}", notation.join(" "))
+    }
+}
+
+impl std::str::FromStr for Algorithm {
+    type Err = crate::error::CubeError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+/// 手順を反転する鏡の面
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MirrorPlane {
+    /// 左右の中央（L面とR面が入れ替わる）
+    M,
+    /// 前後の中央（F面とB面が入れ替わる）
+    S,
+    /// 上下の中央（U面とD面が入れ替わる）
+    E,
+}
+
+impl MirrorPlane {
+    /// すべての鏡の面
+    pub const ALL: [MirrorPlane; 3] = [MirrorPlane::M, MirrorPlane::S, MirrorPlane::E];
+
+    /// 鏡によって入れ替わる面の組
+    fn swapped_faces(self) -> (Face, Face) {
+        match self {
+            MirrorPlane::M => (Face::Left, Face::Right),
+            MirrorPlane::S => (Face::Front, Face::Back),
+            MirrorPlane::E => (Face::Up, Face::Down),
+        }
+    }
+
+    /// 局面に対する同じ鏡映（`Algorithm::mirror` の効果は、元の手順の効果をこの変換で移したもの）
+    #[must_use]
+    pub fn symmetry(self) -> &'static Symmetry {
+        matching_symmetry(|mv| Algorithm::new(vec![mv]).mirror(self).moves[0])
+    }
+}
+
+/// キューブ全体の持ち替え
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rotation {
+    X,
+    Xp,
+    X2,
+    Y,
+    Yp,
+    Y2,
+    Z,
+    Zp,
+    Z2,
+}
+
+impl Rotation {
+    /// すべての持ち替え
+    pub const ALL: [Rotation; 9] = [
+        Rotation::X,
+        Rotation::Xp,
+        Rotation::X2,
+        Rotation::Y,
+        Rotation::Yp,
+        Rotation::Y2,
+        Rotation::Z,
+        Rotation::Zp,
+        Rotation::Z2,
+    ];
+
+    /// 各面が持ち替えによって移る先の面（`Face::ALL` の順）
+    #[must_use]
+    pub fn face_map(self) -> [Face; 6] {
+        use Face::{Back as B, Down as D, Front as F, Left as L, Right as R, Up as U};
+        // x はR、y はU、z はFと同じ向きに回す
+        let (quarter, axis) = match self {
+            Rotation::X => (1, [B, F, L, R, U, D]),
+            Rotation::Y => (1, [U, D, B, F, L, R]),
+            Rotation::Z => (1, [R, L, U, D, F, B]),
+            Rotation::Xp => (3, Rotation::X.face_map()),
+            Rotation::Yp => (3, Rotation::Y.face_map()),
+            Rotation::Zp => (3, Rotation::Z.face_map()),
+            Rotation::X2 => (2, Rotation::X.face_map()),
+            Rotation::Y2 => (2, Rotation::Y.face_map()),
+            Rotation::Z2 => (2, Rotation::Z.face_map()),
+        };
+        Face::ALL.map(|face| (0..quarter).fold(face, |f, _| axis[f.index()]))
+    }
+
+    /// 局面に対する同じ持ち替え（`Algorithm::rotated` の効果は、元の手順の効果をこの変換で移したもの）
+    #[must_use]
+    pub fn symmetry(self) -> &'static Symmetry {
+        matching_symmetry(|mv| Algorithm::new(vec![mv]).rotated(self).moves[0])
+    }
+}
+
+impl std::fmt::Display for Rotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Rotation::X => "x",
+            Rotation::Xp => "x'",
+            Rotation::X2 => "x2",
+            Rotation::Y => "y",
+            Rotation::Yp => "y'",
+            Rotation::Y2 => "y2",
+            Rotation::Z => "z",
+            Rotation::Zp => "z'",
+            Rotation::Z2 => "z2",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// 各回転操作の置き換えと同じ効果を局面に与える対称変換
+fn matching_symmetry(map: impl Fn(Move) -> Move) -> &'static Symmetry {
+    Symmetry::all()
+        .iter()
+        .find(|s| {
+            [Move::R, Move::U, Move::F].into_iter().all(|mv| {
+                s.conjugate(&Cube::from_moves(&[mv])) == Cube::from_moves(&[map(mv)]).normalized()
+            })
+        })
+        .expect("面の置き換えが回転・鏡映であれば対応する対称変換があります")
+}
+
+/// コーナーの巡回（`corners[0]` のピースが `corners[1]` へ、… 最後のピースが `corners[0]` へ移る）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CornerCycle {
+    pub corners: Vec<Corner>,
+    /// 巡回を1周したときのねじれの合計（0: なし、1: 時計回り、2: 反時計回り）
+    pub twist: u8,
+}
+
+impl std::fmt::Display for CornerCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path: Vec<String> = self.corners.iter().map(|c| c.to_string()).collect();
+        write!(f, "{}点巡回 {}", self.corners.len(), path.join("→"))?;
+        match self.twist {
+            1 => write!(f, "（1周で時計回りにねじれ）"),
+            2 => write!(f, "（1周で反時計回りにねじれ）"),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 位置は変わらずにねじれたコーナー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TwistedCorner {
+    pub corner: Corner,
+    /// ねじれ（1: 時計回り、2: 反時計回り）
+    pub twist: u8,
+}
+
+/// 手順の解析結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlgorithmAnalysis {
+    /// 周期（何回繰り返すと色の配置が元に戻るか）
+    pub order: usize,
+    /// 2個以上のコーナーの巡回
+    pub cycles: Vec<CornerCycle>,
+    /// 位置は変わらずにねじれたコーナー
+    pub twisted: Vec<TwistedCorner>,
+    /// 手順で動くステッカーのインデックス（昇順）
+    pub stickers: Vec<usize>,
+}
+
+impl AlgorithmAnalysis {
+    /// 解析結果の説明文（例: 「3点巡回 UFR→UBR→UBL、2個のコーナーがねじれ」）
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let mut parts: Vec<String> = self.cycles.iter().map(|c| c.to_string()).collect();
+        if !self.twisted.is_empty() {
+            let corners: Vec<String> = self.twisted.iter().map(|t| t.corner.to_string()).collect();
+            parts.push(format!(
+                "{}個のコーナーがねじれ（{}）",
+                self.twisted.len(),
+                corners.join(", ")
+            ));
+        }
+        if parts.is_empty() {
+            return "コーナーは動きません".to_string();
+        }
+        parts.join("、")
+    }
+}
+
+/// 手順の同値判定で許す変換
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EquivalenceOptions {
+    /// 手順の前後のU面の調整（AUF）を無視する
+    pub auf: bool,
+    /// キューブ全体の持ち替え（回転した手順、完成時の向きの違い）を無視する
+    pub rotation: bool,
+    /// 鏡像の手順を同じとみなす
+    pub mirror: bool,
+    /// 逆手順を同じとみなす
+    pub inverse: bool,
+}
+
+impl EquivalenceOptions {
+    /// すべての変換を許す
+    #[must_use]
+    pub fn all() -> Self {
+        Self {
+            auf: true,
+            rotation: true,
+            mirror: true,
+            inverse: true,
+        }
+    }
+}
+
+/// 2つの手順を一致させる変換
+///
+/// `other` を（必要なら逆手順にして）`symmetry` で移し、前に `pre_auf`、後に `post_auf` を
+/// 加えると、元の手順と同じ効果になります。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Equivalence {
+    /// 前に加えるU面の調整
+    pub pre_auf: Option<Move>,
+    /// 後に加えるU面の調整
+    pub post_auf: Option<Move>,
+    /// 手順に施す対称変換（恒等変換なら変換なし）
+    pub symmetry: &'static Symmetry,
+    /// 逆手順にしたか
+    pub inverse: bool,
+}
+
+impl Equivalence {
+    /// 変換なしで同じ効果か
+    #[must_use]
+    pub fn is_identical(&self) -> bool {
+        self.pre_auf.is_none()
+            && self.post_auf.is_none()
+            && self.symmetry.index() == 0
+            && !self.inverse
+    }
+
+    /// 変換の説明文
+    #[must_use]
+    pub fn describe(&self) -> String {
+        if self.is_identical() {
+            return "同じ効果の手順です".to_string();
+        }
+        let mut parts = Vec::new();
+        if self.inverse {
+            parts.push("逆手順".to_string());
+        }
+        if self.symmetry.is_mirror() {
+            parts.push("鏡像".to_string());
+        }
+        if self.symmetry.has_rotation() {
+            parts.push("持ち替え".to_string());
+        }
+        if let Some(mv) = self.pre_auf {
+            parts.push(format!("前に{mv}"));
+        }
+        if let Some(mv) = self.post_auf {
+            parts.push(format!("後に{mv}"));
+        }
+        format!("{}で同じ効果になります", parts.join("・"))
+    }
+}
@@ -0,0 +1,324 @@
+use crate::algorithm::Algorithm;
+use crate::cube::{ColorScheme, Cube, CubeFile};
+use crate::error::{CubeError, Result};
+use crate::solver::{self, DEFAULT_MAX_DEPTH};
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 1つのファイルに複数の局面を書くときの区切り行（`--- 名前` のように名前を付けられる）
+pub const POSITION_SEPARATOR: &str = "---";
+
+/// 一括解法の設定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchOptions {
+    /// 最大探索深度
+    pub max_depth: usize,
+    /// `true` の場合、色のみを揃える（向きは無視）
+    pub ignore_orientation: bool,
+    /// 同時に解く局面の数（1なら順番に解く）
+    ///
+    /// 探索は1局面ごとに大きなメモリを使うため、増やすときはメモリ量に注意してください。
+    pub jobs: usize,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_DEPTH,
+            ignore_orientation: true,
+            jobs: 1,
+        }
+    }
+}
+
+/// 解く局面（名前と、読み込んだ局面または読み込めなかった理由）
+#[derive(Debug, Clone)]
+pub struct BatchInput {
+    /// レポートに書く名前（ファイル名、複数の局面を書いたファイルでは `ファイル名#番号` など）
+    pub name: String,
+    pub cube: std::result::Result<Cube, String>,
+    /// 向きを保存していない局面か（向きも揃えて解くときは、解く前に色の並びから向きを復元する）
+    pub restore_orientations: bool,
+}
+
+/// 1局面の結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchResult {
+    pub name: String,
+    /// 有効な局面だったか
+    pub valid: bool,
+    /// 読み込めなかった・無効だった理由、または解が見つからなかったこと
+    pub error: Option<String>,
+    /// 見つかった最短の解法
+    pub solution: Option<Algorithm>,
+    /// 探索にかかった時間（向きの復元を含む）
+    pub duration: Duration,
+}
+
+/// レポートの形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ReportFormat {
+    /// カンマ区切り（必要な値は `"` で囲む）
+    #[default]
+    Csv,
+    /// タブ区切り（値の中のタブと改行は空白にする）
+    Tsv,
+}
+
+impl ReportFormat {
+    /// ファイルの拡張子から形式を決める（`.tsv` 以外は CSV）
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("tsv") => ReportFormat::Tsv,
+            _ => ReportFormat::Csv,
+        }
+    }
+}
+
+impl std::str::FromStr for ReportFormat {
+    type Err = CubeError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(ReportFormat::Csv),
+            "tsv" => Ok(ReportFormat::Tsv),
+            _ => Err(CubeError::InvalidFormat(format!(
+                "レポートの形式 '{s}' を解釈できません（csv または tsv）"
+            ))),
+        }
+    }
+}
+
+/// 局面ファイル（v1・v2形式）の文字列から局面を読み込む
+///
+/// 標準配色で無効な場合は、GUIの読み込みと同じく色の並びから配色を推定します。
+/// 向きを保存していないファイルは、色の並びから向きを復元します。
+///
+/// 向きの復元は探索を使うため時間がかかります。一括解法では読み込み時には復元せず、
+/// 向きも揃えて解くときだけ `solve_batch` の中で復元します。
+pub fn parse_position(text: &str) -> Result<Cube> {
+    let (cube, restore) = read_position(text)?;
+    if restore {
+        cube.with_restored_orientations()
+    } else {
+        Ok(cube)
+    }
+}
+
+/// 局面ファイルの文字列から、局面と向きを復元する必要があるかを読み込む
+fn read_position(text: &str) -> Result<(Cube, bool)> {
+    let file = CubeFile::parse(text)?;
+    let mut cube = file.cube;
+    if cube.is_valid_state().is_err() {
+        let colors = cube.stickers.map(|s| s.color);
+        if let Ok(inferred) = ColorScheme::infer(&colors) {
+            cube = Cube::from_colors(&inferred.to_standard(&colors))?;
+            return Ok((cube, true));
+        }
+    }
+    cube.is_valid_state()?;
+    Ok((cube, !file.has_orientations))
+}
+
+/// ファイルの文字列を局面ごとに分けて読み込む
+///
+/// `---` の行で区切ると1つのファイルに複数の局面を書けます。`--- 名前` の名前は
+/// `ファイル名#名前` に、名前がなければ `ファイル名#番号`（1から）になります。
+/// 区切りのないファイルは1局面で、名前はファイル名のままです。
+#[must_use]
+pub fn parse_positions(name: &str, text: &str) -> Vec<BatchInput> {
+    let mut sections: Vec<(Option<String>, String)> = vec![(None, String::new())];
+    for line in text.lines() {
+        if let Some(label) = line.trim().strip_prefix(POSITION_SEPARATOR) {
+            let label = label.trim_start_matches('-').trim();
+            sections.push((
+                (!label.is_empty()).then(|| label.to_string()),
+                String::new(),
+            ));
+        } else if let Some((_, body)) = sections.last_mut() {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    // 最初の区切りより前に何も書いていなければ、その部分は局面ではない
+    if sections.len() > 1 && sections[0].1.trim().is_empty() {
+        sections.remove(0);
+    }
+
+    let single = sections.len() == 1;
+    sections
+        .into_iter()
+        .enumerate()
+        .map(|(i, (label, body))| {
+            let position = read_position(&body).map_err(|e| e.to_string());
+            BatchInput {
+                name: match label {
+                    _ if single => name.to_string(),
+                    Some(label) => format!("{name}#{label}"),
+                    None => format!("{name}#{}", i + 1),
+                },
+                restore_orientations: position.as_ref().is_ok_and(|(_, restore)| *restore),
+                cube: position.map(|(cube, _)| cube),
+            }
+        })
+        .collect()
+}
+
+/// ファイルを読み込み、局面ごとに分ける（読み込めないファイルは1つの無効な局面になる）
+#[must_use]
+pub fn read_positions(path: &Path) -> Vec<BatchInput> {
+    let name = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |n| n.to_string_lossy().into_owned(),
+    );
+    match std::fs::read_to_string(path) {
+        Ok(text) => parse_positions(&name, &text),
+        Err(e) => vec![BatchInput {
+            name,
+            cube: Err(CubeError::Io(e).to_string()),
+            restore_orientations: false,
+        }],
+    }
+}
+
+/// 1局面を解く
+fn solve_one(input: &BatchInput, options: &BatchOptions) -> BatchResult {
+    let cube = match &input.cube {
+        Ok(cube) => cube,
+        Err(e) => {
+            return BatchResult {
+                name: input.name.clone(),
+                valid: false,
+                error: Some(e.clone()),
+                solution: None,
+                duration: Duration::ZERO,
+            }
+        }
+    };
+
+    let start = Instant::now();
+    // 向きを無視して解くときは、矢印を復元する必要がない
+    let restored = if input.restore_orientations && !options.ignore_orientation {
+        match cube.with_restored_orientations() {
+            Ok(restored) => Some(restored),
+            Err(e) => {
+                return BatchResult {
+                    name: input.name.clone(),
+                    valid: true,
+                    error: Some(e.to_string()),
+                    solution: None,
+                    duration: start.elapsed(),
+                }
+            }
+        }
+    } else {
+        None
+    };
+    let cube = restored.as_ref().unwrap_or(cube);
+    let solution = solver::solve(cube, options.max_depth, options.ignore_orientation);
+    let duration = start.elapsed();
+    BatchResult {
+        name: input.name.clone(),
+        valid: true,
+        error: (!solution.found)
+            .then(|| format!("{}手以内の解が見つかりません", options.max_depth)),
+        solution: solution.found.then(|| Algorithm::new(solution.moves)),
+        duration,
+    }
+}
+
+/// 局面をまとめて解きます。
+///
+/// 結果は `inputs` と同じ順に返します。無効な局面は探索せず、理由を結果に書いて次へ進みます。
+/// `options.jobs` が2以上なら、その数のスレッドで同時に解きます。
+#[must_use]
+pub fn solve_batch(inputs: &[BatchInput], options: &BatchOptions) -> Vec<BatchResult> {
+    let jobs = options.jobs.clamp(1, inputs.len().max(1));
+    if jobs == 1 {
+        return inputs.iter().map(|i| solve_one(i, options)).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<BatchResult>>> = Mutex::new(vec![None; inputs.len()]);
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(input) = inputs.get(index) else {
+                    break;
+                };
+                let result = solve_one(input, options);
+                if let Ok(mut results) = results.lock() {
+                    results[index] = Some(result);
+                }
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// レポートの見出し
+const REPORT_COLUMNS: [&str; 6] = ["file", "valid", "length", "solution", "seconds", "error"];
+
+/// 結果をレポート（1行目は見出し）として書き出します。
+///
+/// 列はファイル名・有効か（`true`/`false`）・最短手数・解法・探索時間（秒）・エラーの順です。
+/// 解がない場合、手数と解法は空欄になります。
+pub fn write_report(
+    results: &[BatchResult],
+    format: ReportFormat,
+    mut writer: impl Write,
+) -> Result<()> {
+    let write_row = |writer: &mut dyn Write, fields: &[String]| -> std::io::Result<()> {
+        let row: Vec<String> = fields.iter().map(|f| escape_field(f, format)).collect();
+        let separator = match format {
+            ReportFormat::Csv => ",",
+            ReportFormat::Tsv => "\t",
+        };
+        writeln!(writer, "{}", row.join(separator))
+    };
+
+    write_row(&mut writer, &REPORT_COLUMNS.map(str::to_string))?;
+    for result in results {
+        write_row(
+            &mut writer,
+            &[
+                result.name.clone(),
+                result.valid.to_string(),
+                result
+                    .solution
+                    .as_ref()
+                    .map(|s| s.len().to_string())
+                    .unwrap_or_default(),
+                result
+                    .solution
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+                format!("{:.3}", result.duration.as_secs_f64()),
+                result.error.clone().unwrap_or_default(),
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// レポートの1つの値
+fn escape_field(field: &str, format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Csv if field.contains([',', '"', '\n', '\r']) => {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        }
+        ReportFormat::Csv => field.to_string(),
+        ReportFormat::Tsv => field.replace(['\t', '\n', '\r'], " "),
+    }
+}
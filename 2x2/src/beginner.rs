@@ -0,0 +1,390 @@
+use crate::algorithm::Algorithm;
+use crate::cube::{Color, Corner, Cube, Face, Move};
+use crate::error::{CubeError, Result};
+use crate::method::{Method, MethodSolution, MethodStep};
+
+/// 1層目の色
+const FIRST_LAYER_COLOR: Color = Color::White;
+
+/// 1層目のスロット（D面）と、その真上のスロット・コーナーを入れる面
+const SLOTS: [(Corner, Corner, Face); 4] = [
+    (Corner::Dfr, Corner::Ufr, Face::Right),
+    (Corner::Dfl, Corner::Ufl, Face::Front),
+    (Corner::Dbl, Corner::Ubl, Face::Left),
+    (Corner::Dbr, Corner::Ubr, Face::Back),
+];
+
+/// 上の面の色を揃える手順（Sune）
+const SUNE: &str = "R U R' U R U2 R'";
+
+/// 右の2個のコーナーを入れ替える手順（T-perm）
+const T_PERM: &str = "R U R' U' R' F R2 U' R' U' R U R' F'";
+
+/// 同じ手順を繰り返す上限（手順の選び方に誤りがあっても止まるように）
+const MAX_REPEAT: usize = 6;
+
+/// 初心者向けの層ごとの解法で、説明付きの手順を求めます。
+///
+/// 白の面をD面にして1層目をコーナー1個ずつ作り、上の面の色を Sune で、
+/// 上の層の並びを T-perm で揃えます。使う手順は `X U X' U'`・Sune・T-perm と
+/// U面の調整だけです。完成の向きは、白がD面になる向きです。
+/// 無効な局面ではエラーを返します。
+pub fn solve(cube: &Cube) -> Result<MethodSolution> {
+    cube.corner_state()?;
+
+    let mut solver = Beginner {
+        cube: cube.normalized(),
+        steps: Vec::new(),
+    };
+    solver.first_layer();
+    solver.orient_last_layer()?;
+    solver.permute_last_layer()?;
+
+    if !solver.cube.is_solved() {
+        return Err(CubeError::Internal(
+            "初心者向けの手順で完成状態に到達しません".to_string(),
+        ));
+    }
+    Ok(MethodSolution {
+        method: Method::Beginner,
+        steps: solver.steps,
+    })
+}
+
+/// 解きながらステップを記録する
+struct Beginner {
+    cube: Cube,
+    steps: Vec<MethodStep>,
+}
+
+impl Beginner {
+    fn apply(&mut self, algorithm: &Algorithm) {
+        algorithm.apply_to(&mut self.cube);
+    }
+
+    fn push(&mut self, label: &str, explanation: String, algorithm: Algorithm) {
+        if !algorithm.is_empty() {
+            self.steps.push(MethodStep {
+                label: label.to_string(),
+                explanation,
+                algorithm,
+            });
+        }
+    }
+
+    /// 1層目: 白のコーナーを1個ずつ、`X U X' U'` を繰り返して入れる
+    fn first_layer(&mut self) {
+        let anchor = self.choose_anchor();
+        let anchor_slot = SLOTS
+            .iter()
+            .position(|&(slot, _, _)| slot == anchor)
+            .expect("基準のコーナーはD面のスロットにあります");
+
+        // 基準のコーナーの次のスロットから順に入れる
+        for offset in 1..SLOTS.len() {
+            let (slot, above, face) = SLOTS[(anchor_slot + offset) % SLOTS.len()];
+            let colors = home_colors(&self.cube, anchor, slot);
+            if self.is_placed(slot, &colors) {
+                continue;
+            }
+
+            let insert = insert_algorithm(face);
+            let mut moves = Vec::new();
+            let mut notes = Vec::new();
+
+            // 別のスロットに入っていれば、そのスロットの手順で上の層に出す
+            let current = self.find_piece(&colors);
+            if let Some(&(_, _, kick_face)) =
+                SLOTS.iter().find(|&&(s, _, _)| s == current && s != slot)
+            {
+                let kick = insert_algorithm(kick_face);
+                self.apply(&kick);
+                notes.push(format!("{current} から {kick} で上の層に出し"));
+                moves.extend(kick.moves);
+            }
+
+            // 上の層にあれば、U を回してスロットの真上に移す
+            if self.find_piece(&colors) != slot {
+                let turn = (0..4u8)
+                    .filter_map(|qt| Move::from_face(Face::Up, qt))
+                    .find(|&mv| {
+                        let mut turned = self.cube.clone();
+                        turned.apply_move(mv);
+                        find_piece(&turned, &colors) == above
+                    });
+                if let Some(turn) = turn {
+                    self.cube.apply_move(turn);
+                    notes.push(format!("{turn} で {above} に移し"));
+                    moves.push(turn);
+                }
+            }
+
+            let mut repeat = 0;
+            while !self.is_placed(slot, &colors) && repeat < MAX_REPEAT {
+                self.apply(&insert);
+                moves.extend(insert.moves.iter().copied());
+                repeat += 1;
+            }
+            notes.push(format!("{insert} を{repeat}回繰り返す"));
+
+            let label = format!("1層目: {}のコーナー", color_names(&colors));
+            let explanation = format!(
+                "{}のコーナーを {slot} に入れます。{}",
+                color_names(&colors),
+                notes.join("、")
+            );
+            self.push(&label, explanation, Algorithm::new(moves));
+        }
+    }
+
+    /// 1層目の基準にするコーナー（白がD面を向いているもの。なければ DFR に入れる）
+    fn choose_anchor(&mut self) -> Corner {
+        let white_down = |cube: &Cube| {
+            SLOTS
+                .iter()
+                .map(|&(slot, _, _)| slot)
+                .find(|slot| cube.stickers[slot.stickers()[0]].color == FIRST_LAYER_COLOR)
+        };
+        if let Some(slot) = white_down(&self.cube) {
+            return slot;
+        }
+
+        // 白のコーナーを1個、DFR に入れる
+        let (slot, above, face) = SLOTS[0];
+        let piece = Corner::ALL
+            .into_iter()
+            .find(|&c| piece_colors(&self.cube, c).contains(&FIRST_LAYER_COLOR))
+            .expect("白のコーナーがあります");
+        let colors = piece_colors(&self.cube, piece);
+        let mut moves = Vec::new();
+        if piece.stickers()[0] >= 4 {
+            // D面の層で白が横を向いている: そのスロットから上の層に出す
+            let kick_face = SLOTS
+                .iter()
+                .find(|&&(s, _, _)| s == piece)
+                .map(|&(_, _, f)| f)
+                .expect("D面のコーナーです");
+            let kick = insert_algorithm(kick_face);
+            self.apply(&kick);
+            moves.extend(kick.moves);
+        }
+        if let Some(turn) = (0..4u8)
+            .filter_map(|qt| Move::from_face(Face::Up, qt))
+            .find(|&mv| {
+                let mut turned = self.cube.clone();
+                turned.apply_move(mv);
+                find_piece(&turned, &colors) == above
+            })
+        {
+            self.cube.apply_move(turn);
+            moves.push(turn);
+        }
+        let insert = insert_algorithm(face);
+        let mut repeat = 0;
+        while self.cube.stickers[slot.stickers()[0]].color != FIRST_LAYER_COLOR
+            && repeat < MAX_REPEAT
+        {
+            self.apply(&insert);
+            moves.extend(insert.moves.iter().copied());
+            repeat += 1;
+        }
+        let label = format!("1層目: {}のコーナー", color_names(&colors));
+        let explanation = format!(
+            "白がD面を向いたコーナーがないので、{}のコーナーを {above} に移し、{insert} を白が下を向くまで繰り返します。",
+            color_names(&colors)
+        );
+        self.push(&label, explanation, Algorithm::new(moves));
+        slot
+    }
+
+    /// スロットに正しいコーナーが白を下にして入っているか
+    fn is_placed(&self, slot: Corner, colors: &[Color; 3]) -> bool {
+        self.cube.stickers[slot.stickers()[0]].color == FIRST_LAYER_COLOR
+            && same_piece(&piece_colors(&self.cube, slot), colors)
+    }
+
+    fn find_piece(&self, colors: &[Color; 3]) -> Corner {
+        find_piece(&self.cube, colors)
+    }
+
+    /// 2ステップ目: 上の面の色を Sune で揃える
+    fn orient_last_layer(&mut self) -> Result<()> {
+        let top = top_color(&self.cube)?;
+        let sune = Algorithm::parse(SUNE)?;
+        let mut count = 0;
+        while !self.top_is_oriented(top) {
+            if count >= MAX_REPEAT {
+                return Err(CubeError::Internal("上の面の色が揃いません".to_string()));
+            }
+            let facing_up = (0..4)
+                .filter(|&i| self.cube.stickers[i].color == top)
+                .count();
+            // 上を向いた数で、左前 (UFL) に置くコーナーを決める
+            let (rule, sticker) = match facing_up {
+                1 => ("1個だけ上を向いているコーナーを左前に置いて", 2),
+                0 => ("左面の左前に上の面の色が来るように置いて", 9),
+                _ => ("前面の左前に上の面の色が来るように置いて", 16),
+            };
+            let turn = (0..4u8)
+                .map(|qt| Move::from_face(Face::Up, qt))
+                .find(|mv| {
+                    let mut turned = self.cube.clone();
+                    if let Some(mv) = mv {
+                        turned.apply_move(*mv);
+                    }
+                    turned.stickers[sticker].color == top
+                })
+                .ok_or_else(|| CubeError::Internal("上の面の向きを判定できません".to_string()))?;
+
+            let mut moves: Vec<Move> = turn.into_iter().collect();
+            moves.extend(sune.moves.iter().copied());
+            let algorithm = Algorithm::new(moves);
+            self.apply(&algorithm);
+            count += 1;
+            let explanation = format!(
+                "上の面の{}が{}個上を向いています。{}{}Sune ({sune}) を回します。",
+                top.label(),
+                facing_up,
+                rule,
+                turn.map_or(String::new(), |mv| format!("（{mv}）"))
+            );
+            self.push("上の面の色", explanation, algorithm);
+        }
+        Ok(())
+    }
+
+    fn top_is_oriented(&self, top: Color) -> bool {
+        (0..4).all(|i| self.cube.stickers[i].color == top)
+    }
+
+    /// 3ステップ目: 上の層の並びを T-perm で揃え、U を回して合わせる
+    fn permute_last_layer(&mut self) -> Result<()> {
+        let t_perm = Algorithm::parse(T_PERM)?;
+        let mut count = 0;
+        while !self.top_is_permuted() {
+            if count >= MAX_REPEAT {
+                return Err(CubeError::Internal("上の層の並びが揃いません".to_string()));
+            }
+            // 側面の上段の2枚が同じ色（ヘッドライト）を左面に置く
+            let turn = (0..4u8).map(|qt| Move::from_face(Face::Up, qt)).find(|mv| {
+                let mut turned = self.cube.clone();
+                if let Some(mv) = mv {
+                    turned.apply_move(*mv);
+                }
+                turned.stickers[8].color == turned.stickers[9].color
+            });
+            let (explanation, moves) = match turn {
+                Some(turn) => (
+                    format!(
+                        "同じ色が並んだ側面（ヘッドライト）を左に置いて{}T-perm ({t_perm}) で右の2個を入れ替えます。",
+                        turn.map_or(String::new(), |mv| format!("（{mv}）"))
+                    ),
+                    turn.into_iter().chain(t_perm.moves.iter().copied()).collect(),
+                ),
+                None => (
+                    format!(
+                        "ヘッドライトがないので、まず T-perm ({t_perm}) を回してヘッドライトを作ります。"
+                    ),
+                    t_perm.moves.clone(),
+                ),
+            };
+            let algorithm = Algorithm::new(moves);
+            self.apply(&algorithm);
+            count += 1;
+            self.push("上の層の並び", explanation, algorithm);
+        }
+
+        let turn = (1..4u8)
+            .filter_map(|qt| Move::from_face(Face::Up, qt))
+            .find(|&mv| {
+                let mut turned = self.cube.clone();
+                turned.apply_move(mv);
+                turned.is_solved()
+            });
+        if let Some(turn) = turn {
+            self.cube.apply_move(turn);
+            self.push(
+                "仕上げ",
+                format!("{turn} を回して上の層を下の層に合わせます。"),
+                Algorithm::new(vec![turn]),
+            );
+        }
+        Ok(())
+    }
+
+    /// 上の層の4個が、互いに正しい並びか（側面の上段がすべて同じ色）
+    fn top_is_permuted(&self) -> bool {
+        [8, 12, 16, 20]
+            .iter()
+            .all(|&i| self.cube.stickers[i].color == self.cube.stickers[i + 1].color)
+    }
+}
+
+/// 面 `X` の下のスロットにコーナーを入れる手順 `X U X' U'`
+fn insert_algorithm(face: Face) -> Algorithm {
+    let turn = |qt| Move::from_face(face, qt).expect("1回転の操作はあります");
+    Algorithm::new(vec![turn(1), Move::U, turn(3), Move::Up])
+}
+
+/// スロットにあるコーナーの色（U面・D面のステッカーから時計回り）
+fn piece_colors(cube: &Cube, slot: Corner) -> [Color; 3] {
+    slot.stickers().map(|i| cube.stickers[i].color)
+}
+
+/// 同じ色の組み合わせのコーナーか
+fn same_piece(a: &[Color; 3], b: &[Color; 3]) -> bool {
+    a.iter().all(|c| b.contains(c))
+}
+
+/// 指定した色の組み合わせのコーナーがあるスロット
+fn find_piece(cube: &Cube, colors: &[Color; 3]) -> Corner {
+    Corner::ALL
+        .into_iter()
+        .find(|&slot| same_piece(&piece_colors(cube, slot), colors))
+        .expect("有効な局面にはすべてのコーナーがあります")
+}
+
+/// 基準のコーナーに対して、スロットに入るべきコーナーの色
+///
+/// 基準と面を共有するスロットには、その面の基準の色を持つコーナーが入ります。
+/// 対角のスロットには、基準の横の色をどちらも持たないコーナーが入ります。
+fn home_colors(cube: &Cube, anchor_slot: Corner, slot: Corner) -> [Color; 3] {
+    let shared: Vec<Color> = anchor_slot.stickers()[1..]
+        .iter()
+        .filter(|&&i| slot.stickers()[1..].iter().any(|&j| j / 4 == i / 4))
+        .map(|&i| cube.stickers[i].color)
+        .collect();
+    let anchor_sides = [
+        cube.stickers[anchor_slot.stickers()[1]].color,
+        cube.stickers[anchor_slot.stickers()[2]].color,
+    ];
+    Corner::ALL
+        .into_iter()
+        .map(|c| piece_colors(cube, c))
+        .find(|colors| {
+            colors.contains(&FIRST_LAYER_COLOR)
+                && !same_piece(colors, &piece_colors(cube, anchor_slot))
+                && anchor_sides
+                    .iter()
+                    .all(|side| colors.contains(side) == shared.contains(side))
+        })
+        .expect("白のコーナーは4個あります")
+}
+
+/// 上の層の色（1層目の色の反対。上の層の4個に共通する色）
+fn top_color(cube: &Cube) -> Result<Color> {
+    Color::ALL
+        .into_iter()
+        .find(|&color| {
+            [Corner::Ufr, Corner::Ufl, Corner::Ubl, Corner::Ubr]
+                .iter()
+                .all(|&c| piece_colors(cube, c).contains(&color))
+        })
+        .ok_or_else(|| CubeError::Internal("1層目が揃っていません".to_string()))
+}
+
+/// コーナーの色の名前（例: `白・赤・緑`）
+fn color_names(colors: &[Color; 3]) -> String {
+    let names: Vec<&str> = colors.iter().map(|c| c.label()).collect();
+    names.join("・")
+}
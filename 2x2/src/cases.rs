@@ -0,0 +1,287 @@
+use crate::algorithm::Algorithm;
+use crate::cube::symmetry::Symmetry;
+use crate::cube::{Color, Cube, Face, Move};
+use rustc_hash::FxHashMap;
+use std::sync::OnceLock;
+
+/// 同梱のケース表（`セット<TAB>ケース名<TAB>手順`、`#` 以降はコメント）
+const CASE_TABLE: &str = include_str!("cases.tsv");
+
+/// U面・D面の調整の候補（調整なしを先頭に）
+const U_ADJUSTMENTS: [&[Move]; 4] = [&[], &[Move::U], &[Move::U2], &[Move::Up]];
+const D_ADJUSTMENTS: [&[Move]; 4] = [&[], &[Move::D], &[Move::D2], &[Move::Dp]];
+
+/// ケースのセット（解法の段階）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CaseSet {
+    /// 下の層が完成した状態から上の層を揃える
+    Cll,
+    /// 下の層の隣り合う2個が入れ替わった状態から、上下の層を揃える
+    Eg1,
+    /// 下の層の対角の2個が入れ替わった状態から、上下の層を揃える
+    Eg2,
+    /// 下の面がそろった状態から、上の面の色を揃える
+    OrtegaOll,
+    /// 上下の面がそろった状態から、上下の層を揃える
+    OrtegaPbl,
+}
+
+impl CaseSet {
+    /// すべてのセット（表示の順）
+    pub const ALL: [CaseSet; 5] = [
+        CaseSet::Cll,
+        CaseSet::Eg1,
+        CaseSet::Eg2,
+        CaseSet::OrtegaOll,
+        CaseSet::OrtegaPbl,
+    ];
+
+    /// 表示とケース表で使う名前
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            CaseSet::Cll => "CLL",
+            CaseSet::Eg1 => "EG-1",
+            CaseSet::Eg2 => "EG-2",
+            CaseSet::OrtegaOll => "Ortega OLL",
+            CaseSet::OrtegaPbl => "Ortega PBL",
+        }
+    }
+}
+
+impl std::fmt::Display for CaseSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// ケース表の1行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub set: CaseSet,
+    pub name: String,
+    /// 下の層をD面、DBLを左奥下に置いた持ち方で揃える手順
+    pub algorithm: Algorithm,
+}
+
+/// 認識したケース
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecognizedCase {
+    pub case: &'static Case,
+    /// 上にする面（キューブの今の持ち方での面。手順の面もこの持ち方に読み替えています）
+    pub top: Face,
+    /// 手順の前に上の層（PBLでは下の層も）を回す調整
+    pub auf: Algorithm,
+    /// ケースの手順
+    pub algorithm: Algorithm,
+    /// 手順の後に上下の層を回す調整
+    pub post_auf: Algorithm,
+}
+
+impl RecognizedCase {
+    /// 調整を含めた、完成までの手順
+    #[must_use]
+    pub fn solution(&self) -> Algorithm {
+        let moves = [&self.auf, &self.algorithm, &self.post_auf]
+            .into_iter()
+            .flat_map(|alg| alg.moves.iter().copied())
+            .collect();
+        Algorithm::new(moves)
+    }
+
+    /// 表示用の説明（例: `CLL Sune 1, AUF U'`）
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let mut text = format!("{} {}", self.case.set, self.case.name);
+        if !self.auf.is_empty() {
+            text.push_str(&format!(", AUF {}", self.auf));
+        }
+        text
+    }
+}
+
+/// ケース表のすべてのケース
+pub fn cases() -> &'static [Case] {
+    &tables().cases
+}
+
+/// 局面が当てはまるケースを、セットごとに1つずつ探します（`CaseSet::ALL` の順）。
+///
+/// 持ち替え（どの面を下にしているか）と配色の向きを問わず認識します。
+/// Ortega OLL は上の面の色の並び、その他のセットは局面全体で照合します。
+/// 完成している局面や、どのケースにも当てはまらない局面では空になります。
+#[must_use]
+pub fn recognize(cube: &Cube) -> Vec<RecognizedCase> {
+    let tables = tables();
+    let mut found: Vec<RecognizedCase> = Vec::new();
+
+    for hold in Symmetry::rotations() {
+        let held = hold.transform(cube);
+        for scheme in Symmetry::rotations() {
+            let mut view = held.clone();
+            for sticker in &mut view.stickers {
+                sticker.color = scheme.recolor(sticker.color);
+            }
+            for (u, d) in adjustments() {
+                let mut adjusted = view.clone();
+                for &mv in u.iter().chain(d) {
+                    adjusted.apply_move(mv);
+                }
+
+                let mut matches: Vec<(usize, &[Move])> = tables
+                    .states
+                    .get(&color_key(&adjusted))
+                    .map(|m| m.iter().map(|(i, post)| (*i, post.as_slice())).collect())
+                    .unwrap_or_default();
+                if d.is_empty() && bottom_is_built(&adjusted) {
+                    if let Some((i, post)) = tables.orientations.get(&top_mask(&adjusted)) {
+                        matches.push((*i, post.as_slice()));
+                    }
+                }
+
+                for (index, post) in matches {
+                    let case = &tables.cases[index];
+                    if found.iter().any(|r| r.case.set == case.set) {
+                        continue;
+                    }
+                    let pre: Vec<Move> = u.iter().chain(d).copied().collect();
+                    found.push(RecognizedCase {
+                        case,
+                        top: unheld_face(hold, Face::Up),
+                        auf: unheld(hold, &Algorithm::new(pre)),
+                        algorithm: unheld(hold, &case.algorithm),
+                        post_auf: unheld(hold, &Algorithm::new(post.to_vec())),
+                    });
+                }
+            }
+        }
+    }
+    found.sort_by_key(|r| r.case.set);
+    found
+}
+
+/// 認識に使う調整の組（U面の調整、D面の調整）
+fn adjustments() -> impl Iterator<Item = (&'static [Move], &'static [Move])> {
+    D_ADJUSTMENTS
+        .into_iter()
+        .flat_map(|d| U_ADJUSTMENTS.into_iter().map(move |u| (u, d)))
+}
+
+/// 持ち替えた後の面 `face` が、持ち替える前のどの面か
+fn unheld_face(hold: &Symmetry, face: Face) -> Face {
+    let map = hold.face_map();
+    Face::ALL
+        .into_iter()
+        .find(|f| map[f.index()] == face)
+        .expect("持ち替えは面の並べ替えです")
+}
+
+/// 持ち替えた後の手順を、持ち替える前の面の手順に読み替える
+pub(crate) fn unheld(hold: &Symmetry, algorithm: &Algorithm) -> Algorithm {
+    Algorithm::new(
+        algorithm
+            .moves
+            .iter()
+            .map(|mv| mv.with_face(unheld_face(hold, mv.face())))
+            .collect(),
+    )
+}
+
+/// 色の並びによる照合キー
+fn color_key(cube: &Cube) -> [u8; 24] {
+    cube.stickers.map(|s| s.color as u8)
+}
+
+/// D面がすべて標準配色のD面の色か
+fn bottom_is_built(cube: &Cube) -> bool {
+    let bottom = Cube::new().stickers[Face::Down.index() * 4].color;
+    cube.stickers[Face::Down.index() * 4..][..4]
+        .iter()
+        .all(|s| s.color == bottom)
+}
+
+/// 標準配色のU面の色のステッカーの位置（ビットの集合）
+fn top_mask(cube: &Cube) -> u32 {
+    let top: Color = Cube::new().stickers[Face::Up.index() * 4].color;
+    cube.stickers
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.color == top)
+        .fold(0, |mask, (i, _)| mask | (1 << i))
+}
+
+/// ケース表と照合用の索引
+struct Tables {
+    cases: Vec<Case>,
+    /// 局面の色 → (ケースの番号, 手順の後の調整)
+    states: FxHashMap<[u8; 24], Vec<(usize, Vec<Move>)>>,
+    /// Ortega OLL: U面の色の位置 → (ケースの番号, 手順の後の調整)
+    orientations: FxHashMap<u32, (usize, Vec<Move>)>,
+}
+
+static TABLES: OnceLock<Tables> = OnceLock::new();
+
+fn tables() -> &'static Tables {
+    TABLES.get_or_init(|| {
+        let cases = parse_table(CASE_TABLE);
+        let mut states: FxHashMap<[u8; 24], Vec<(usize, Vec<Move>)>> = FxHashMap::default();
+        let mut orientations = FxHashMap::default();
+
+        for (index, case) in cases.iter().enumerate() {
+            for (u, d) in adjustments() {
+                // 手順と調整 (U, D) で完成する局面 = 完成状態に調整の逆と手順の逆を適用した局面
+                let setup: Vec<Move> = Algorithm::new(u.iter().chain(d).copied().collect())
+                    .inverse()
+                    .moves
+                    .into_iter()
+                    .chain(case.algorithm.inverse().moves)
+                    .collect();
+                let state = Cube::from_moves(&setup);
+                let post: Vec<Move> = u.iter().chain(d).copied().collect();
+
+                if case.set == CaseSet::OrtegaOll {
+                    if d.is_empty() {
+                        orientations
+                            .entry(top_mask(&state))
+                            .or_insert((index, post));
+                    }
+                    continue;
+                }
+                let entry = states.entry(color_key(&state)).or_default();
+                if !entry.iter().any(|(i, _)| cases[*i].set == case.set) {
+                    entry.push((index, post));
+                }
+            }
+        }
+        Tables {
+            cases,
+            states,
+            orientations,
+        }
+    })
+}
+
+/// 同梱のケース表を読み込む
+fn parse_table(text: &str) -> Vec<Case> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [set, name, algorithm] = fields[..] else {
+                panic!("ケース表の行の形式が正しくありません: {line}");
+            };
+            let set = CaseSet::ALL
+                .into_iter()
+                .find(|s| s.name() == set)
+                .unwrap_or_else(|| panic!("ケース表のセットが不明です: {set}"));
+            let algorithm = Algorithm::parse(algorithm)
+                .unwrap_or_else(|e| panic!("ケース表の手順を解釈できません: {e}"));
+            Case {
+                set,
+                name: name.to_string(),
+                algorithm,
+            }
+        })
+        .collect()
+}
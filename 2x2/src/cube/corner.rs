@@ -0,0 +1,208 @@
+use super::{Color, Cube};
+use crate::error::{CubeError, Result};
+
+/// コーナーの位置（スロット）
+///
+/// 2x2キューブは8個のコーナーピースのみで構成されます。
+/// 各バリアントはスロットの位置を表すと同時に、完成状態でそのスロットにある
+/// ピースの識別子としても使用します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Corner {
+    Ufr,
+    Ufl,
+    Ubl,
+    Ubr,
+    Dfr,
+    Dfl,
+    Dbl,
+    Dbr,
+}
+
+impl Corner {
+    /// すべてのコーナー
+    pub const ALL: [Corner; 8] = [
+        Corner::Ufr,
+        Corner::Ufl,
+        Corner::Ubl,
+        Corner::Ubr,
+        Corner::Dfr,
+        Corner::Dfl,
+        Corner::Dbl,
+        Corner::Dbr,
+    ];
+
+    /// コーナーの番号（0-7、`ALL` の並び順）
+    #[must_use]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// このコーナーを構成する3枚のステッカーのインデックス
+    ///
+    /// 先頭はU面またはD面のステッカーで、以降はコーナーを外側から見て時計回りの順です。
+    #[must_use]
+    pub fn stickers(self) -> [usize; 3] {
+        match self {
+            Corner::Ufr => [3, 12, 17],
+            Corner::Ufl => [2, 16, 9],
+            Corner::Ubl => [0, 8, 21],
+            Corner::Ubr => [1, 20, 13],
+            Corner::Dfr => [5, 19, 14],
+            Corner::Dfl => [4, 11, 18],
+            Corner::Dbl => [6, 23, 10],
+            Corner::Dbr => [7, 15, 22],
+        }
+    }
+
+    /// ステッカーのインデックスから、そのステッカーが属するコーナーと
+    /// コーナー内での位置（0-2）を取得
+    #[must_use]
+    pub fn from_sticker(index: usize) -> Option<(Corner, usize)> {
+        Corner::ALL.iter().find_map(|&corner| {
+            corner
+                .stickers()
+                .iter()
+                .position(|&i| i == index)
+                .map(|pos| (corner, pos))
+        })
+    }
+}
+
+impl std::fmt::Display for Corner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Corner::Ufr => "UFR",
+            Corner::Ufl => "UFL",
+            Corner::Ubl => "UBL",
+            Corner::Ubr => "UBR",
+            Corner::Dfr => "DFR",
+            Corner::Dfl => "DFL",
+            Corner::Dbl => "DBL",
+            Corner::Dbr => "DBR",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl std::str::FromStr for Corner {
+    type Err = CubeError;
+
+    /// 面の文字（例: `UFR`）からコーナーを取得します。文字の順序と大文字・小文字は問いません。
+    fn from_str(s: &str) -> Result<Self> {
+        let mut letters: Vec<char> = s.trim().to_uppercase().chars().collect();
+        letters.sort_unstable();
+        Corner::ALL
+            .into_iter()
+            .find(|corner| {
+                let mut name: Vec<char> = corner.to_string().chars().collect();
+                name.sort_unstable();
+                name == letters
+            })
+            .ok_or_else(|| CubeError::InvalidFormat(format!("コーナー '{s}' を解釈できません")))
+    }
+}
+
+/// 全コーナーの配置（各スロットにどのピースが、どのねじれで入っているか）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CornerState {
+    /// 各スロット（`Corner::ALL` の順）に入っているピース
+    pub pieces: [Corner; 8],
+    /// 各スロットのピースのねじれ（0: 正常、1: 時計回り、2: 反時計回り）
+    pub twists: [u8; 8],
+}
+
+impl CornerState {
+    /// 完成状態のコーナー配置
+    #[must_use]
+    pub fn solved() -> Self {
+        Self {
+            pieces: Corner::ALL,
+            twists: [0; 8],
+        }
+    }
+
+    /// 色配列からコーナー配置を解析します。
+    ///
+    /// 各スロットの3色から、どのピースが入っているかとねじれを求めます。
+    /// 存在しない色の組み合わせ、鏡像になったピース、同じピースの重複はエラーになります。
+    /// ねじれの合計はここでは検証しません（`validation::is_valid_state` が行います）。
+    pub fn from_colors(colors: &[Color; 24]) -> Result<Self> {
+        let home = home_colors();
+        let mut pieces = [Corner::Ufr; 8];
+        let mut twists = [0u8; 8];
+        let mut seen = [false; 8];
+
+        for slot in Corner::ALL {
+            let slot_colors = slot.stickers().map(|i| colors[i]);
+
+            let (piece, twist) = identify_piece(slot_colors, &home)
+                .ok_or_else(|| invalid_corner_error(slot, slot_colors, &home))?;
+
+            if seen[piece.index()] {
+                return Err(CubeError::InvalidState(format!(
+                    "{}のピース（{:?}）が複数あります",
+                    piece,
+                    home[piece.index()]
+                )));
+            }
+            seen[piece.index()] = true;
+            pieces[slot.index()] = piece;
+            twists[slot.index()] = twist;
+        }
+
+        Ok(Self { pieces, twists })
+    }
+
+    /// ねじれの合計が3の倍数か（物理的に可能なねじれか）
+    #[must_use]
+    pub fn twist_sum_is_valid(&self) -> bool {
+        self.twists.iter().map(|&t| t as u32).sum::<u32>() % 3 == 0
+    }
+}
+
+/// 完成状態で各コーナーピースが持つ色（`Corner::stickers` の順）
+pub(crate) fn home_colors() -> [[Color; 3]; 8] {
+    let solved = Cube::new();
+    Corner::ALL.map(|corner| corner.stickers().map(|i| solved.stickers[i].color))
+}
+
+/// スロットの3色（`Corner::stickers` の順）から、ピースとねじれを特定
+pub(crate) fn identify_piece(
+    slot_colors: [Color; 3],
+    home: &[[Color; 3]; 8],
+) -> Option<(Corner, u8)> {
+    Corner::ALL.iter().find_map(|&piece| {
+        let piece_colors = home[piece.index()];
+        (0..3).find_map(|twist| {
+            let matches = (0..3).all(|k| slot_colors[(twist + k) % 3] == piece_colors[k]);
+            matches.then_some((piece, twist as u8))
+        })
+    })
+}
+
+/// 該当するピースが見つからなかった場合のエラーを作成
+fn invalid_corner_error(
+    slot: Corner,
+    slot_colors: [Color; 3],
+    home: &[[Color; 3]; 8],
+) -> CubeError {
+    let mut sorted = slot_colors;
+    sorted.sort_by_key(|c| *c as u8);
+    let mirrored = home.iter().any(|piece_colors| {
+        let mut p = *piece_colors;
+        p.sort_by_key(|c| *c as u8);
+        p == sorted
+    });
+
+    if mirrored {
+        CubeError::InvalidState(format!(
+            "{}の色の並び {:?} が鏡像になっています（ステッカーの貼り間違いの可能性があります）",
+            slot, slot_colors
+        ))
+    } else {
+        CubeError::InvalidState(format!(
+            "{}の色の組み合わせ {:?} は存在しません",
+            slot, slot_colors
+        ))
+    }
+}
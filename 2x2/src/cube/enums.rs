@@ -9,6 +9,50 @@ pub enum Face {
     Back,
 }
 
+impl Face {
+    /// すべての面（ステッカーのインデックス順: U, D, L, R, F, B）
+    pub const ALL: [Face; 6] = [
+        Face::Up,
+        Face::Down,
+        Face::Left,
+        Face::Right,
+        Face::Front,
+        Face::Back,
+    ];
+
+    /// 面の番号（0-5）。この面のステッカーは `index() * 4` から4枚です。
+    #[must_use]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// 表示用の面の名前
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Face::Up => "Up (上面)",
+            Face::Down => "Down (下面)",
+            Face::Left => "Left (左面)",
+            Face::Right => "Right (右面)",
+            Face::Front => "Front (前面)",
+            Face::Back => "Back (背面)",
+        }
+    }
+
+    /// 反対側の面
+    #[must_use]
+    pub fn opposite(self) -> Face {
+        match self {
+            Face::Up => Face::Down,
+            Face::Down => Face::Up,
+            Face::Left => Face::Right,
+            Face::Right => Face::Left,
+            Face::Front => Face::Back,
+            Face::Back => Face::Front,
+        }
+    }
+}
+
 /// ステッカーの色
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Color {
@@ -21,8 +65,58 @@ pub enum Color {
     Gray, // 未設定のセル用
 }
 
+impl Color {
+    /// ステッカーに使われる6色（未設定用のグレーを除く）
+    pub const ALL: [Color; 6] = [
+        Color::White,
+        Color::Yellow,
+        Color::Green,
+        Color::Blue,
+        Color::Red,
+        Color::Orange,
+    ];
+
+    /// 表示用の色の名前
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Color::White => "白",
+            Color::Yellow => "黄",
+            Color::Green => "緑",
+            Color::Blue => "青",
+            Color::Red => "赤",
+            Color::Orange => "橙",
+            Color::Gray => "未設定",
+        }
+    }
+
+    /// ファイル・フェイスレット・セッションで使う色の文字（未設定のグレーは `-`）
+    #[must_use]
+    pub fn letter(self) -> char {
+        match self {
+            Color::White => 'W',
+            Color::Yellow => 'Y',
+            Color::Green => 'G',
+            Color::Blue => 'B',
+            Color::Red => 'R',
+            Color::Orange => 'O',
+            Color::Gray => '-',
+        }
+    }
+
+    /// 色の文字から色を求めます（大文字・小文字は区別しません。`-` は未設定のグレー）。
+    #[must_use]
+    pub fn from_letter(c: char) -> Option<Color> {
+        Color::ALL
+            .into_iter()
+            .chain([Color::Gray])
+            .find(|color| color.letter() == c.to_ascii_uppercase())
+    }
+}
+
 /// ステッカー（色と向き情報を持つ）
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sticker {
     pub color: Color,
     /// 向き（0-3の値で、90度単位の回転を表す）
@@ -98,6 +192,43 @@ impl Move {
         ]
     }
 
+    /// 回転する面
+    #[must_use]
+    pub fn face(self) -> Face {
+        match self {
+            Move::R | Move::Rp | Move::R2 => Face::Right,
+            Move::L | Move::Lp | Move::L2 => Face::Left,
+            Move::U | Move::Up | Move::U2 => Face::Up,
+            Move::D | Move::Dp | Move::D2 => Face::Down,
+            Move::F | Move::Fp | Move::F2 => Face::Front,
+            Move::B | Move::Bp | Move::B2 => Face::Back,
+        }
+    }
+
+    /// 時計回りに90度回す回数（1: 時計回り、2: 180度、3: 反時計回り）
+    #[must_use]
+    pub fn quarter_turns(self) -> u8 {
+        match self {
+            Move::R | Move::L | Move::U | Move::D | Move::F | Move::B => 1,
+            Move::R2 | Move::L2 | Move::U2 | Move::D2 | Move::F2 | Move::B2 => 2,
+            Move::Rp | Move::Lp | Move::Up | Move::Dp | Move::Fp | Move::Bp => 3,
+        }
+    }
+
+    /// 面と時計回りに90度回す回数から回転操作を取得（回数が4の倍数なら `None`）
+    #[must_use]
+    pub fn from_face(face: Face, quarter_turns: u8) -> Option<Move> {
+        Move::all_moves()
+            .into_iter()
+            .find(|mv| mv.face() == face && mv.quarter_turns() == quarter_turns % 4)
+    }
+
+    /// 回転の量を保ったまま、回す面を置き換えた回転操作
+    #[must_use]
+    pub fn with_face(self, face: Face) -> Move {
+        Move::from_face(face, self.quarter_turns()).expect("回転の量は1-3です")
+    }
+
     /// 逆操作を取得
     #[must_use]
     pub fn inverse(self) -> Move {
@@ -124,6 +255,25 @@ impl Move {
     }
 }
 
+impl std::str::FromStr for Move {
+    type Err = crate::error::CubeError;
+
+    /// 記号（`R`, `R'`, `R2` など）から回転操作を取得します。`’` と `2'` も受け付けます。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.replace('’', "'");
+        Move::all_moves()
+            .into_iter()
+            .find(|mv| {
+                let notation = mv.to_string();
+                normalized == notation
+                    || (notation.ends_with('2') && normalized == format!("{notation}'"))
+            })
+            .ok_or_else(|| {
+                crate::error::CubeError::InvalidFormat(format!("回転記号 '{s}' を解釈できません"))
+            })
+    }
+}
+
 impl std::fmt::Display for Move {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
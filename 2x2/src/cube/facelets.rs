@@ -0,0 +1,233 @@
+use super::{Color, ColorScheme, Cube, Face};
+use crate::error::{parse_error, CubeError, Result};
+
+/// 1つの面の4枚のステッカーを読む順
+///
+/// 面は展開図と同じ向き（U面はB面を上、D面はF面を上、側面はU面を上）で見ます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ReadingDirection {
+    /// 左上→右上→左下→右下（行ごと）
+    #[default]
+    Rows,
+    /// 左上→左下→右上→右下（列ごと）
+    Columns,
+    /// 左上→右上→右下→左下（時計回り）
+    Clockwise,
+    /// 左上→左下→右下→右上（反時計回り）
+    CounterClockwise,
+}
+
+impl ReadingDirection {
+    /// すべての読み方
+    pub const ALL: [ReadingDirection; 4] = [
+        ReadingDirection::Rows,
+        ReadingDirection::Columns,
+        ReadingDirection::Clockwise,
+        ReadingDirection::CounterClockwise,
+    ];
+
+    /// 表示用の名前
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            ReadingDirection::Rows => "行ごと",
+            ReadingDirection::Columns => "列ごと",
+            ReadingDirection::Clockwise => "時計回り",
+            ReadingDirection::CounterClockwise => "反時計回り",
+        }
+    }
+
+    /// 読む順の n 番目が、面の中の何番目（左上・右上・左下・右下の順）のステッカーか
+    fn positions(self) -> [usize; 4] {
+        match self {
+            ReadingDirection::Rows => [0, 1, 2, 3],
+            ReadingDirection::Columns => [0, 2, 1, 3],
+            ReadingDirection::Clockwise => [0, 1, 3, 2],
+            ReadingDirection::CounterClockwise => [0, 2, 3, 1],
+        }
+    }
+}
+
+/// フェイスレット文字列（24文字）の面の順と、面ごとの読み方
+///
+/// 既定は多くのソルバーやシミュレーターが使う URFDLB の順で、どの面も行ごとに読みます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FaceletOrder {
+    /// 文字列に並べる面の順
+    pub faces: [Face; 6],
+    /// 面ごとの読み方（`faces` の順）
+    pub reading: [ReadingDirection; 6],
+}
+
+impl Default for FaceletOrder {
+    fn default() -> Self {
+        Self::urfdlb()
+    }
+}
+
+impl FaceletOrder {
+    /// URFDLB の順（行ごとに読む）
+    #[must_use]
+    pub fn urfdlb() -> Self {
+        Self {
+            faces: [
+                Face::Up,
+                Face::Right,
+                Face::Front,
+                Face::Down,
+                Face::Left,
+                Face::Back,
+            ],
+            reading: [ReadingDirection::Rows; 6],
+        }
+    }
+
+    /// 面の順を `"ULFRBD"` のような6文字で指定します（どの面も行ごとに読む）。
+    ///
+    /// 6面がちょうど1回ずつ現れない場合はエラーになります。
+    pub fn from_face_letters(letters: &str) -> Result<Self> {
+        let faces: Vec<Face> = letters
+            .trim()
+            .chars()
+            .map(|c| {
+                face_of_letter(c.to_ascii_uppercase())
+                    .ok_or_else(|| CubeError::InvalidFormat(format!("面の文字ではありません: {c}")))
+            })
+            .collect::<Result<_>>()?;
+        let faces: [Face; 6] = faces.try_into().map_err(|f: Vec<Face>| {
+            CubeError::InvalidFormat(format!("面の順は6文字必要ですが{}文字です", f.len()))
+        })?;
+        if let Some(missing) = Face::ALL.into_iter().find(|face| !faces.contains(face)) {
+            return Err(CubeError::InvalidFormat(format!(
+                "面の順に{}がありません",
+                face_letter(missing)
+            )));
+        }
+        Ok(Self {
+            faces,
+            reading: [ReadingDirection::Rows; 6],
+        })
+    }
+
+    /// 面の順を6文字で表したもの（例: `URFDLB`）
+    #[must_use]
+    pub fn face_letters(&self) -> String {
+        self.faces.iter().map(|&face| face_letter(face)).collect()
+    }
+
+    /// 指定した面の読み方を変えたもの
+    #[must_use]
+    pub fn with_reading(mut self, face: Face, reading: ReadingDirection) -> Self {
+        if let Some(i) = self.faces.iter().position(|&f| f == face) {
+            self.reading[i] = reading;
+        }
+        self
+    }
+
+    /// 文字列の n 文字目に対応するステッカーのインデックス
+    pub(crate) fn sticker_indices(&self) -> [usize; 24] {
+        let mut indices = [0; 24];
+        for (n, (face, reading)) in self.faces.iter().zip(self.reading).enumerate() {
+            for (k, pos) in reading.positions().into_iter().enumerate() {
+                indices[n * 4 + k] = face.index() * 4 + pos;
+            }
+        }
+        indices
+    }
+}
+
+/// 面の文字（U, D, L, R, F, B）
+fn face_letter(face: Face) -> char {
+    match face {
+        Face::Up => 'U',
+        Face::Down => 'D',
+        Face::Left => 'L',
+        Face::Right => 'R',
+        Face::Front => 'F',
+        Face::Back => 'B',
+    }
+}
+
+fn face_of_letter(c: char) -> Option<Face> {
+    Face::ALL.into_iter().find(|&face| face_letter(face) == c)
+}
+
+/// 色の文字（W, Y, G, B, R, O）
+fn color_of_letter(c: char) -> Option<Color> {
+    Color::from_letter(c).filter(|&color| color != Color::Gray)
+}
+
+/// キューブをフェイスレット文字列に変換
+///
+/// 各文字は、そのステッカーの色が完成状態でどの面の色か（面の文字）です。
+pub(crate) fn to_facelets(cube: &Cube, order: &FaceletOrder) -> String {
+    let solved = Cube::new();
+    order
+        .sticker_indices()
+        .iter()
+        .map(|&index| {
+            let color = cube.stickers[index].color;
+            Face::ALL
+                .into_iter()
+                .find(|face| solved.stickers[face.index() * 4].color == color)
+                .map_or('?', face_letter)
+        })
+        .collect()
+}
+
+/// フェイスレット文字列からキューブを作成
+///
+/// 面の文字（`U` を含む）と、配色 `scheme` での色の文字（`W` を含む）のどちらでも読み込めます。
+/// 空白は無視します。読めない文字は、元の文字列での行・列（1始まり）を `CubeError::Parse` で返します。
+pub(crate) fn from_facelets(s: &str, order: &FaceletOrder, scheme: &ColorScheme) -> Result<Cube> {
+    // 空白以外の文字と、その行・列
+    let positioned: Vec<(usize, usize, char)> = s
+        .lines()
+        .enumerate()
+        .flat_map(|(line, text)| {
+            text.chars()
+                .enumerate()
+                .filter(|(_, c)| !c.is_whitespace())
+                .map(move |(column, c)| (line + 1, column + 1, c))
+        })
+        .collect();
+    let chars: Vec<char> = positioned
+        .iter()
+        .map(|&(_, _, c)| c.to_ascii_uppercase())
+        .collect();
+    if chars.len() != 24 {
+        return Err(CubeError::InvalidFormat(format!(
+            "フェイスレットは24文字必要ですが{}文字です",
+            chars.len()
+        )));
+    }
+
+    // `B`・`R` は面の文字にも色の文字にもなるため、`U` と `W` のどちらを含むかで見分ける
+    let by_face = match (chars.contains(&'U'), chars.contains(&'W')) {
+        (true, false) => true,
+        (false, true) => false,
+        _ => {
+            return Err(CubeError::InvalidFormat(
+                "面の文字（U を含む）か色の文字（W を含む）のどちらかで書いてください".to_string(),
+            ))
+        }
+    };
+
+    let solved = Cube::new();
+    let mut colors = [Color::Gray; 24];
+    for ((&index, &c), &(line, column, found)) in
+        order.sticker_indices().iter().zip(&chars).zip(&positioned)
+    {
+        let invalid = |expected: &str| parse_error(line, column, expected, format!("`{found}`"));
+        colors[index] = if by_face {
+            let face = face_of_letter(c).ok_or_else(|| invalid("面の文字（U・R・F・D・L・B）"))?;
+            solved.stickers[face.index() * 4].color
+        } else {
+            let color =
+                color_of_letter(c).ok_or_else(|| invalid("色の文字（W・Y・G・B・R・O）"))?;
+            scheme.to_standard_color(color)
+        };
+    }
+    Cube::validate_colors(&colors)?;
+    Cube::from_colors(&colors)
+}
@@ -1,130 +1,455 @@
-use super::{Color, Cube};
-use crate::error::{CubeError, Result};
+use super::{Color, ColorScheme, Cube};
+use crate::error::{first_column, parse_error, Result};
+
+/// v2形式のファイルの先頭行（形式のバージョン）
+pub const FILE_HEADER_V2: &str = "2x2cube v2";
+
+/// v2形式で、展開図の後に向き（矢印）のブロックが続くことを示す行
+const ORIENTATION_MARKER: &str = "orientation:";
+
+/// 展開図の形
+///
+/// どちらの形でも、各面の4枚は左上→右上→左下→右下の順に並べます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NetLayout {
+    /// 十字形（3行）: 1行目にU、2行目にL F R B、3行目にD
+    #[default]
+    Cross,
+    /// T字形（4行）: 1行目にU、2行目にL F R、3行目にD、4行目にB
+    ///
+    /// B面はD面の下に折り返して置くため、十字形のB面を180度回した並びになります。
+    TShape,
+}
+
+impl NetLayout {
+    /// すべての形
+    pub const ALL: [NetLayout; 2] = [NetLayout::Cross, NetLayout::TShape];
+
+    /// 表示用の名前
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            NetLayout::Cross => "十字形",
+            NetLayout::TShape => "T字形",
+        }
+    }
+
+    /// 各行に並ぶ面（内部の面番号）と、その面を180度回して置くか
+    fn rows(self) -> &'static [&'static [(usize, bool)]] {
+        match self {
+            NetLayout::Cross => &[
+                &[(0, false)],
+                &[(2, false), (4, false), (3, false), (5, false)],
+                &[(1, false)],
+            ],
+            NetLayout::TShape => &[
+                &[(0, false)],
+                &[(2, false), (4, false), (3, false)],
+                &[(1, false)],
+                &[(5, true)],
+            ],
+        }
+    }
+
+    /// 2行目の文字数から形を判別する（T字形の12文字以外は十字形とみなす）
+    fn detect(second_row_len: usize) -> Self {
+        if second_row_len == 12 {
+            NetLayout::TShape
+        } else {
+            NetLayout::Cross
+        }
+    }
+}
+
+/// ファイルの内容（キューブの状態・コメント・メタデータ）
+///
+/// v2形式は次の順に書きます。空行と `#` で始まる行（コメント）はどこに置いても構いません。
+///
+/// ```text
+/// 2x2cube v2
+/// # コメント
+///      WWWW
+/// GGGG RRRR BBBB OOOO
+///      YYYY
+/// orientation:
+///      1203
+/// 1203 1203 1203 1203
+///      1203
+/// scramble: R U F'
+/// author: katoy
+/// date: 2026-01-11
+/// ```
+///
+/// 向きのブロックとメタデータ（`キー: 値`）は省略できます。
+/// 先頭行のない3行の展開図（v1形式）も読み込めます。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CubeFile {
+    pub cube: Cube,
+    /// 展開図の形（読み込んだファイルの形、書き出す形）
+    pub layout: NetLayout,
+    /// 向きのブロックを持つか（読み込んだファイルにあったか、書き出すか）
+    pub has_orientations: bool,
+    /// コメント（`#` と直後の空白を除いた本文、ファイルでの順）
+    pub comments: Vec<String>,
+    /// メタデータ（`scramble`・`author`・`date` など、ファイルでの順）
+    pub metadata: Vec<(String, String)>,
+}
+
+impl CubeFile {
+    /// 向きのブロックを含み、コメントとメタデータのないファイル
+    #[must_use]
+    pub fn new(cube: Cube) -> Self {
+        Self {
+            cube,
+            layout: NetLayout::Cross,
+            has_orientations: true,
+            comments: Vec::new(),
+            metadata: Vec::new(),
+        }
+    }
+
+    /// メタデータの値
+    #[must_use]
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// メタデータを設定します（同じキーがあれば置き換え、なければ末尾に追加）。
+    pub fn set_metadata(&mut self, key: &str, value: &str) {
+        match self.metadata.iter_mut().find(|(k, _)| k == key) {
+            Some(entry) => entry.1 = value.to_string(),
+            None => self.metadata.push((key.to_string(), value.to_string())),
+        }
+    }
+
+    /// v1形式またはv2形式の文字列を読み込みます。
+    ///
+    /// 展開図の形（十字形・T字形）は2行目の文字数から判別します。タブや行末の空白、
+    /// CRLFの改行、空行は無視します。書式の誤りは行と列の位置を持つ
+    /// `CubeError::Parse` になります。
+    pub fn parse(s: &str) -> Result<Self> {
+        Self::parse_with_scheme(s, &ColorScheme::standard())
+    }
+
+    /// 指定した配色のv1形式またはv2形式の文字列を読み込みます。
+    pub fn parse_with_scheme(s: &str, scheme: &ColorScheme) -> Result<Self> {
+        if is_v2(s) {
+            parse_v2(s, scheme)
+        } else {
+            let (cube, layout) = parse_v1(s, scheme)?;
+            Ok(Self {
+                cube,
+                layout,
+                has_orientations: false,
+                comments: Vec::new(),
+                metadata: Vec::new(),
+            })
+        }
+    }
+
+    /// v2形式の文字列に変換
+    #[must_use]
+    pub fn to_file_format(&self) -> String {
+        self.to_file_format_with_scheme(&ColorScheme::standard())
+    }
+
+    /// 指定した配色の色で、v2形式の文字列に変換
+    ///
+    /// コメントやメタデータの改行は空白に置き換えます。
+    #[must_use]
+    pub fn to_file_format_with_scheme(&self, scheme: &ColorScheme) -> String {
+        let mut result = format!("{FILE_HEADER_V2}\n");
+        for comment in &self.comments {
+            result.push_str(&format!("# {}\n", single_line(comment)));
+        }
+        result.push_str(&format_net(self.layout, |i, _| {
+            color_char(&self.cube, scheme, i)
+        }));
+        if self.has_orientations {
+            result.push_str(ORIENTATION_MARKER);
+            result.push('\n');
+            result.push_str(&format_net(self.layout, |i, turned| {
+                let orientation = self.cube.stickers[i].orientation + if turned { 2 } else { 0 };
+                char::from(b'0' + orientation % 4)
+            }));
+        }
+        for (key, value) in &self.metadata {
+            result.push_str(&format!("{}: {}\n", single_line(key), single_line(value)));
+        }
+        result
+    }
+}
+
+/// 1行に書く値の改行を空白に置き換える
+pub(crate) fn single_line(s: &str) -> String {
+    s.replace(['\r', '\n'], " ")
+}
 
 /// キューブの状態をファイル形式の文字列に変換
 pub fn to_file_format(cube: &Cube) -> String {
-    let mut result = String::new();
+    to_file_format_with_scheme(cube, &ColorScheme::standard())
+}
+
+/// キューブの状態を、指定した配色の色でファイル形式の文字列に変換
+pub fn to_file_format_with_scheme(cube: &Cube, scheme: &ColorScheme) -> String {
+    format_net(NetLayout::Cross, |i, _| color_char(cube, scheme, i))
+}
+
+/// ステッカーの色の文字（配色 `scheme` での色、未設定は空白）
+fn color_char(cube: &Cube, scheme: &ColorScheme, index: usize) -> char {
+    match scheme.from_standard_color(cube.stickers[index].color) {
+        Color::Gray => ' ',
+        color => color.letter(),
+    }
+}
 
-    // ヘルパー関数：面の4文字を取得
-    let get_face = |face_idx: usize| -> String {
-        let start = face_idx * 4;
+/// ステッカーごとの文字を展開図の形に並べる
+///
+/// `sticker_char` にはステッカーのインデックスと、その面を180度回して置くかを渡します。
+fn format_net(layout: NetLayout, sticker_char: impl Fn(usize, bool) -> char) -> String {
+    let get_face = |(face_idx, turned): (usize, bool)| -> String {
         (0..4)
-            .map(|i| match cube.stickers[start + i].color {
-                Color::White => 'W',
-                Color::Yellow => 'Y',
-                Color::Green => 'G',
-                Color::Blue => 'B',
-                Color::Red => 'R',
-                Color::Orange => 'O',
-                Color::Gray => ' ',
+            .map(|k| {
+                let pos = if turned { 3 - k } else { k };
+                sticker_char(face_idx * 4 + pos, turned)
             })
             .collect()
     };
 
-    // 展開図形式で出力
-    // 1行目: Up (Down面は使わない、White面)
-    result.push_str("     ");
-    result.push_str(&get_face(0)); // Up
-    result.push('\n');
-
-    // 2行目: Left Front Right Back (Yellow Green Blue Red)
-    result.push_str(&get_face(2)); // Left
-    result.push(' ');
-    result.push_str(&get_face(4)); // Front
-    result.push(' ');
-    result.push_str(&get_face(3)); // Right
-    result.push(' ');
-    result.push_str(&get_face(5)); // Back
-    result.push('\n');
-
-    // 3行目: Down (Orange面)
-    result.push_str("     ");
-    result.push_str(&get_face(1)); // Down
-    result.push('\n');
-
+    let mut result = String::new();
+    for faces in layout.rows() {
+        if faces.len() == 1 {
+            result.push_str("     ");
+        }
+        let row: Vec<String> = faces.iter().map(|&face| get_face(face)).collect();
+        result.push_str(&row.join(" "));
+        result.push('\n');
+    }
     result
 }
 
 /// ファイル形式の文字列からキューブを作成
 pub fn from_file_format(s: &str) -> Result<Cube> {
-    let lines: Vec<&str> = s.lines().collect();
-
-    if lines.len() != 3 {
-        return Err(CubeError::InvalidFormat(format!(
-            "3行必要ですが{}行しかありません",
-            lines.len()
-        )));
-    }
-
-    // 色を解析
-    let parse_colors = |s: &str| -> Result<Vec<Color>> {
-        s.chars()
-            .filter(|c| !c.is_whitespace())
-            .map(|c| match c.to_ascii_uppercase() {
-                'W' => Ok(Color::White),
-                'Y' => Ok(Color::Yellow),
-                'G' => Ok(Color::Green),
-                'B' => Ok(Color::Blue),
-                'R' => Ok(Color::Red),
-                'O' => Ok(Color::Orange),
-                _ => Err(CubeError::InvalidColorChar(c)),
-            })
-            .collect()
-    };
+    from_file_format_with_scheme(s, &ColorScheme::standard())
+}
+
+/// 指定した配色のファイル形式の文字列からキューブを作成
+///
+/// 読み込んだ色は標準配色に読み替えてからキューブを作成します。
+/// v2形式に向きのブロックがあれば、その向きを使います。
+pub fn from_file_format_with_scheme(s: &str, scheme: &ColorScheme) -> Result<Cube> {
+    CubeFile::parse_with_scheme(s, scheme).map(|file| file.cube)
+}
+
+/// 先頭行（空行とコメントを除く）がv2形式の見出しか
+fn is_v2(s: &str) -> bool {
+    content_lines(s)
+        .next()
+        .is_some_and(|(_, line)| line.trim_start().starts_with("2x2cube"))
+}
+
+/// 空行とコメントを除いた行（行番号は1から）
+///
+/// ほかのファイル形式の読み込みでも使います。
+pub(crate) fn content_lines(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    s.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_end()))
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+}
+
+/// 先頭行のない展開図（v1形式）
+fn parse_v1(s: &str, scheme: &ColorScheme) -> Result<(Cube, NetLayout)> {
+    let end = s.lines().count() + 1;
+    let mut lines = content_lines(s);
+    let (colors, layout) = parse_net(&mut lines, end, None, &COLOR_CELLS)?;
+    if let Some((number, line)) = lines.next() {
+        return Err(parse_error(
+            number,
+            first_column(line),
+            "ファイルの終わり",
+            format!("`{}`", line.trim()),
+        ));
+    }
+    Ok((colors_to_cube(colors, scheme)?, layout))
+}
 
-    // 各行から色を取得
-    let line1_colors = parse_colors(lines[0])?;
-    let line2_colors = parse_colors(lines[1])?;
-    let line3_colors = parse_colors(lines[2])?;
+/// v2形式（見出し・コメント・展開図・向き・メタデータ）
+fn parse_v2(s: &str, scheme: &ColorScheme) -> Result<CubeFile> {
+    let comments = s
+        .lines()
+        .filter_map(|line| line.trim_start().strip_prefix('#'))
+        .map(|comment| comment.trim().to_string())
+        .collect();
 
-    // 検証
-    if line1_colors.len() != 4 {
-        return Err(CubeError::InvalidFormat(format!(
-            "1行目: 4文字必要ですが{}文字です",
-            line1_colors.len()
-        )));
+    let end = s.lines().count() + 1;
+    let mut lines = content_lines(s);
+    let (number, header) = lines.next().unwrap_or_default();
+    if header.trim() != FILE_HEADER_V2 {
+        return Err(parse_error(
+            number,
+            first_column(header),
+            &format!("`{FILE_HEADER_V2}`"),
+            format!("`{}`", header.trim()),
+        ));
     }
-    if line2_colors.len() != 16 {
-        return Err(CubeError::InvalidFormat(format!(
-            "2行目: 16文字必要ですが{}文字です",
-            line2_colors.len()
-        )));
+
+    let (colors, layout) = parse_net(&mut lines, end, None, &COLOR_CELLS)?;
+    let mut cube = colors_to_cube(colors, scheme)?;
+
+    let mut has_orientations = false;
+    let mut metadata: Vec<(String, String)> = Vec::new();
+    let mut rest = lines.peekable();
+    if rest
+        .peek()
+        .is_some_and(|(_, line)| line.trim() == ORIENTATION_MARKER)
+    {
+        rest.next();
+        let (orientations, _) = parse_net(&mut rest, end, Some(layout), &ORIENTATION_CELLS)?;
+        for (sticker, orientation) in cube.stickers.iter_mut().zip(orientations) {
+            sticker.orientation = orientation;
+        }
+        has_orientations = true;
     }
-    if line3_colors.len() != 4 {
-        return Err(CubeError::InvalidFormat(format!(
-            "3行目: 4文字必要ですが{}文字です",
-            line3_colors.len()
-        )));
+
+    for (number, line) in rest {
+        let (key, value) = line
+            .split_once(':')
+            .filter(|(key, _)| !key.trim().is_empty())
+            .ok_or_else(|| {
+                parse_error(
+                    number,
+                    first_column(line),
+                    "`キー: 値` の形式のメタデータ",
+                    format!("`{}`", line.trim()),
+                )
+            })?;
+        metadata.push((key.trim().to_string(), value.trim().to_string()));
     }
 
-    // 24色の配列を作成（内部順序: Up, Down, Left, Right, Front, Back）
-    let mut colors = vec![Color::White; 24];
+    Ok(CubeFile {
+        cube,
+        layout,
+        has_orientations,
+        comments,
+        metadata,
+    })
+}
+
+/// 展開図の1マスに書く値の読み方
+struct Cells<T> {
+    /// 展開図の名前（エラーの表示用）
+    name: &'static str,
+    /// 1文字の説明（エラーの表示用）
+    expected: &'static str,
+    parse: fn(char) -> Option<T>,
+    /// 面を180度回して置いたときの値の読み替え
+    turn: fn(T) -> T,
+    fill: T,
+}
 
-    // Up (0-3)
-    colors[0..4].copy_from_slice(&line1_colors);
+const COLOR_CELLS: Cells<Color> = Cells {
+    name: "展開図",
+    expected: "色の文字（W/Y/G/B/R/O）",
+    parse: parse_color,
+    turn: |color| color,
+    fill: Color::White,
+};
 
-    // Down (4-7)
-    colors[4..8].copy_from_slice(&line3_colors);
+const ORIENTATION_CELLS: Cells<u8> = Cells {
+    name: "向き",
+    expected: "向きの数字（0-3）",
+    parse: parse_orientation,
+    turn: |orientation| (orientation + 2) % 4,
+    fill: 0,
+};
 
-    // Left (8-11)
-    colors[8..12].copy_from_slice(&line2_colors[0..4]);
+/// 色の文字（未設定の `-` は展開図には書けない）
+fn parse_color(c: char) -> Option<Color> {
+    Color::from_letter(c).filter(|&color| color != Color::Gray)
+}
 
-    // Right (12-15)
-    colors[12..16].copy_from_slice(&line2_colors[8..12]);
+/// 向き（矢印）の数字 0-3
+fn parse_orientation(c: char) -> Option<u8> {
+    c.to_digit(10).filter(|&d| d < 4).map(|d| d as u8)
+}
 
-    // Front (16-19)
-    colors[16..20].copy_from_slice(&line2_colors[4..8]);
+/// 展開図の行を読み、内部順序（Up, Down, Left, Right, Front, Back）の24個の値を取り出す
+///
+/// `layout` が `None` のときは2行目の文字数から形を判別します（1行目はどちらの形もU面だけです）。
+/// `end` はファイルの終わりの行番号です（行が足りないときのエラーの位置）。
+fn parse_net<'a, T: Copy>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    end: usize,
+    layout: Option<NetLayout>,
+    cells: &Cells<T>,
+) -> Result<([T; 24], NetLayout)> {
+    let mut layout = layout;
+    let mut values = [cells.fill; 24];
+    let mut row = 0;
+    while row < layout.map_or(2, |l| l.rows().len()) {
+        let (number, line) = lines.next().ok_or_else(|| {
+            parse_error(
+                end,
+                1,
+                &format!("{}の{}行目", cells.name, row + 1),
+                "ファイルの終わり",
+            )
+        })?;
+        // 空白（タブを含む）を除いた文字と、その列
+        let chars: Vec<(usize, char)> = line
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| !c.is_whitespace())
+            .map(|(i, c)| (i + 1, c))
+            .collect();
+        if row == 1 && layout.is_none() {
+            layout = Some(NetLayout::detect(chars.len()));
+        }
+        let faces = layout.unwrap_or_default().rows()[row];
 
-    // Back (20-23)
-    colors[20..24].copy_from_slice(&line2_colors[12..16]);
+        let needed = faces.len() * 4;
+        if let Some(&(column, c)) = chars.get(needed) {
+            return Err(parse_error(
+                number,
+                column,
+                &format!("行の終わり（{needed}文字）"),
+                format!("{}文字目の `{c}`", needed + 1),
+            ));
+        }
+        if chars.len() < needed {
+            return Err(parse_error(
+                number,
+                line.chars().count() + 1,
+                &format!("{needed}文字の{}", cells.expected),
+                format!("{}文字で行の終わり", chars.len()),
+            ));
+        }
 
-    let colors_array: [Color; 24] = colors
-        .try_into()
-        .map_err(|_| CubeError::Internal("色の数が24個ではありません".to_string()))?;
+        for (n, &(column, c)) in chars.iter().enumerate() {
+            let value = (cells.parse)(c)
+                .ok_or_else(|| parse_error(number, column, cells.expected, format!("`{c}`")))?;
+            let (face, turned) = faces[n / 4];
+            let (pos, value) = if turned {
+                (3 - n % 4, (cells.turn)(value))
+            } else {
+                (n % 4, value)
+            };
+            values[face * 4 + pos] = value;
+        }
+        row += 1;
+    }
+    Ok((values, layout.unwrap_or_default()))
+}
 
+/// ファイルの色（指定した配色）からキューブを作成
+fn colors_to_cube(colors: [Color; 24], scheme: &ColorScheme) -> Result<Cube> {
     // 妥当性チェック
     use super::validation;
-    validation::validate_colors(&colors_array)?;
+    validation::validate_colors(&colors)?;
 
-    Cube::from_colors(&colors_array)
+    Cube::from_colors(&scheme.to_standard(&colors))
 }
@@ -1,9 +1,21 @@
+pub mod corner;
 pub mod enums;
+pub mod facelets;
 pub mod io;
+mod permutation;
+pub mod repair;
 pub mod rotation;
+pub mod scan;
+pub mod scheme;
+pub mod symmetry;
 pub mod validation;
 
+pub use self::corner::{Corner, CornerState};
 pub use self::enums::{Color, Face, Move, Sticker};
+pub use self::facelets::{FaceletOrder, ReadingDirection};
+pub use self::io::{CubeFile, NetLayout};
+pub use self::scheme::ColorScheme;
+pub use self::symmetry::{Symmetry, SymmetryClass};
 
 /// 2x2 ルービックキューブ
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -17,41 +29,8 @@ impl Cube {
     /// 完成状態のキューブを作成します。
     #[must_use]
     pub fn new() -> Self {
-        let mut stickers = [Sticker::new(Color::White); 24];
-        let clockwise_pattern = [1, 2, 0, 3];
-
-        // Up face (White)
-        for (i, sticker) in stickers.iter_mut().take(4).enumerate() {
-            *sticker = Sticker::new(Color::White);
-            sticker.orientation = clockwise_pattern[i];
-        }
-        // Down face (Yellow)
-        for (i, sticker) in stickers.iter_mut().take(8).skip(4).enumerate() {
-            *sticker = Sticker::new(Color::Yellow);
-            sticker.orientation = clockwise_pattern[i];
-        }
-        // Left face (Green)
-        for (i, sticker) in stickers.iter_mut().take(12).skip(8).enumerate() {
-            *sticker = Sticker::new(Color::Green);
-            sticker.orientation = clockwise_pattern[i];
-        }
-        // Right face (Blue)
-        for (i, sticker) in stickers.iter_mut().take(16).skip(12).enumerate() {
-            *sticker = Sticker::new(Color::Blue);
-            sticker.orientation = clockwise_pattern[i];
-        }
-        // Front face (Red)
-        for (i, sticker) in stickers.iter_mut().take(20).skip(16).enumerate() {
-            *sticker = Sticker::new(Color::Red);
-            sticker.orientation = clockwise_pattern[i];
-        }
-        // Back face (Orange)
-        for (i, sticker) in stickers.iter_mut().skip(20).enumerate() {
-            *sticker = Sticker::new(Color::Orange);
-            sticker.orientation = clockwise_pattern[i];
-        }
-
-        Self { stickers }
+        let stickers = ColorScheme::standard().solved_colors().map(Sticker::new);
+        Self { stickers }.with_clockwise_orientations()
     }
 
     /// キューブが完成しているか判定します（色のみ、向きは無視）。
@@ -125,6 +104,11 @@ impl Cube {
         validation::is_valid_state(self)
     }
 
+    /// コーナーの配置（ピースとねじれ）を解析
+    pub fn corner_state(&self) -> crate::error::Result<CornerState> {
+        CornerState::from_colors(&self.stickers.map(|s| s.color))
+    }
+
     /// キューブの状態をファイル形式の文字列に変換
     pub fn to_file_format(&self) -> String {
         io::to_file_format(self)
@@ -135,6 +119,42 @@ impl Cube {
         io::from_file_format(s)
     }
 
+    /// 指定した配色でキューブの状態をファイル形式の文字列に変換
+    pub fn to_file_format_with_scheme(&self, scheme: &ColorScheme) -> String {
+        io::to_file_format_with_scheme(self, scheme)
+    }
+
+    /// 指定した配色のファイル形式の文字列からキューブを作成
+    pub fn from_file_format_with_scheme(
+        s: &str,
+        scheme: &ColorScheme,
+    ) -> crate::error::Result<Self> {
+        io::from_file_format_with_scheme(s, scheme)
+    }
+
+    /// フェイスレット文字列（面の文字24文字、例: 完成状態は URFDLB の順で `UUUURRRRFFFFDDDDLLLLBBBB`）に変換します。
+    #[must_use]
+    pub fn to_facelets(&self, order: &FaceletOrder) -> String {
+        facelets::to_facelets(self, order)
+    }
+
+    /// フェイスレット文字列からキューブを作成します。
+    ///
+    /// 面の文字（`U` `R` `F` `D` `L` `B`）と色の文字（`W` `Y` `G` `B` `R` `O`）のどちらでも読み込めます。
+    /// 空白は無視し、大文字・小文字は区別しません。
+    pub fn from_facelets(s: &str, order: &FaceletOrder) -> crate::error::Result<Self> {
+        facelets::from_facelets(s, order, &ColorScheme::standard())
+    }
+
+    /// 色の文字を指定した配色の色として読み、フェイスレット文字列からキューブを作成します。
+    pub fn from_facelets_with_scheme(
+        s: &str,
+        order: &FaceletOrder,
+        scheme: &ColorScheme,
+    ) -> crate::error::Result<Self> {
+        facelets::from_facelets(s, order, scheme)
+    }
+
     /// 回転操作を実行
     pub fn apply_move(&mut self, mv: Move) {
         rotation::apply_move(self, mv);
@@ -145,18 +165,49 @@ impl Cube {
         rotation::scramble(self, moves);
     }
 
+    /// 色の並びだけから、各ステッカーの向きを復元した局面を返します。
+    ///
+    /// 向きを無視して解いた最短手順を、到達した完成状態から逆にたどった向きを付けます。
+    /// 回転操作での矢印の回り方は経路によって180度ずれることがあり、コーナーの配置だけからは
+    /// 決まらないため、実際に回して到達できる（向きも含めて最大11手で解ける）向きを探索で求めます。
+    /// 探索はその場で行うため、手数の多い局面では時間がかかります（GUIでは別スレッドで行います）。
+    /// 色の数やピース・ねじれが無効な局面ではエラーになります。
+    pub fn with_restored_orientations(&self) -> crate::error::Result<Self> {
+        self.is_valid_state()?;
+        // 浅い局面ほど早く終わるよう、探索の深さを1手ずつ増やす
+        let solution = (0..=crate::solver::DEFAULT_MAX_DEPTH)
+            .map(|depth| crate::solver::solve(self, depth, true))
+            .find(|solution| solution.found)
+            .ok_or_else(|| {
+                crate::error::CubeError::InvalidState(
+                    "向きを無視しても解けない局面です".to_string(),
+                )
+            })?;
+        let mut restored = self.clone();
+        restored.apply_orientation_solution(&solution)?;
+        Ok(restored)
+    }
+
     /// ソリューション（向き無視で解いたもの）を使って、現在のキューブの正しい向きを復元します。
     pub fn apply_orientation_solution(
         &mut self,
         solution: &crate::solver::Solution,
     ) -> crate::error::Result<()> {
-        // Solved状態から逆操作を適用して、現在の色配置を再現
-        let mut reference_cube = Cube::new();
-        // 解の手順: Current -> Solved
-        // 逆手順: Solved -> Current
-        for mv in solution.moves.iter().rev() {
-            reference_cube.apply_move(mv.inverse());
+        // 解の手順: Current -> Solved なので、到達した完成状態に手順の逆を合成すると
+        // 正しい向きを持った現在の局面になる
+        let mut reached = self.clone();
+        for &mv in &solution.moves {
+            reached.apply_move(mv);
         }
+        let solved = crate::solver::get_solved_states()
+            .iter()
+            .find(|solved| solved.normalized() == reached.normalized())
+            .ok_or_else(|| {
+                crate::error::CubeError::Internal(
+                    "ソルバーの手順で完成状態に到達しません".to_string(),
+                )
+            })?;
+        let reference_cube = solved.compose(&Cube::from_moves(&solution.moves).inverse()?)?;
 
         // Orientationのみコピー
         for (i, sticker) in self.stickers.iter_mut().enumerate() {
@@ -174,6 +225,71 @@ impl Cube {
         Ok(())
     }
 
+    /// 完成状態に手順を適用した局面を作成します。
+    #[must_use]
+    pub fn from_moves(moves: &[Move]) -> Self {
+        permutation::from_moves(moves)
+    }
+
+    /// この局面に、`other` の効果（完成状態から `other` に至る手順）を続けて適用した局面を返します。
+    ///
+    /// ステッカーの並べ替えと向き（矢印）の回転の両方を合成します。
+    /// `a.compose(&b)` は、`a` を作った手順の後に `b` を作った手順を回した局面です。
+    /// `other` に実在しないピースや重複したピースがある場合はエラーになります。
+    pub fn compose(&self, other: &Cube) -> crate::error::Result<Cube> {
+        permutation::compose(self, other)
+    }
+
+    /// この局面の効果を打ち消す局面（逆元）を返します。
+    ///
+    /// `cube.compose(&cube.inverse()?)` は完成状態（`Cube::new`）になります。
+    pub fn inverse(&self) -> crate::error::Result<Cube> {
+        permutation::inverse(self)
+    }
+
+    /// この局面を `by` で共役した局面（`by`、この局面、`by` の逆の順に合成）を返します。
+    ///
+    /// 手順 `A` の局面を手順 `S` の局面で共役すると、手順 `S A S'` の局面になります。
+    pub fn conjugate(&self, by: &Cube) -> crate::error::Result<Cube> {
+        by.compose(self)?.compose(&by.inverse()?)
+    }
+
+    /// 対称変換（回転・鏡像の48通り）と持ち替えで移り合う局面のうち、代表となる局面を返します。
+    ///
+    /// 同じ同値類の局面は同じ代表を持つため、データベースなどで1局面だけを保存できます。
+    /// 色の配置のみを比較し、返すキューブの向きは0になります。
+    #[must_use]
+    pub fn canonical(&self) -> Cube {
+        symmetry::canonical_with_symmetry(self).0
+    }
+
+    /// 局面の対称性による同値類（代表・代表への変換・同値類の大きさ）を求めます。
+    #[must_use]
+    pub fn symmetry_class(&self) -> SymmetryClass {
+        symmetry::symmetry_class(self)
+    }
+
+    /// 48通りの対称変換と、それぞれで移した局面を列挙します。
+    pub fn symmetries(&self) -> impl Iterator<Item = (&'static Symmetry, Cube)> + '_ {
+        Symmetry::all().iter().map(move |s| (s, s.conjugate(self)))
+    }
+
+    /// `other` をこの局面に移す対称変換を探します（持ち替えは同一視します）。
+    ///
+    /// 回転で移り合う場合は回転を優先して返します。鏡像でのみ移り合う場合、
+    /// この局面は `other` の鏡像です。
+    #[must_use]
+    pub fn symmetry_to(&self, other: &Cube) -> Option<&'static Symmetry> {
+        let target: Vec<Cube> = Symmetry::rotations()
+            .iter()
+            .map(|r| r.transform(self))
+            .collect();
+        // 対称変換の結果は向きが0になるため、比較対象も色のみで比べる
+        Symmetry::all()
+            .iter()
+            .find(|s| target.contains(&s.conjugate(other)))
+    }
+
     /// 色情報のみ比較するために、向き情報をリセットしたキューブを返します。
     pub fn normalized(&self) -> Self {
         let mut new_cube = self.clone();
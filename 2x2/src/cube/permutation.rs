@@ -0,0 +1,108 @@
+use super::{Corner, Cube, Move};
+use crate::error::Result;
+use std::ops::Mul;
+
+/// 局面の効果（完成状態からどのステッカーがどこへ、どれだけ回って移ったか）
+///
+/// 位置 `i` には完成状態の位置 `from[i]` のステッカーが入っており、
+/// その向きは完成状態から `turns[i]`（90度単位、時計回り）だけ回っています。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Effect {
+    from: [u8; 24],
+    turns: [u8; 24],
+}
+
+impl Effect {
+    /// キューブの色と向きから効果を求める
+    ///
+    /// 各スロットのピースとねじれからステッカーの元の位置を特定するため、
+    /// 実在しないピースや重複したピースがある場合はエラーになります。
+    fn of(cube: &Cube) -> Result<Self> {
+        let state = cube.corner_state()?;
+        let solved = Cube::new();
+
+        let mut from = [0u8; 24];
+        let mut turns = [0u8; 24];
+        for slot in Corner::ALL {
+            let piece = state.pieces[slot.index()];
+            let twist = state.twists[slot.index()] as usize;
+            for (pos, &index) in slot.stickers().iter().enumerate() {
+                // スロットの pos 番目には、ピースの (pos - twist) 番目のステッカーが来ている
+                let home = piece.stickers()[(pos + 3 - twist) % 3];
+                from[index] = home as u8;
+                turns[index] =
+                    (cube.stickers[index].orientation + 4 - solved.stickers[home].orientation) % 4;
+            }
+        }
+        Ok(Self { from, turns })
+    }
+
+    /// キューブにこの効果を適用する（局面を作った手順を続けて回すのと同じ）
+    fn apply_to(&self, cube: &Cube) -> Cube {
+        let mut result = cube.clone();
+        for (i, sticker) in result.stickers.iter_mut().enumerate() {
+            *sticker = cube.stickers[self.from[i] as usize];
+            sticker.orientation = (sticker.orientation + self.turns[i]) % 4;
+        }
+        result
+    }
+
+    /// 逆の効果
+    fn inverse(&self) -> Self {
+        let mut from = [0u8; 24];
+        let mut turns = [0u8; 24];
+        for i in 0..24 {
+            let src = self.from[i] as usize;
+            from[src] = i as u8;
+            turns[src] = (4 - self.turns[i]) % 4;
+        }
+        Self { from, turns }
+    }
+}
+
+/// 完成状態に手順を適用した局面
+pub(crate) fn from_moves(moves: &[Move]) -> Cube {
+    let mut cube = Cube::new();
+    for &mv in moves {
+        cube.apply_move(mv);
+    }
+    cube
+}
+
+/// `cube` に `other` の効果を続けて適用した局面
+pub(crate) fn compose(cube: &Cube, other: &Cube) -> Result<Cube> {
+    // 色の並びは `cube` から取るため、`cube` 自体は有効なピースでなくてもよい
+    Ok(Effect::of(other)?.apply_to(cube))
+}
+
+/// 効果を打ち消す局面（`cube` と合成すると完成状態になる）
+pub(crate) fn inverse(cube: &Cube) -> Result<Cube> {
+    Ok(Effect::of(cube)?.inverse().apply_to(&Cube::new()))
+}
+
+impl Mul<&Cube> for &Cube {
+    type Output = Cube;
+
+    /// `self` に `rhs` の効果を続けて適用した局面（`Cube::compose` と同じ）
+    ///
+    /// # Panics
+    ///
+    /// `rhs` に実在しないピースや重複したピースがある場合
+    fn mul(self, rhs: &Cube) -> Cube {
+        self.compose(rhs)
+            .expect("合成する局面のコーナーは有効なピースである必要があります")
+    }
+}
+
+impl Mul for Cube {
+    type Output = Cube;
+
+    /// `self` に `rhs` の効果を続けて適用した局面（`Cube::compose` と同じ）
+    ///
+    /// # Panics
+    ///
+    /// `rhs` に実在しないピースや重複したピースがある場合
+    fn mul(self, rhs: Cube) -> Cube {
+        &self * &rhs
+    }
+}
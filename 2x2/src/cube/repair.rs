@@ -0,0 +1,322 @@
+use super::{corner, validation, Color, Corner};
+use std::collections::HashMap;
+
+/// 提示する修正案の最大数
+const MAX_REPAIRS: usize = 8;
+
+/// 色数の不足を補うために試す、色の変更の最大数
+const MAX_RECOLORS: usize = 3;
+
+/// 入れ替え・ねじりを組み合わせる最大数
+const MAX_PERMUTING_ACTIONS: usize = 2;
+
+/// 無効な状態を有効にするための1つの操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RepairAction {
+    /// ステッカーの色を変更
+    Recolor { index: usize, color: Color },
+    /// 2枚のステッカーを入れ替え
+    Swap { a: usize, b: usize },
+    /// コーナーのピースを回す（`clockwise` は修正のために回す向き）
+    Twist { corner: Corner, clockwise: bool },
+}
+
+impl RepairAction {
+    /// この操作で変化するステッカーの枚数
+    #[must_use]
+    pub fn sticker_count(self) -> usize {
+        match self {
+            RepairAction::Recolor { .. } => 1,
+            RepairAction::Swap { .. } => 2,
+            RepairAction::Twist { .. } => 3,
+        }
+    }
+
+    /// 色配列にこの操作を適用
+    pub fn apply(self, colors: &mut [Color; 24]) {
+        match self {
+            RepairAction::Recolor { index, color } => colors[index] = color,
+            RepairAction::Swap { a, b } => colors.swap(a, b),
+            RepairAction::Twist { corner, clockwise } => {
+                let [a, b, c] = corner.stickers();
+                if clockwise {
+                    // 外側から見て時計回り: 各ステッカーが次の位置へ移る
+                    (colors[a], colors[b], colors[c]) = (colors[c], colors[a], colors[b]);
+                } else {
+                    (colors[a], colors[b], colors[c]) = (colors[b], colors[c], colors[a]);
+                }
+            }
+        }
+    }
+
+    /// 操作の説明文
+    #[must_use]
+    pub fn describe(self) -> String {
+        match self {
+            RepairAction::Recolor { index, color } => {
+                format!("ステッカー{}の色を{:?}に変更", index, color)
+            }
+            RepairAction::Swap { a, b } => format!("ステッカー{}と{}を入れ替え", a, b),
+            RepairAction::Twist { corner, clockwise } => {
+                let (twisted, fix) = if clockwise {
+                    ("反時計回り", "時計回り")
+                } else {
+                    ("時計回り", "反時計回り")
+                };
+                format!(
+                    "{}のコーナーが{}にねじれています。{}に1回まわしてください",
+                    corner, twisted, fix
+                )
+            }
+        }
+    }
+}
+
+/// 修正案（操作の組み合わせ）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repair {
+    pub actions: Vec<RepairAction>,
+    /// 修正後の色配列
+    pub colors: [Color; 24],
+}
+
+impl Repair {
+    /// 変化するステッカーの枚数
+    #[must_use]
+    pub fn sticker_count(&self) -> usize {
+        self.actions.iter().map(|a| a.sticker_count()).sum()
+    }
+
+    /// 修正案の説明文
+    #[must_use]
+    pub fn describe(&self) -> String {
+        self.actions
+            .iter()
+            .map(|a| a.describe())
+            .collect::<Vec<_>>()
+            .join("、")
+    }
+}
+
+/// 無効な色配列を有効な状態にする、最小の修正案を探します。
+///
+/// 変化するステッカーの枚数が最も少ない修正案を最大 `MAX_REPAIRS` 件返します。
+/// 色の変更（1枚）、2枚の入れ替え（2枚）、コーナーのねじり（3枚）を組み合わせます。
+/// 既に有効な状態であれば空のベクタを返します。
+#[must_use]
+pub fn find_repairs(colors: &[Color; 24]) -> Vec<Repair> {
+    if is_valid(colors) {
+        return Vec::new();
+    }
+
+    let recolor_sets = recolor_sets(colors);
+    // 色の変更が必要な場合は、組み合わせ数を抑えるため入れ替え・ねじりは1つまで
+    let max_depth = if recolor_sets.iter().any(|set| !set.is_empty()) {
+        1
+    } else {
+        MAX_PERMUTING_ACTIONS
+    };
+
+    // 入れ替え・ねじりの数を1つずつ増やし、有効な状態が見つかった深さで打ち切る
+    let mut candidates = Vec::new();
+    for depth in 0..=max_depth {
+        for recolors in &recolor_sets {
+            let mut base = *colors;
+            for &action in recolors {
+                action.apply(&mut base);
+            }
+            let mut actions = recolors.clone();
+            search_permuting_actions(&base, &mut actions, depth, &mut candidates);
+        }
+        if !candidates.is_empty() {
+            break;
+        }
+    }
+
+    let Some(min_count) = candidates.iter().map(Repair::sticker_count).min() else {
+        return Vec::new();
+    };
+
+    let mut repairs: Vec<Repair> = Vec::new();
+    for candidate in candidates {
+        if candidate.sticker_count() == min_count
+            && !repairs.iter().any(|r| r.colors == candidate.colors)
+        {
+            repairs.push(candidate);
+        }
+    }
+    repairs.truncate(MAX_REPAIRS);
+    repairs
+}
+
+/// 色数を合わせた配列に対し、入れ替えとねじりをちょうど `remaining` 個適用して有効になる組み合わせを探す
+fn search_permuting_actions(
+    colors: &[Color; 24],
+    actions: &mut Vec<RepairAction>,
+    remaining: usize,
+    candidates: &mut Vec<Repair>,
+) {
+    if remaining == 0 {
+        if validation::check_corners(colors).is_ok() {
+            candidates.push(Repair {
+                actions: actions.clone(),
+                colors: *colors,
+            });
+        }
+        return;
+    }
+
+    for action in permuting_actions(colors) {
+        // 同じ組み合わせを順序違いで重複して試さない
+        if let Some(&last) = actions.last() {
+            if !matches!(last, RepairAction::Recolor { .. })
+                && action_key(action) <= action_key(last)
+            {
+                continue;
+            }
+        }
+        let mut next = *colors;
+        action.apply(&mut next);
+        actions.push(action);
+        search_permuting_actions(&next, actions, remaining - 1, candidates);
+        actions.pop();
+    }
+}
+
+/// 試す価値のある入れ替えとねじりの一覧
+///
+/// 色の組み合わせが不正なコーナーがある場合は、そのコーナーのステッカーを含む入れ替えのみを試します。
+/// すべてのコーナーが実在するピースであれば（ねじれの合計のみが不正）、ねじりのみを試します。
+fn permuting_actions(colors: &[Color; 24]) -> Vec<RepairAction> {
+    let suspicious = suspicious_stickers(colors);
+    let mut actions = Vec::new();
+    if suspicious.iter().any(|&s| s) {
+        for a in 0..24 {
+            for b in (a + 1)..24 {
+                if colors[a] != colors[b] && (suspicious[a] || suspicious[b]) {
+                    actions.push(RepairAction::Swap { a, b });
+                }
+            }
+        }
+    }
+    for corner in Corner::ALL {
+        for clockwise in [true, false] {
+            actions.push(RepairAction::Twist { corner, clockwise });
+        }
+    }
+    actions
+}
+
+/// 実在するピースに一致しない（鏡像を含む）、または他のスロットと同じピースになっているコーナーのステッカー
+fn suspicious_stickers(colors: &[Color; 24]) -> [bool; 24] {
+    let home = corner::home_colors();
+    let pieces: Vec<Option<Corner>> = Corner::ALL
+        .iter()
+        .map(|slot| {
+            corner::identify_piece(slot.stickers().map(|i| colors[i]), &home).map(|(p, _)| p)
+        })
+        .collect();
+
+    let mut suspicious = [false; 24];
+    for (slot, piece) in Corner::ALL.iter().zip(&pieces) {
+        let bad = match piece {
+            None => true,
+            Some(p) => pieces.iter().filter(|q| **q == Some(*p)).count() > 1,
+        };
+        if bad {
+            for i in slot.stickers() {
+                suspicious[i] = true;
+            }
+        }
+    }
+    suspicious
+}
+
+/// 操作の並び順を決めるキー
+fn action_key(action: RepairAction) -> (u8, usize, usize) {
+    match action {
+        RepairAction::Recolor { index, color } => (0, index, color as usize),
+        RepairAction::Swap { a, b } => (1, a, b),
+        RepairAction::Twist { corner, clockwise } => (2, corner.index(), clockwise as usize),
+    }
+}
+
+/// 色数の過不足を解消する色の変更の組み合わせを列挙
+fn recolor_sets(colors: &[Color; 24]) -> Vec<Vec<RepairAction>> {
+    let expected = Color::ALL;
+    let mut counts: HashMap<Color, usize> = HashMap::new();
+    for &color in colors {
+        *counts.entry(color).or_insert(0) += 1;
+    }
+
+    // 不足している色（不足数だけ並べる）
+    let mut missing = Vec::new();
+    for color in expected {
+        let count = counts.get(&color).copied().unwrap_or(0);
+        missing.extend(std::iter::repeat_n(color, 4usize.saturating_sub(count)));
+    }
+    if missing.is_empty() {
+        return vec![Vec::new()];
+    }
+    if missing.len() > MAX_RECOLORS {
+        return Vec::new();
+    }
+
+    // 変更候補: 多すぎる色、または想定外の色（未設定など）のステッカー
+    let excess: Vec<usize> = (0..24)
+        .filter(|&i| {
+            let color = colors[i];
+            !expected.contains(&color) || counts.get(&color).copied().unwrap_or(0) > 4
+        })
+        .collect();
+
+    let mut sets = Vec::new();
+    let mut current = Vec::new();
+    choose_recolors(colors, &excess, &missing, 0, &mut current, &mut sets);
+    sets
+}
+
+/// 変更候補のステッカーに不足色を割り当てる組み合わせを再帰的に列挙
+fn choose_recolors(
+    colors: &[Color; 24],
+    excess: &[usize],
+    missing: &[Color],
+    start: usize,
+    current: &mut Vec<RepairAction>,
+    sets: &mut Vec<Vec<RepairAction>>,
+) {
+    let Some((&color, rest)) = missing.split_first() else {
+        // 変更後に各色がちょうど4枚になっているものだけを採用
+        let mut result = *colors;
+        for &action in current.iter() {
+            action.apply(&mut result);
+        }
+        if validation::validate_colors(&result).is_ok() {
+            sets.push(current.clone());
+        }
+        return;
+    };
+
+    // 同じ不足色が続く場合は、ステッカーのインデックスを昇順に限定する
+    let repeated = current
+        .last()
+        .is_some_and(|a| matches!(a, RepairAction::Recolor { color: c, .. } if *c == color));
+    let from = if repeated { start } else { 0 };
+
+    for (pos, &index) in excess.iter().enumerate().skip(from) {
+        if current
+            .iter()
+            .any(|a| matches!(a, RepairAction::Recolor { index: i, .. } if *i == index))
+        {
+            continue;
+        }
+        current.push(RepairAction::Recolor { index, color });
+        choose_recolors(colors, excess, rest, pos + 1, current, sets);
+        current.pop();
+    }
+}
+
+/// 色配列が物理的に可能な状態か
+fn is_valid(colors: &[Color; 24]) -> bool {
+    validation::validate_colors(colors).is_ok() && validation::check_corners(colors).is_ok()
+}
@@ -0,0 +1,263 @@
+use super::{validation, Color, Face};
+
+/// 一度に向きを疑う面の最大数
+const MAX_SUSPECT_FACES: usize = 3;
+
+/// 面の入力時の変換（実際の面に対して、入力がどう回転・反転していたか）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FaceTransform {
+    /// 時計回りの90度回転の回数（0-3）。反転がある場合は反転後に回転します。
+    pub quarter_turns: u8,
+    /// 左右反転しているか
+    pub mirrored: bool,
+}
+
+impl FaceTransform {
+    /// 恒等変換以外の7通りの変換
+    fn non_identity() -> impl Iterator<Item = FaceTransform> {
+        [false, true].into_iter().flat_map(|mirrored| {
+            (0..4u8)
+                .map(move |quarter_turns| FaceTransform {
+                    quarter_turns,
+                    mirrored,
+                })
+                .filter(|t| t.quarter_turns != 0 || t.mirrored)
+        })
+    }
+
+    /// 面内の位置の対応表: 変換後の位置 `i` には、変換前の位置 `map[i]` の色が入る
+    fn position_map(self) -> [usize; 4] {
+        // 面内の位置: 0=左上, 1=右上, 2=左下, 3=右下
+        let mut map = if self.mirrored {
+            [1, 0, 3, 2]
+        } else {
+            [0, 1, 2, 3]
+        };
+        for _ in 0..self.quarter_turns {
+            // 時計回り: 左上 <- 左下, 右上 <- 左上, 右下 <- 右上, 左下 <- 右下
+            map = [map[2], map[0], map[3], map[1]];
+        }
+        map
+    }
+
+    /// 面の4色にこの変換を適用
+    fn apply(self, face: [Color; 4]) -> [Color; 4] {
+        self.position_map().map(|i| face[i])
+    }
+
+    /// 面の4色にこの変換の逆変換を適用（入力ミスを元に戻す）
+    fn undo(self, face: [Color; 4]) -> [Color; 4] {
+        let map = self.position_map();
+        let mut result = face;
+        for (dst, &src) in map.iter().enumerate() {
+            result[src] = face[dst];
+        }
+        result
+    }
+
+    /// 変換の説明文
+    #[must_use]
+    pub fn describe(self) -> &'static str {
+        match (self.mirrored, self.quarter_turns % 4) {
+            (false, 0) => "正しい向き",
+            (false, 1) => "時計回りに90°回転",
+            (false, 2) => "180°回転",
+            (false, _) => "反時計回りに90°回転",
+            (true, 0) => "左右反転",
+            (true, 2) => "上下反転",
+            (true, _) => "対角線で反転",
+        }
+    }
+}
+
+/// 1つの面の入力ミス
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FaceEntryError {
+    pub face: Face,
+    /// 入力された面が、本来の面に対してどう変換されていたか
+    pub entered_as: FaceTransform,
+}
+
+impl FaceEntryError {
+    /// 本来の色配列に、この入力ミスを再現した色配列を返します。
+    #[must_use]
+    pub fn apply(self, colors: &[Color; 24]) -> [Color; 24] {
+        let mut result = *colors;
+        let start = self.face.index() * 4;
+        let face = face_colors(colors, self.face);
+        result[start..start + 4].copy_from_slice(&self.entered_as.apply(face));
+        result
+    }
+
+    /// この入力ミスを元に戻した色配列を返します。
+    #[must_use]
+    pub fn undo(self, colors: &[Color; 24]) -> [Color; 24] {
+        let mut result = *colors;
+        let start = self.face.index() * 4;
+        let face = face_colors(colors, self.face);
+        result[start..start + 4].copy_from_slice(&self.entered_as.undo(face));
+        result
+    }
+}
+
+/// 面の向きの入力ミスに対する修正案
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrientationFix {
+    /// 向きを誤って入力したと推定される面
+    pub faces: Vec<FaceEntryError>,
+    /// 修正後の色配列
+    pub colors: [Color; 24],
+}
+
+impl OrientationFix {
+    /// 修正案の説明文
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let uniform = self.faces.len() == Face::ALL.len()
+            && self
+                .faces
+                .iter()
+                .all(|e| e.entered_as == self.faces[0].entered_as);
+        if uniform {
+            return format!(
+                "全ての面が{}した状態で入力されているようです。修正しますか？",
+                self.faces[0].entered_as.describe()
+            );
+        }
+
+        let parts: Vec<String> = self
+            .faces
+            .iter()
+            .map(|e| format!("{}が{}", e.face.label(), e.entered_as.describe()))
+            .collect();
+        format!(
+            "{}した状態で入力されているようです。修正しますか？",
+            parts.join("、")
+        )
+    }
+}
+
+/// スキャン入力の面の向きの誤りを検出し、修正案を返します。
+///
+/// 入力が既に有効な状態であれば空のベクタを返します。
+/// 無効な場合は、各面の回転（90°/180°/270°）と反転をすべて試し、
+/// 有効な状態になる解釈のうち、変更する面の数が最も少ないものを返します。
+/// 全面を同じように誤って入力した場合（展開図全体の鏡像など）も検出します。
+/// 戻り値が1件のときは、その解釈が一意に定まります。
+#[must_use]
+pub fn find_orientation_fixes(colors: &[Color; 24]) -> Vec<OrientationFix> {
+    if validation::check_corners(colors).is_ok() {
+        return Vec::new();
+    }
+
+    let mut fixes: Vec<OrientationFix> = Vec::new();
+
+    // 全面が同じ変換で入力されている場合
+    for transform in FaceTransform::non_identity() {
+        let errors: Vec<FaceEntryError> = Face::ALL
+            .iter()
+            .map(|&face| FaceEntryError {
+                face,
+                entered_as: transform,
+            })
+            .collect();
+        try_candidate(colors, &errors, false, &mut fixes);
+    }
+
+    for count in 1..=MAX_SUSPECT_FACES {
+        for faces in face_subsets(count) {
+            let mut transforms = vec![
+                FaceTransform {
+                    quarter_turns: 0,
+                    mirrored: false,
+                };
+                count
+            ];
+            search_transforms(colors, &faces, &mut transforms, 0, &mut fixes);
+        }
+        if !fixes.is_empty() {
+            break;
+        }
+    }
+
+    fixes
+}
+
+/// 指定した面の組み合わせに対し、各面の変換を総当たりする
+fn search_transforms(
+    colors: &[Color; 24],
+    faces: &[Face],
+    transforms: &mut Vec<FaceTransform>,
+    depth: usize,
+    fixes: &mut Vec<OrientationFix>,
+) {
+    if depth == faces.len() {
+        let errors: Vec<FaceEntryError> = faces
+            .iter()
+            .zip(transforms.iter())
+            .map(|(&face, &entered_as)| FaceEntryError { face, entered_as })
+            .collect();
+        try_candidate(colors, &errors, true, fixes);
+        return;
+    }
+    for transform in FaceTransform::non_identity() {
+        transforms[depth] = transform;
+        search_transforms(colors, faces, transforms, depth + 1, fixes);
+    }
+}
+
+/// 修正候補を適用し、有効な状態になれば修正案に追加する
+fn try_candidate(
+    colors: &[Color; 24],
+    errors: &[FaceEntryError],
+    require_each_face_changed: bool,
+    fixes: &mut Vec<OrientationFix>,
+) {
+    let fixed = errors.iter().fold(*colors, |acc, e| e.undo(&acc));
+
+    // 面の色が対称で変換しても変化しない場合は、その面を疑う意味がない
+    if require_each_face_changed
+        && errors
+            .iter()
+            .any(|e| face_colors(&fixed, e.face) == face_colors(colors, e.face))
+    {
+        return;
+    }
+
+    if validation::check_corners(&fixed).is_ok() && !fixes.iter().any(|f| f.colors == fixed) {
+        fixes.push(OrientationFix {
+            faces: errors.to_vec(),
+            colors: fixed,
+        });
+    }
+}
+
+/// 指定した面の4色を取得
+fn face_colors(colors: &[Color; 24], face: Face) -> [Color; 4] {
+    let start = face.index() * 4;
+    [
+        colors[start],
+        colors[start + 1],
+        colors[start + 2],
+        colors[start + 3],
+    ]
+}
+
+/// 6面から `count` 面を選ぶ組み合わせ
+fn face_subsets(count: usize) -> Vec<Vec<Face>> {
+    let mut result = Vec::new();
+    let mut current = Vec::with_capacity(count);
+    fn recurse(start: usize, count: usize, current: &mut Vec<Face>, result: &mut Vec<Vec<Face>>) {
+        if current.len() == count {
+            result.push(current.clone());
+            return;
+        }
+        for i in start..Face::ALL.len() {
+            current.push(Face::ALL[i]);
+            recurse(i + 1, count, current, result);
+            current.pop();
+        }
+    }
+    recurse(0, count, &mut current, &mut result);
+    result
+}
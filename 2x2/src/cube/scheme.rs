@@ -0,0 +1,265 @@
+use super::{validation, Color, Corner, Face};
+use crate::error::{CubeError, Result};
+use std::collections::HashSet;
+
+/// 表示用のRGB値の既定値（`Color` の順、最後は未設定用のグレー）
+const DEFAULT_PALETTE: [[u8; 3]; 7] = [
+    [255, 255, 255],
+    [255, 255, 0],
+    [0, 200, 0],
+    [0, 100, 255],
+    [255, 50, 50],
+    [255, 165, 0],
+    [180, 180, 180],
+];
+
+/// キューブの配色
+///
+/// 各面の色（反対色の組を含む）と、表示に使うRGB値を保持します。
+///
+/// `Cube` の内部表現は常に標準配色（`ColorScheme::standard`）です。
+/// 別の配色のキューブを入力・保存・表示するときは、`to_standard` / `from_standard` で
+/// 面ごとに色を読み替えます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColorScheme {
+    /// 各面の色（`Face::ALL` の順）
+    faces: [Color; 6],
+    /// 表示用のRGB値（`Color` の順）
+    palette: [[u8; 3]; 7],
+}
+
+impl ColorScheme {
+    /// 標準配色（白の反対が黄、緑の反対が青、赤の反対が橙）
+    #[must_use]
+    pub fn standard() -> Self {
+        Self {
+            faces: [
+                Color::White,
+                Color::Yellow,
+                Color::Green,
+                Color::Blue,
+                Color::Red,
+                Color::Orange,
+            ],
+            palette: DEFAULT_PALETTE,
+        }
+    }
+
+    /// 日本配色（白の反対が青、緑の反対が黄、赤の反対が橙）
+    #[must_use]
+    pub fn japanese() -> Self {
+        Self {
+            faces: [
+                Color::White,
+                Color::Blue,
+                Color::Green,
+                Color::Yellow,
+                Color::Red,
+                Color::Orange,
+            ],
+            palette: DEFAULT_PALETTE,
+        }
+    }
+
+    /// 名前付きの配色の一覧
+    #[must_use]
+    pub fn presets() -> [(&'static str, ColorScheme); 2] {
+        [
+            ("標準配色", Self::standard()),
+            ("日本配色", Self::japanese()),
+        ]
+    }
+
+    /// 各面の色（`Face::ALL` の順）を指定して配色を作成します。
+    ///
+    /// 6色がちょうど1回ずつ使われている必要があります。
+    pub fn new(faces: [Color; 6]) -> Result<Self> {
+        for color in Color::ALL {
+            let count = faces.iter().filter(|&&c| c == color).count();
+            if count != 1 {
+                return Err(CubeError::InvalidColors(format!(
+                    "配色の{}が{}面に割り当てられています（1面である必要があります）",
+                    color.label(),
+                    count
+                )));
+            }
+        }
+        Ok(Self {
+            faces,
+            palette: DEFAULT_PALETTE,
+        })
+    }
+
+    /// 配色の名前（名前付きの配色を回転したものでなければ「カスタム配色」）
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        Self::presets()
+            .into_iter()
+            .find(|(_, preset)| self.is_rotation_of(preset))
+            .map_or("カスタム配色", |(name, _)| name)
+    }
+
+    /// 指定した面の色
+    #[must_use]
+    pub fn face_color(&self, face: Face) -> Color {
+        self.faces[face.index()]
+    }
+
+    /// 指定した色の面（グレーは `None`）
+    #[must_use]
+    pub fn face_of(&self, color: Color) -> Option<Face> {
+        Face::ALL
+            .into_iter()
+            .find(|&face| self.face_color(face) == color)
+    }
+
+    /// 反対側の面の色（グレーは `None`）
+    #[must_use]
+    pub fn opposite(&self, color: Color) -> Option<Color> {
+        self.face_of(color)
+            .map(|face| self.face_color(face.opposite()))
+    }
+
+    /// 反対色の組（U/D, L/R, F/B）
+    #[must_use]
+    pub fn opposite_pairs(&self) -> [(Color, Color); 3] {
+        [
+            (self.face_color(Face::Up), self.face_color(Face::Down)),
+            (self.face_color(Face::Left), self.face_color(Face::Right)),
+            (self.face_color(Face::Front), self.face_color(Face::Back)),
+        ]
+    }
+
+    /// 色の表示用RGB値
+    #[must_use]
+    pub fn rgb(&self, color: Color) -> [u8; 3] {
+        self.palette[color as usize]
+    }
+
+    /// 色の表示用RGB値を変更
+    pub fn set_rgb(&mut self, color: Color, rgb: [u8; 3]) {
+        self.palette[color as usize] = rgb;
+    }
+
+    /// 内部表現（標準配色）の色を、この配色で表示するときのRGB値
+    #[must_use]
+    pub fn display_rgb(&self, color: Color) -> [u8; 3] {
+        self.rgb(self.from_standard_color(color))
+    }
+
+    /// この配色の色を、同じ面の標準配色の色に読み替え
+    #[must_use]
+    pub fn to_standard_color(&self, color: Color) -> Color {
+        self.face_of(color)
+            .map_or(color, |face| Self::standard().face_color(face))
+    }
+
+    /// 標準配色の色を、同じ面のこの配色の色に読み替え
+    #[must_use]
+    pub fn from_standard_color(&self, color: Color) -> Color {
+        Self::standard()
+            .face_of(color)
+            .map_or(color, |face| self.face_color(face))
+    }
+
+    /// この配色の色配列を標準配色に読み替え
+    #[must_use]
+    pub fn to_standard(&self, colors: &[Color; 24]) -> [Color; 24] {
+        colors.map(|c| self.to_standard_color(c))
+    }
+
+    /// 標準配色の色配列をこの配色に読み替え
+    #[must_use]
+    pub fn from_standard(&self, colors: &[Color; 24]) -> [Color; 24] {
+        colors.map(|c| self.from_standard_color(c))
+    }
+
+    /// この配色での完成状態の色配列
+    #[must_use]
+    pub fn solved_colors(&self) -> [Color; 24] {
+        std::array::from_fn(|i| self.faces[i / 4])
+    }
+
+    /// キューブ全体を回転させたときに一致する配色か（表示用のRGB値は比較しません）
+    #[must_use]
+    pub fn is_rotation_of(&self, other: &ColorScheme) -> bool {
+        other.rotations().contains(&self.faces)
+    }
+
+    /// キューブ全体の回転24通りに対応する、各面の色の並び
+    fn rotations(&self) -> HashSet<[Color; 6]> {
+        // 面の並び（U, D, L, R, F, B）に対する置換: 回転後の面 i には元の面 perm[i] の色が来る
+        const X: [usize; 6] = [4, 5, 2, 3, 1, 0];
+        const Y: [usize; 6] = [0, 1, 4, 5, 3, 2];
+
+        let mut seen = HashSet::from([self.faces]);
+        let mut stack = vec![self.faces];
+        while let Some(faces) = stack.pop() {
+            for perm in [X, Y] {
+                let rotated = perm.map(|i| faces[i]);
+                if seen.insert(rotated) {
+                    stack.push(rotated);
+                }
+            }
+        }
+        seen
+    }
+
+    /// スキャンした色配列から配色を推定します。
+    ///
+    /// 同じコーナーに一度も現れない色の組を反対色とし、DBLのコーナーにある3色を
+    /// D・B・L面の色として各面の色を決めます。推定した配色で物理的に有効な状態に
+    /// ならない場合はエラーになります。名前付きの配色を回転したものであれば、
+    /// その配色を返します。
+    pub fn infer(colors: &[Color; 24]) -> Result<ColorScheme> {
+        validation::validate_colors(colors)?;
+
+        // 同じコーナーに現れる色の組
+        let mut together = [[false; 7]; 7];
+        for corner in Corner::ALL {
+            let corner_colors = corner.stickers().map(|i| colors[i]);
+            for a in corner_colors {
+                for b in corner_colors {
+                    together[a as usize][b as usize] = true;
+                }
+            }
+        }
+
+        let opposite = |color: Color| -> Result<Color> {
+            let candidates: Vec<Color> = Color::ALL
+                .into_iter()
+                .filter(|&other| !together[color as usize][other as usize])
+                .collect();
+            match candidates.as_slice() {
+                [other] => Ok(*other),
+                _ => Err(CubeError::InvalidState(format!(
+                    "{}の反対色を特定できません",
+                    color.label()
+                ))),
+            }
+        };
+
+        let [down, back, left] = Corner::Dbl.stickers().map(|i| colors[i]);
+        let scheme = Self::new([
+            opposite(down)?,
+            down,
+            left,
+            opposite(left)?,
+            opposite(back)?,
+            back,
+        ])?;
+        validation::check_corners(&scheme.to_standard(colors))?;
+
+        Ok(Self::presets()
+            .into_iter()
+            .map(|(_, preset)| preset)
+            .find(|preset| scheme.is_rotation_of(preset))
+            .unwrap_or(scheme))
+    }
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
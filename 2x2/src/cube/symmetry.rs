@@ -0,0 +1,296 @@
+use super::{Color, Cube, Face, Move, Sticker};
+use std::sync::OnceLock;
+
+/// ステッカーの位置の並べ替え（変換後の位置 `i` には、変換前の位置 `from[i]` のステッカーが入る）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct StickerMap {
+    from: [u8; 24],
+}
+
+impl StickerMap {
+    /// 恒等変換
+    pub(crate) fn identity() -> Self {
+        Self {
+            from: std::array::from_fn(|i| i as u8),
+        }
+    }
+
+    /// 回転操作と同じ並べ替え
+    pub(crate) fn from_move(mv: Move) -> Self {
+        let mut from = [0u8; 24];
+        for src in 0..24 {
+            // 1枚だけ色の違うステッカーを置き、移動先を調べる
+            let mut cube = Cube {
+                stickers: [Sticker::new(Color::Gray); 24],
+            };
+            cube.stickers[src] = Sticker::new(Color::White);
+            cube.apply_move(mv);
+
+            let dst = cube
+                .stickers
+                .iter()
+                .position(|s| s.color == Color::White)
+                .expect("回転操作でステッカーが失われることはありません");
+            from[dst] = src as u8;
+        }
+        Self { from }
+    }
+
+    /// M面（左右の中央）を鏡として反転する並べ替え
+    fn mirror() -> Self {
+        // 各面内で左右を入れ替え、L面とR面は互いに入れ替わる
+        const PAIRS: [(u8, u8); 12] = [
+            (0, 1),
+            (2, 3),
+            (4, 5),
+            (6, 7),
+            (8, 13),
+            (9, 12),
+            (10, 15),
+            (11, 14),
+            (16, 17),
+            (18, 19),
+            (20, 21),
+            (22, 23),
+        ];
+        let mut from = [0u8; 24];
+        for (a, b) in PAIRS {
+            from[a as usize] = b;
+            from[b as usize] = a;
+        }
+        Self { from }
+    }
+
+    /// `self` の後に `next` を適用する並べ替え
+    pub(crate) fn then(&self, next: &StickerMap) -> Self {
+        Self {
+            from: std::array::from_fn(|i| self.from[next.from[i] as usize]),
+        }
+    }
+
+    /// 逆変換
+    pub(crate) fn inverse(&self) -> Self {
+        let mut from = [0u8; 24];
+        for (i, &src) in self.from.iter().enumerate() {
+            from[src as usize] = i as u8;
+        }
+        Self { from }
+    }
+
+    /// 色配列を並べ替える
+    pub(crate) fn apply(&self, colors: &[Color; 24]) -> [Color; 24] {
+        std::array::from_fn(|i| colors[self.from[i] as usize])
+    }
+
+    /// 変換前の位置 `index` のステッカーが移る位置
+    pub(crate) fn destination(&self, index: usize) -> usize {
+        self.inverse().from[index] as usize
+    }
+}
+
+/// キューブの対称変換（持ち替え24通りと、その鏡像24通り）
+///
+/// 2x2では色の配置からステッカーの向きが決まるため、対称変換は色の配置のみを移し、
+/// 結果のキューブの向きは0（`Cube::normalized` と同じ）になります。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symmetry {
+    index: usize,
+    map: StickerMap,
+    mirror: bool,
+    /// 変換後も標準配色になるための色の読み替え（`Color` の順）
+    recolor: [Color; 7],
+}
+
+static SYMMETRIES: OnceLock<Vec<Symmetry>> = OnceLock::new();
+
+impl Symmetry {
+    /// 全48通りの対称変換（先頭24通りが回転、残りが鏡像。先頭は恒等変換）
+    pub fn all() -> &'static [Symmetry] {
+        SYMMETRIES.get_or_init(generate_symmetries)
+    }
+
+    /// 回転のみの24通りの対称変換
+    pub fn rotations() -> &'static [Symmetry] {
+        &Self::all()[..24]
+    }
+
+    /// 恒等変換
+    pub fn identity() -> &'static Symmetry {
+        &Self::all()[0]
+    }
+
+    /// 変換の番号（0-47、`all` の並び順）
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// 鏡像を含む変換か
+    #[must_use]
+    pub fn is_mirror(&self) -> bool {
+        self.mirror
+    }
+
+    /// 持ち替え（恒等変換以外の回転）を含む変換か
+    #[must_use]
+    pub fn has_rotation(&self) -> bool {
+        !self.index.is_multiple_of(24)
+    }
+
+    /// 逆変換
+    pub fn inverse(&self) -> &'static Symmetry {
+        let inverse = self.map.inverse();
+        Self::all()
+            .iter()
+            .find(|s| s.map == inverse)
+            .expect("対称変換の逆変換は対称変換です")
+    }
+
+    /// この変換で色がどう読み替えられるか
+    #[must_use]
+    pub fn recolor(&self, color: Color) -> Color {
+        self.recolor[color as usize]
+    }
+
+    /// 各面が `transform` によって移る先の面（`Face::ALL` の順）
+    #[must_use]
+    pub fn face_map(&self) -> [Face; 6] {
+        Face::ALL.map(|face| Face::ALL[self.map.destination(face.index() * 4) / 4])
+    }
+
+    /// キューブ全体を空間的に変換します（色は読み替えません）。
+    ///
+    /// 回転の場合は、同じ局面を別の向きで持ち替えた状態になります。
+    /// 鏡像の場合は、鏡に映したキューブ（物理的には存在しない配色）になります。
+    #[must_use]
+    pub fn transform(&self, cube: &Cube) -> Cube {
+        let colors = self.map.apply(&cube.stickers.map(|s| s.color));
+        Cube {
+            stickers: colors.map(Sticker::new),
+        }
+    }
+
+    /// 局面をこの対称変換で移した局面を返します。
+    ///
+    /// 空間的に変換したうえで色を読み替えるため、完成状態は完成状態に移り、
+    /// 有効な局面は有効な局面に移ります（手順 `R U` の局面を鏡像で移すと `L' U'` の局面になります）。
+    #[must_use]
+    pub fn conjugate(&self, cube: &Cube) -> Cube {
+        let mut result = self.transform(cube);
+        for sticker in &mut result.stickers {
+            sticker.color = self.recolor(sticker.color);
+        }
+        result
+    }
+}
+
+/// 48通りの対称変換を生成
+fn generate_symmetries() -> Vec<Symmetry> {
+    // 持ち替えは U D' (y) と R L' (x) の組み合わせで24通りすべて得られる
+    let y = StickerMap::from_move(Move::U).then(&StickerMap::from_move(Move::Dp));
+    let x = StickerMap::from_move(Move::R).then(&StickerMap::from_move(Move::Lp));
+
+    let mut rotations = vec![StickerMap::identity()];
+    let mut next = 0;
+    while next < rotations.len() {
+        let current = rotations[next];
+        for generator in [&y, &x] {
+            let candidate = current.then(generator);
+            if !rotations.contains(&candidate) {
+                rotations.push(candidate);
+            }
+        }
+        next += 1;
+    }
+    debug_assert_eq!(rotations.len(), 24);
+    let rotations_len = rotations.len();
+
+    let mirror = StickerMap::mirror();
+    let mirrored: Vec<StickerMap> = rotations.iter().map(|r| mirror.then(r)).collect();
+
+    let solved = Cube::new();
+    rotations
+        .into_iter()
+        .chain(mirrored)
+        .enumerate()
+        .map(|(index, map)| {
+            let mirror = index >= rotations_len;
+            // 各面の色を、その面のステッカーが移る先の面の色に読み替える
+            let mut recolor = [Color::Gray; 7];
+            for face_start in (0..24).step_by(4) {
+                let color = solved.stickers[face_start].color;
+                let dst = map.destination(face_start);
+                recolor[color as usize] = solved.stickers[dst].color;
+            }
+            Symmetry {
+                index,
+                map,
+                mirror,
+                recolor,
+            }
+        })
+        .collect()
+}
+
+/// 対称性による同値類
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymmetryClass {
+    /// 同値類の代表（`Cube::canonical` と同じ）
+    pub canonical: Cube,
+    /// 局面を代表に移す対称変換（持ち替えは含みません）
+    pub symmetry: Symmetry,
+    /// 同値類に含まれる、持ち替えを同一視した異なる局面の数（1-48）
+    pub size: usize,
+    /// 鏡像の局面が、回転による対称変換と持ち替えだけで元の局面に一致するか
+    pub mirror_symmetric: bool,
+}
+
+/// 局面の比較に使うキー（持ち替えを同一視し、最小のものを代表とする）
+fn sort_key(cube: &Cube) -> [u8; 24] {
+    cube.stickers.map(|s| s.color as u8)
+}
+
+/// 持ち替え24通りのうち、キーが最小になるもの
+pub(crate) fn min_reorientation(cube: &Cube) -> Cube {
+    Symmetry::rotations()
+        .iter()
+        .map(|r| r.transform(cube))
+        .min_by_key(sort_key)
+        .expect("回転は24通りあります")
+}
+
+/// 対称変換48通りと持ち替え24通りを組み合わせた中で、最小の局面と、そのときの対称変換
+pub(crate) fn canonical_with_symmetry(cube: &Cube) -> (Cube, Symmetry) {
+    Symmetry::all()
+        .iter()
+        .map(|s| (min_reorientation(&s.conjugate(cube)), *s))
+        .min_by_key(|(c, _)| sort_key(c))
+        .expect("対称変換は48通りあります")
+}
+
+/// 局面の対称性による同値類を求める
+pub(crate) fn symmetry_class(cube: &Cube) -> SymmetryClass {
+    let images: Vec<Cube> = Symmetry::all()
+        .iter()
+        .map(|s| min_reorientation(&s.conjugate(cube)))
+        .collect();
+
+    let mut distinct: Vec<&Cube> = Vec::new();
+    for image in &images {
+        if !distinct.contains(&image) {
+            distinct.push(image);
+        }
+    }
+
+    let (canonical, symmetry) = canonical_with_symmetry(cube);
+    let mirror_symmetric = images[24..]
+        .iter()
+        .any(|image| images[..24].contains(image));
+
+    SymmetryClass {
+        canonical,
+        symmetry,
+        size: distinct.len(),
+        mirror_symmetric,
+    }
+}
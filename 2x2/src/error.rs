@@ -38,6 +38,53 @@ pub enum CubeError {
     /// コーナーパリティエラー
     #[error("コーナーパリティエラー: {0}")]
     CornerParity(String),
+
+    /// ファイルの解析エラー（行・列つき）
+    #[error("ファイルの解析エラー: {0}")]
+    Parse(ParseError),
+}
+
+/// ファイルの解析エラーの位置と内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 行番号（1から）
+    pub line: usize,
+    /// 列番号（1から、タブも1文字と数える）
+    pub column: usize,
+    /// 必要だったもの
+    pub expected: String,
+    /// 実際にあったもの
+    pub found: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}行{}列目: {}が必要ですが、{}があります",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+/// 位置つきの解析エラー（各ファイル形式の読み込みで共通）
+pub(crate) fn parse_error(
+    line: usize,
+    column: usize,
+    expected: &str,
+    found: impl Into<String>,
+) -> CubeError {
+    CubeError::Parse(ParseError {
+        line,
+        column,
+        expected: expected.to_string(),
+        found: found.into(),
+    })
+}
+
+/// 行の最初の空白でない文字の列（1から）
+pub(crate) fn first_column(line: &str) -> usize {
+    line.chars().take_while(|c| c.is_whitespace()).count() + 1
 }
 
 /// Result型のエイリアス
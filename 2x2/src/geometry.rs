@@ -0,0 +1,449 @@
+//! 局面の描画に共通する幾何
+//!
+//! GUI（`gui::renderer`・`gui::renderer_3d`）と、GUIを使わない出力（`svg`・`raster`・`model`）が
+//! 同じ絵を描けるように、展開図の配置・3D表示の投影・回転のイージング・書き出す数値の書式をまとめています。
+
+use crate::cube::{Color, ColorScheme, Cube, Face, Move};
+use egui::{Color32, Pos2, Rect};
+use glam::{Mat4, Vec3};
+use std::f32::consts::PI;
+
+/// デフォルトのアニメーション時間(秒)
+pub(crate) const DEFAULT_ANIMATION_DURATION: f32 = 0.3;
+
+/// 展開図の列数（1マスが1ステッカー）
+pub(crate) const NET_COLUMNS: usize = 8;
+/// 展開図の行数
+pub(crate) const NET_ROWS: usize = 6;
+
+/// マスに対するステッカーの大きさ（GUIと同じく、マスの85%の95%）
+pub(crate) const STICKER_SCALE: f32 = 0.85 * 0.95;
+/// マスに対する向きの矢印の長さ
+pub(crate) const ARROW_SCALE: f32 = 0.85 * 0.3;
+
+/// 面の左上のマス（列, 行）
+///
+/// U面を上、D面を下に置き、中段に左からL・F・R・Bの順に並べた十字形です。
+pub(crate) fn net_face_origin(face: Face) -> (usize, usize) {
+    match face {
+        Face::Up => (2, 0),
+        Face::Down => (2, 4),
+        Face::Left => (0, 2),
+        Face::Right => (4, 2),
+        Face::Front => (2, 2),
+        Face::Back => (6, 2),
+    }
+}
+
+/// ステッカーのマス（列, 行）
+pub(crate) fn net_cell(index: usize) -> (usize, usize) {
+    let (col, row) = net_face_origin(Face::ALL[index / 4]);
+    (col + index % 2, row + index % 4 / 2)
+}
+
+/// イージング関数 (ease-in-out)
+///
+/// 回転の進み具合（0.0-1.0）を、始めと終わりがゆっくりになるように変換します。
+pub(crate) fn ease_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        2.0 * t * t
+    } else {
+        -1.0 + (4.0 - 2.0 * t) * t
+    }
+}
+
+/// ステッカーの色（内部表現の標準配色）を、配色に従ってegui Color32に変換
+pub(crate) fn color_to_color32(scheme: &ColorScheme, color: Color) -> Color32 {
+    let [r, g, b] = scheme.display_rgb(color);
+    Color32::from_rgb(r, g, b)
+}
+
+/// 3D描画の設定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct View3D {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub scale: f32,
+}
+
+impl Default for View3D {
+    fn default() -> Self {
+        Self {
+            yaw: PI / 4.0,
+            pitch: PI / 6.0,
+            scale: 1.0,
+        }
+    }
+}
+
+/// ステッカーの3D情報
+#[derive(Clone, Copy)]
+pub(crate) struct Sticker3D {
+    pub(crate) index: usize,
+    pub(crate) center: Vec3,
+    pub(crate) normal: Vec3,
+    pub(crate) u_vec: Vec3, // ステッカーの「右」方向
+    pub(crate) v_vec: Vec3, // ステッカーの「下」方向
+}
+
+/// ステッカーの枠線の太さ
+pub(crate) const OUTLINE_WIDTH: f32 = 1.0;
+/// 編集中の面の枠線の太さ
+pub(crate) const HIGHLIGHT_WIDTH: f32 = 3.0;
+/// 編集中の面の枠線の色
+pub(crate) const HIGHLIGHT_COLOR: Color32 = Color32::from_rgb(255, 140, 0);
+/// 向きの矢印の太さ
+pub(crate) const ARROW_WIDTH: f32 = 6.0;
+
+/// 描画用の頂点データ
+pub(crate) struct DrawFace {
+    z_score: f32,
+    pub(crate) points: Vec<Pos2>,
+    pub(crate) color: Color32,
+    pub(crate) sticker_index: usize,
+    pub(crate) center_2d: Pos2,
+    u_vec_2d: egui::Vec2,
+    v_vec_2d: egui::Vec2,
+}
+
+impl DrawFace {
+    /// ステッカーの向きを示す矢印のベクトル（投影後）
+    ///
+    /// 0: 上 (-v), 1: 右 (+u), 2: 下 (+v), 3: 左 (-u)
+    /// ※ rotate_cwで+1されるため
+    pub(crate) fn arrow_vector(&self, orientation: u8) -> egui::Vec2 {
+        match orientation {
+            0 => -self.v_vec_2d,
+            1 => self.u_vec_2d,
+            2 => self.v_vec_2d,
+            3 => -self.u_vec_2d,
+            _ => egui::Vec2::ZERO,
+        }
+    }
+}
+
+/// 3D投影ヘルパー関数
+fn project_point(
+    p: Vec3,
+    model_mat: &Mat4,
+    view_mat: &Mat4,
+    scale: f32,
+    screen_center: Pos2,
+) -> Pos2 {
+    let world = model_mat.transform_point3(p);
+    let view = view_mat.transform_point3(world);
+    let distance = 5.0 - view.z;
+    let perspective = 5.0 / distance;
+    Pos2::new(
+        screen_center.x + view.x * scale * perspective,
+        screen_center.y - view.y * scale * perspective,
+    )
+}
+
+/// ステッカーの初期3D配置を生成
+pub(crate) fn get_initial_stickers() -> Vec<Sticker3D> {
+    let mut stickers = Vec::with_capacity(24);
+    let size = 0.45; // ステッカーのサイズ（少し小さくして境界を作る）
+
+    // ヘルパー: 面ごとの生成
+    // U (Up): y = +1, index 0-3
+    for i in 0..4 {
+        let col = (i % 2) as f32; // 0, 1
+        let row = (i / 2) as f32; // 0, 1
+        let x = (col - 0.5) * 1.0;
+        let z = (row - 0.5) * 1.0;
+        stickers.push(Sticker3D {
+            index: i,
+            center: Vec3::new(x, 1.0, z),
+            normal: Vec3::Y,
+            u_vec: Vec3::X * size,
+            v_vec: Vec3::Z * size,
+        });
+    }
+
+    // D (Down): y = -1, index 4-7
+    for i in 0..4 {
+        let col = (i % 2) as f32;
+        let row = (i / 2) as f32;
+        let x = (col - 0.5) * 1.0;
+        let z = (1.0 - row - 0.5) * 1.0;
+        stickers.push(Sticker3D {
+            index: 4 + i,
+            center: Vec3::new(x, -1.0, z),
+            normal: -Vec3::Y,
+            u_vec: Vec3::X * size,
+            v_vec: -Vec3::Z * size,
+        });
+    }
+
+    // L (Left): x = -1, index 8-11
+    for i in 0..4 {
+        let col = (i % 2) as f32;
+        let row = (i / 2) as f32;
+        let z = (col - 0.5) * 1.0;
+        let y = (1.0 - row - 0.5) * 1.0;
+        stickers.push(Sticker3D {
+            index: 8 + i,
+            center: Vec3::new(-1.0, y, z),
+            normal: -Vec3::X,
+            u_vec: Vec3::Z * size, // 修正: -Vec3::Z から Vec3::Z
+            v_vec: -Vec3::Y * size,
+        });
+    }
+
+    // R (Right): x = +1, index 12-15
+    for i in 0..4 {
+        let col = (i % 2) as f32;
+        let row = (i / 2) as f32;
+        let z = (1.0 - col - 0.5) * 1.0;
+        let y = (1.0 - row - 0.5) * 1.0;
+        stickers.push(Sticker3D {
+            index: 12 + i,
+            center: Vec3::new(1.0, y, z),
+            normal: Vec3::X,
+            u_vec: -Vec3::Z * size, // 修正: Vec3::Z から -Vec3::Z
+            v_vec: -Vec3::Y * size,
+        });
+    }
+
+    // F (Front): z = +1, index 16-19
+    for i in 0..4 {
+        let col = (i % 2) as f32;
+        let row = (i / 2) as f32;
+        let x = (col - 0.5) * 1.0;
+        let y = (1.0 - row - 0.5) * 1.0;
+        stickers.push(Sticker3D {
+            index: 16 + i,
+            center: Vec3::new(x, y, 1.0),
+            normal: Vec3::Z,
+            u_vec: Vec3::X * size,
+            v_vec: -Vec3::Y * size,
+        });
+    }
+
+    // B (Back): z = -1, index 20-23
+    for i in 0..4 {
+        let col = (i % 2) as f32;
+        let row = (i / 2) as f32;
+        let x = (1.0 - col - 0.5) * 1.0;
+        let y = (1.0 - row - 0.5) * 1.0;
+        stickers.push(Sticker3D {
+            index: 20 + i,
+            center: Vec3::new(x, y, -1.0),
+            normal: -Vec3::Z,
+            u_vec: -Vec3::X * size,
+            v_vec: -Vec3::Y * size,
+        });
+    }
+
+    stickers
+}
+
+/// 矢印の先端の三角形（矢印が極端に小さい場合は `None`）
+pub(crate) fn arrow_head(center: Pos2, target: Pos2) -> Option<[Pos2; 3]> {
+    let direction = target - center;
+    let arrow_length = direction.length();
+    if arrow_length < 0.1 {
+        return None;
+    }
+
+    let dir_normalized = direction.normalized();
+    let arrow_head_size = arrow_length * 0.6;
+    let perpendicular = egui::vec2(-dir_normalized.y, dir_normalized.x);
+
+    let tip = target;
+    let left = target - dir_normalized * arrow_head_size + perpendicular * arrow_head_size * 0.5;
+    let right = target - dir_normalized * arrow_head_size - perpendicular * arrow_head_size * 0.5;
+    Some([tip, left, right])
+}
+
+/// 画面上の投影のパラメータ（描画領域の中心と倍率）
+pub(crate) fn screen_projection(rect: Rect, view: &View3D) -> (Pos2, f32) {
+    let min_dim = rect.width().min(rect.height());
+    (rect.center(), min_dim * 0.3 * view.scale)
+}
+
+/// 回転中の手の回転（軸・層・角度）
+///
+/// `turn` は回転中の手と、イージング後の進み具合（0.0-1.0）です。
+fn turn_rotation(turn: Option<(Move, f32)>) -> (Vec3, i32, f32) {
+    let Some((mv, progress)) = turn else {
+        return (Vec3::X, 0, 0.0);
+    };
+    let base_angle = match mv {
+        Move::R2 | Move::L2 | Move::U2 | Move::D2 | Move::F2 | Move::B2 => 180.0f32.to_radians(),
+        _ => 90.0f32.to_radians(),
+    };
+    let angle = progress * base_angle;
+    let angle = match mv {
+        Move::R
+        | Move::L
+        | Move::U
+        | Move::D
+        | Move::F
+        | Move::B
+        | Move::R2
+        | Move::L2
+        | Move::U2
+        | Move::D2
+        | Move::F2
+        | Move::B2 => angle,
+        _ => -angle, // Prime moves
+    };
+
+    match mv {
+        Move::R | Move::Rp | Move::R2 => (Vec3::X, 1, -angle), // Right is x > 0
+        Move::L | Move::Lp | Move::L2 => (Vec3::X, -1, angle), // Left is x < 0
+        Move::U | Move::Up | Move::U2 => (Vec3::Y, 1, -angle), // Up is y > 0
+        Move::D | Move::Dp | Move::D2 => (Vec3::Y, -1, angle), // Down is y < 0
+        Move::F | Move::Fp | Move::F2 => (Vec3::Z, 1, -angle), // Front is z > 0
+        Move::B | Move::Bp | Move::B2 => (Vec3::Z, -1, angle), // Back is z < 0
+    }
+}
+
+/// カメラに向いているステッカーを投影し、奥から手前の順に並べる
+///
+/// GUIの描画とヘッドレスの描画（`raster`）で共通です。`turn` は `turn_rotation` と同じです。
+pub(crate) fn project_faces(
+    rect: Rect,
+    cube: &Cube,
+    scheme: &ColorScheme,
+    turn: Option<(Move, f32)>,
+    view: &View3D,
+) -> Vec<DrawFace> {
+    // カメラ設定
+    let (center, scale) = screen_projection(rect, view);
+
+    // ビュー行列 (Orbit camera)
+    let rotation = Mat4::from_rotation_x(view.pitch) * Mat4::from_rotation_y(view.yaw);
+    let view_mat = rotation; // カメラ位置は固定、オブジェクトを回転させるイメージ
+
+    // アニメーション情報取得
+    let (anim_axis, anim_layer, anim_angle) = turn_rotation(turn);
+
+    let initial_stickers = get_initial_stickers();
+    let mut draw_faces = Vec::new();
+
+    for sticker_def in initial_stickers {
+        // 現在のステッカーの状態（色）を取得
+        let sticker_data = cube.get_sticker(sticker_def.index);
+        let color = color_to_color32(scheme, sticker_data.color);
+
+        // アニメーション回転の適用
+        let mut model_mat = Mat4::IDENTITY;
+
+        if turn.is_some() {
+            let is_affected = match anim_axis {
+                v if v == Vec3::X => {
+                    (sticker_def.center.x > 0.0 && anim_layer == 1)
+                        || (sticker_def.center.x < 0.0 && anim_layer == -1)
+                }
+                v if v == Vec3::Y => {
+                    (sticker_def.center.y > 0.0 && anim_layer == 1)
+                        || (sticker_def.center.y < 0.0 && anim_layer == -1)
+                }
+                v if v == Vec3::Z => {
+                    (sticker_def.center.z > 0.0 && anim_layer == 1)
+                        || (sticker_def.center.z < 0.0 && anim_layer == -1)
+                }
+                _ => false,
+            };
+
+            if is_affected {
+                model_mat = Mat4::from_axis_angle(anim_axis, anim_angle);
+            }
+        }
+
+        // 頂点の計算
+        let corners = [
+            sticker_def.center - sticker_def.u_vec - sticker_def.v_vec,
+            sticker_def.center + sticker_def.u_vec - sticker_def.v_vec,
+            sticker_def.center + sticker_def.u_vec + sticker_def.v_vec,
+            sticker_def.center - sticker_def.u_vec + sticker_def.v_vec,
+        ];
+
+        let mut transformed_corners = Vec::with_capacity(4);
+        let mut avg_z = 0.0;
+
+        // 法線の変換
+        let normal_transformed =
+            view_mat.transform_vector3(model_mat.transform_vector3(sticker_def.normal));
+
+        // バックフェイスカリング（簡易）
+        // view_matで変換した結果、Zが正ならカメラに向いている
+        if normal_transformed.z > 0.2 {
+            // 少し余裕を持たせる
+            for p in corners {
+                let p_world = model_mat.transform_point3(p);
+                let p_view = view_mat.transform_point3(p_world);
+
+                // 透視投影っぽい効果 (Zに応じてスケール)
+                let distance = 5.0 - p_view.z; // カメラ距離
+                let perspective = 5.0 / distance;
+
+                let x = center.x + p_view.x * scale * perspective;
+                let y = center.y - p_view.y * scale * perspective; // Y-up to Y-down screen
+
+                transformed_corners.push(Pos2::new(x, y));
+                avg_z += p_view.z;
+            }
+            avg_z /= 4.0;
+
+            // ステッカーの中心を計算（2D投影後）
+            let center_2d = project_point(sticker_def.center, &model_mat, &view_mat, scale, center);
+
+            // U方向とV方向のベクトルを計算（2D投影後）
+            // ベクトルの先端を計算してから差分を取る
+            let u_end_2d = project_point(
+                sticker_def.center + sticker_def.u_vec * 0.6,
+                &model_mat,
+                &view_mat,
+                scale,
+                center,
+            );
+            let v_end_2d = project_point(
+                sticker_def.center + sticker_def.v_vec * 0.6,
+                &model_mat,
+                &view_mat,
+                scale,
+                center,
+            );
+
+            let u_vec_2d = u_end_2d - center_2d;
+            let v_vec_2d = v_end_2d - center_2d;
+
+            draw_faces.push(DrawFace {
+                z_score: avg_z,
+                points: transformed_corners,
+                color,
+                sticker_index: sticker_def.index,
+                center_2d,
+                u_vec_2d,
+                v_vec_2d,
+            });
+        }
+    }
+
+    // Zソート（奥から手前へ）
+    draw_faces.sort_by(|a, b| {
+        a.z_score
+            .partial_cmp(&b.z_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    draw_faces
+}
+
+/// 書き出す座標の数値（小数第 `decimals` 位まで、末尾の0と `-0` の符号は省く）
+pub(crate) fn format_number(value: f32, decimals: usize) -> String {
+    let text = format!("{value:.decimals$}");
+    let text = if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &text
+    };
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
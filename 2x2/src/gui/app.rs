@@ -1,7 +1,18 @@
-use crate::cube::{Color, Cube, Move};
-use crate::gui::renderer_3d::{draw_cube_3d, View3D};
+use crate::alg_file::{AlgFile, ALG_FILE_EXTENSION};
+use crate::algorithm::Algorithm;
+use crate::cases;
+use crate::cube::repair::{self, Repair};
+use crate::cube::scan::{self, OrientationFix};
+use crate::cube::{
+    Color, ColorScheme, Corner, Cube, CubeFile, Face, FaceletOrder, Move, ReadingDirection,
+};
+use crate::geometry::{self, View3D, DEFAULT_ANIMATION_DURATION};
+use crate::gui::renderer_3d::draw_cube_3d;
+use crate::gui::session::{self, Session, SESSION_EXTENSION};
 use crate::history::History;
-use crate::solver;
+use crate::method::{self, Method, MethodSolution};
+use crate::search::{self, SearchOptions, SearchTarget};
+use crate::solver::{self, SolveOptions, TargetOrientation};
 use crate::statistics::Statistics;
 use std::sync::mpsc::{channel, Receiver};
 use std::thread;
@@ -14,9 +25,6 @@ const MIN_SCRAMBLE_MOVES: usize = 5;
 /// スクランブルの最大手数
 const MAX_SCRAMBLE_MOVES: usize = 10;
 
-/// デフォルトのアニメーション時間(秒)
-const DEFAULT_ANIMATION_DURATION: f32 = 0.3;
-
 /// アニメーション速度の最小値
 #[allow(dead_code)]
 const MIN_ANIMATION_SPEED: f32 = 0.1;
@@ -82,12 +90,7 @@ impl AnimationState {
 
     /// イージング関数 (ease-in-out)
     pub fn eased_progress(&self) -> f32 {
-        let t = self.progress;
-        if t < 0.5 {
-            2.0 * t * t
-        } else {
-            -1.0 + (4.0 - 2.0 * t) * t
-        }
+        geometry::ease_in_out(self.progress)
     }
 }
 
@@ -128,18 +131,70 @@ pub struct CubeApp {
     pub input_buffer: [Option<Color>; 24],
     pub selected_input_color: Color,
     pub input_error_message: String,
+    /// 面の向きの入力ミスに対する修正案（一意に定まった場合のみ）
+    pub scan_fix: Option<OrientationFix>,
+    /// 無効な状態に対する最小の修正案
+    pub repairs: Vec<Repair>,
+    /// 入力・表示・保存に使う配色
+    pub color_scheme: ColorScheme,
 
     // デバッグオプション
     pub skip_parity_check: bool,
 
     // ソルバータスクの種類
     pub solver_task: SolverTask,
+    /// 完成とみなすキューブ全体の向き
+    pub solve_target: TargetOrientation,
+    /// 手法で解くときの解法
+    pub method: Method,
+    /// 手法で解いた結果（ステップ操作中に、いまのステップの説明を表示する）
+    pub method_solution: Option<MethodSolution>,
+    /// 読み込んだ手順ファイル（ステップ操作中に、いまのステップのコメントを表示する）
+    pub alg_file: Option<AlgFile>,
+
+    // 手順の入力
+    /// 入力中の手順の記号
+    pub algorithm_input: String,
+    /// 手順の解析結果
+    pub algorithm_text: String,
+
+    // フェイスレット文字列
+    /// 入力中（貼り付けた）フェイスレット文字列
+    pub facelet_input: String,
+    /// フェイスレット文字列の面の順（例: `URFDLB`）
+    pub facelet_order_input: String,
+    /// フェイスレット文字列の面ごとの読み方（`Face::ALL` の順）
+    pub facelet_reading: [ReadingDirection; 6],
+
+    // 手順の検索
+    /// 検索に使う面（`Face::ALL` の順）
+    pub search_faces: [bool; 6],
+    /// 検索する最大手数
+    pub search_max_length: usize,
+    /// 1層目（D面の層）を保つ
+    pub search_preserve_first_layer: bool,
+    /// 入れ替える2つのコーナー
+    pub search_swap: Option<(Corner, Corner)>,
+    /// 検索結果
+    pub search_results: Vec<Algorithm>,
+    /// 検索の状態の表示
+    pub search_text: String,
+    search_receiver: Option<Receiver<Vec<Algorithm>>>,
+
+    // 対称性の解析
+    /// 比較の基準にする局面
+    pub symmetry_reference: Option<Cube>,
+    /// 対称性の解析結果
+    pub symmetry_text: String,
 
     // 統計情報
     pub statistics: Statistics,
 
     // 操作履歴
     pub history: History,
+
+    /// 終了時にセッションを自動保存し、次回の起動時に復元する
+    pub autosave: bool,
 }
 
 /// ソルバーのタスク種類
@@ -174,10 +229,32 @@ impl Default for CubeApp {
             input_buffer: [None; 24],
             selected_input_color: Color::White,
             input_error_message: String::new(),
+            scan_fix: None,
+            repairs: Vec::new(),
+            color_scheme: ColorScheme::standard(),
             skip_parity_check: false,
             solver_task: SolverTask::Normal,
+            solve_target: TargetOrientation::Any,
+            method: Method::Beginner,
+            method_solution: None,
+            alg_file: None,
+            algorithm_input: String::new(),
+            algorithm_text: String::new(),
+            facelet_input: String::new(),
+            facelet_order_input: FaceletOrder::urfdlb().face_letters(),
+            facelet_reading: [ReadingDirection::Rows; 6],
+            search_faces: [true, false, false, true, true, false],
+            search_max_length: 7,
+            search_preserve_first_layer: true,
+            search_swap: Some((Corner::Ufr, Corner::Ubr)),
+            search_results: Vec::new(),
+            search_text: String::new(),
+            search_receiver: None,
+            symmetry_reference: None,
+            symmetry_text: String::new(),
             statistics: Statistics::new(),
             history: History::new(),
+            autosave: true,
         }
     }
 }
@@ -203,7 +280,16 @@ impl CubeApp {
         );
         cc.egui_ctx.set_style(style);
 
-        Self::default()
+        // 前回の終了時に自動保存したセッションを復元（自動保存をオフにして終了した場合は設定だけ）
+        let mut app = Self::default();
+        if let Some(session) = session::load_autosave() {
+            if session.autosave {
+                app.restore_session(session);
+            } else {
+                app.autosave = false;
+            }
+        }
+        app
     }
 
     /// 回転操作をキューに追加
@@ -256,6 +342,8 @@ impl CubeApp {
     pub fn cancel_solve(&mut self) {
         self.solving = false;
         self.solution = None;
+        self.method_solution = None;
+        self.alg_file = None;
         self.solution_text.clear();
         self.solver_receiver = None;
         self.progress_receiver = None;
@@ -264,20 +352,76 @@ impl CubeApp {
 
     /// ソルバー実行（通常）
     pub fn solve(&mut self) {
-        self.start_solver_internal(SolverTask::Normal, self.ignore_orientation);
+        let options = SolveOptions {
+            ignore_orientation: self.ignore_orientation,
+            target: self.solve_target.clone(),
+            ..SolveOptions::default()
+        };
+        self.start_solver_internal(SolverTask::Normal, options);
+    }
+
+    /// 選んだ解法（初心者向け・Ortega・CLL・EG）のステップに沿って解く
+    ///
+    /// ケース表を引くだけで探索はしないため、その場で結果を表示します。
+    pub fn solve_with_method(&mut self) {
+        if self.solving {
+            return;
+        }
+        match method::solve(&self.cube, self.method) {
+            Ok(solution) => {
+                let mut text = format!("{}: {} 手", solution.method, solution.algorithm().len());
+                for (i, step) in solution.steps.iter().enumerate() {
+                    text.push_str(&format!(
+                        "\nステップ{}: {} ({}手)\n  {}",
+                        i + 1,
+                        step.label,
+                        step.algorithm.len(),
+                        step.algorithm
+                    ));
+                }
+                self.solution_text = text;
+                self.solution = Some(solution.algorithm().moves);
+                self.solution_cube_state = Some(self.cube.clone());
+                self.solution_step = 0;
+                self.method_solution = Some(solution);
+                self.alg_file = None;
+            }
+            Err(e) => {
+                self.solution = None;
+                self.method_solution = None;
+                self.solution_text = format!("解けません: {}", e);
+            }
+        }
     }
 
     /// 向きの自動復元を開始（非同期）
+    ///
+    /// 矢印の回り方は経路によって180度ずれるため、コーナーの配置だけからは決まりません。
+    /// 向きを無視して解いた手順から求めるので、画面が止まらないよう別スレッドで探索します。
+    /// 無効な状態（存在しないピースなど）のときは向きをそのままにします。
     pub fn start_restore_orientation(&mut self) {
-        self.start_solver_internal(SolverTask::RestoreOrientation, true);
+        if self.cube.is_valid_state().is_err() {
+            return;
+        }
+        // 前の局面の復元が終わっていなければ、その結果は捨てて新しい局面で探索し直す
+        if self.solving && self.solver_task == SolverTask::RestoreOrientation {
+            self.cancel_solve();
+        }
+        let options = SolveOptions {
+            ignore_orientation: true,
+            ..SolveOptions::default()
+        };
+        self.start_solver_internal(SolverTask::RestoreOrientation, options);
     }
 
     /// ソルバー実行の内部処理
-    fn start_solver_internal(&mut self, task: SolverTask, ignore_orientation: bool) {
+    fn start_solver_internal(&mut self, task: SolverTask, options: SolveOptions) {
         if self.solving {
             return;
         }
         self.solving = true;
+        self.method_solution = None;
+        self.alg_file = None;
         self.solver_task = task;
         self.solver_progress = 0.0;
 
@@ -300,17 +444,11 @@ impl CubeApp {
 
         thread::spawn(move || {
             // HTM対応により、向きの有無に関わらず最大11手で必ず解ける
-            let max_depth = solver::DEFAULT_MAX_DEPTH;
             println!(
                 "ソルバー開始: 深度{}まで探索 (タスク: {:?})",
-                max_depth, task
-            );
-            let solution = solver::solve_with_progress(
-                &cube_clone,
-                max_depth,
-                ignore_orientation,
-                Some(progress_tx),
+                options.max_depth, task
             );
+            let solution = solver::solve_with_options(&cube_clone, &options, Some(progress_tx));
             println!(
                 "ソルバー完了: 解が{}",
                 if solution.found {
@@ -378,19 +516,44 @@ impl CubeApp {
                             };
                             self.solution_text =
                                 format!("解法: {} 手{}", solution.moves.len(), duration_text);
+                            let reached = self
+                                .solution_cube_state
+                                .as_ref()
+                                .and_then(|start| solution.reached_orientation(start));
+                            if let Some(orientation) = reached {
+                                self.solution_text.push_str(&format!(
+                                    "\n完成の向き: U面 {}、F面 {}、D面 {}",
+                                    self.color_label(orientation.up),
+                                    self.color_label(orientation.front),
+                                    self.color_label(orientation.down())
+                                ));
+                            }
+                            if let Some(start) = &self.solution_cube_state {
+                                for case in cases::recognize(start) {
+                                    self.solution_text.push_str(&format!(
+                                        "\nケース: {}\n  {}",
+                                        case.describe(),
+                                        case.algorithm
+                                    ));
+                                    if !case.post_auf.is_empty() {
+                                        self.solution_text
+                                            .push_str(&format!("（最後に {}）", case.post_auf));
+                                    }
+                                }
+                            }
                             self.solution_step = 0;
                             // 自動実行はしない（ステップ操作で手動実行）
                         }
                         SolverTask::RestoreOrientation => {
-                            // 復元処理
-                            if let Err(e) = self.cube.apply_orientation_solution(&solution) {
+                            // 探索の間に局面が変わっていなければ、解いた手順から向きを復元する
+                            if self.solution_cube_state.as_ref() != Some(&self.cube) {
+                                self.solution_text.clear();
+                            } else if let Err(e) = self.cube.apply_orientation_solution(&solution) {
                                 self.solution_text = format!("復元失敗: {}", e);
                             } else {
                                 self.solution_text = "向きを復元しました".to_string();
                             }
-                            // 完了後、少し待ってからメッセージを消すなどの処理があればいいが、
-                            // とりあえず solution_text に残す。
-                            // モードはNormalに戻さないと操作できないので solving = falseでOK。
+                            self.solution_cube_state = None;
                         }
                     }
                 } else {
@@ -417,6 +580,11 @@ impl CubeApp {
         }
     }
 
+    /// 内部表現（標準配色）の色を、現在の配色での色の名前に変換
+    pub fn color_label(&self, color: Color) -> &'static str {
+        self.color_scheme.from_standard_color(color).label()
+    }
+
     /// キューブの状態を取得
     pub fn cube(&self) -> &Cube {
         &self.cube
@@ -442,7 +610,8 @@ impl CubeApp {
                     }
                 }
 
-                Cube::from_colors(&colors).unwrap_or_else(|_| Cube::new())
+                Cube::from_colors(&self.color_scheme.to_standard(&colors))
+                    .unwrap_or_else(|_| Cube::new())
             }
             InputState::Normal => {
                 // 通常モード: 実際のキューブを返す
@@ -587,6 +756,7 @@ impl CubeApp {
             ui,
             rect,
             &display_cube,
+            &self.color_scheme,
             self.animation.as_ref(),
             &self.view_3d,
             highlight_face,
@@ -618,11 +788,152 @@ impl CubeApp {
             ui,
             rect,
             &display_cube,
+            &self.color_scheme,
             self.animation.as_ref(),
             highlight_face,
         );
     }
 
+    // ============ 手順の入力 ============
+
+    /// 入力された手順を解析（周期・コーナーの巡回・動くステッカー）
+    pub fn analyze_algorithm(&mut self) {
+        self.algorithm_text = match Algorithm::parse(&self.algorithm_input) {
+            Ok(algorithm) if algorithm.is_empty() => "手順を入力してください".to_string(),
+            Ok(algorithm) => {
+                let analysis = algorithm.analyze();
+                format!(
+                    "{}（{}手）\n周期: {}回\n{}\n動くステッカー: {}枚",
+                    algorithm,
+                    algorithm.len(),
+                    analysis.order,
+                    analysis.describe(),
+                    analysis.stickers.len()
+                )
+            }
+            Err(e) => e.to_string(),
+        };
+    }
+
+    /// 入力された手順をキューブに適用
+    pub fn apply_algorithm(&mut self) {
+        match Algorithm::parse(&self.algorithm_input) {
+            Ok(algorithm) => {
+                for mv in algorithm.moves {
+                    self.queue_move(mv);
+                }
+            }
+            Err(e) => self.algorithm_text = e.to_string(),
+        }
+    }
+
+    /// 入力された手順を変換して入力欄に戻す（鏡像・逆手順など）
+    pub fn transform_algorithm(&mut self, transform: impl Fn(&Algorithm) -> Algorithm) {
+        match Algorithm::parse(&self.algorithm_input) {
+            Ok(algorithm) => {
+                self.algorithm_input = transform(&algorithm).to_string();
+                self.analyze_algorithm();
+            }
+            Err(e) => self.algorithm_text = e.to_string(),
+        }
+    }
+
+    // ============ 手順の検索 ============
+
+    /// 検索中か
+    pub fn is_searching(&self) -> bool {
+        self.search_receiver.is_some()
+    }
+
+    /// 条件に合う手順の検索を開始（非同期）
+    pub fn start_algorithm_search(&mut self) {
+        if self.is_searching() {
+            return;
+        }
+        let faces: Vec<Face> = Face::ALL
+            .into_iter()
+            .filter(|face| self.search_faces[face.index()])
+            .collect();
+        if faces.is_empty() {
+            self.search_text = "使う面を選択してください".to_string();
+            return;
+        }
+
+        let mut target = SearchTarget::any();
+        if self.search_preserve_first_layer {
+            target = target.preserve_layer(Face::Down);
+        }
+        if let Some((a, b)) = self.search_swap {
+            target = target.swap_corners(a, b);
+        }
+        let options = SearchOptions {
+            max_length: self.search_max_length,
+            ..SearchOptions::with_faces(&faces)
+        };
+
+        let (tx, rx) = channel();
+        self.search_receiver = Some(rx);
+        self.search_results.clear();
+        self.search_text = "検索中...".to_string();
+        thread::spawn(move || {
+            let _ = tx.send(search::search(&target, &options));
+        });
+    }
+
+    /// 手順の検索結果を確認
+    fn check_search_result(&mut self) {
+        if let Some(rx) = &self.search_receiver {
+            if let Ok(results) = rx.try_recv() {
+                self.search_text = if results.is_empty() {
+                    format!(
+                        "{}手以内の手順は見つかりませんでした",
+                        self.search_max_length
+                    )
+                } else {
+                    format!("{}件見つかりました", results.len())
+                };
+                self.search_results = results;
+                self.search_receiver = None;
+            }
+        }
+    }
+
+    /// 検索結果の手順を入力欄に設定
+    pub fn select_search_result(&mut self, index: usize) {
+        if let Some(algorithm) = self.search_results.get(index) {
+            self.algorithm_input = algorithm.to_string();
+            self.analyze_algorithm();
+        }
+    }
+
+    // ============ 対称性の解析 ============
+
+    /// 現在の局面を対称性の比較の基準にする
+    pub fn set_symmetry_reference(&mut self) {
+        self.symmetry_reference = Some(self.cube.clone());
+        self.symmetry_text = "現在の局面を基準にしました".to_string();
+    }
+
+    /// 現在の局面の対称性を調べる
+    pub fn analyze_symmetry(&mut self) {
+        let class = self.cube.symmetry_class();
+        let mut lines = vec![format!("同値な局面: {}通り", class.size)];
+        if class.mirror_symmetric {
+            lines.push("鏡像と同じ局面です".to_string());
+        }
+
+        if let Some(reference) = &self.symmetry_reference {
+            let relation = match self.cube.symmetry_to(reference) {
+                Some(s) if s.index() == 0 => "基準の局面と同じです",
+                Some(s) if s.is_mirror() => "基準の局面の鏡像です",
+                Some(_) => "基準の局面を回転させた局面です",
+                None => "基準の局面とは対称ではありません",
+            };
+            lines.push(relation.to_string());
+        }
+        self.symmetry_text = lines.join("\n");
+    }
+
     // ============ 6面スキャン入力モード用メソッド ============
 
     /// スキャンモードを開始
@@ -631,6 +942,8 @@ impl CubeApp {
         self.input_buffer = [None; 24];
         self.selected_input_color = Color::White;
         self.input_error_message.clear();
+        self.scan_fix = None;
+        self.repairs.clear();
     }
 
     /// スキャンモードをキャンセル
@@ -638,6 +951,8 @@ impl CubeApp {
         self.input_state = InputState::Normal;
         self.input_buffer = [None; 24];
         self.input_error_message.clear();
+        self.scan_fix = None;
+        self.repairs.clear();
     }
 
     /// 次の面へ進む
@@ -669,6 +984,9 @@ impl CubeApp {
             let global_index = face_index * 4 + position;
             if global_index < 24 {
                 self.input_buffer[global_index] = Some(color);
+                // 入力が変わったら修正案は無効
+                self.scan_fix = None;
+                self.repairs.clear();
             }
         }
     }
@@ -687,15 +1005,9 @@ impl CubeApp {
     /// 現在の面の名前を取得
     pub fn get_current_face_name(&self) -> &str {
         if let InputState::Scanning { face_index } = self.input_state {
-            match face_index {
-                0 => "Up (上面)",
-                1 => "Down (下面)",
-                2 => "Left (左面)",
-                3 => "Right (右面)",
-                4 => "Front (前面)",
-                5 => "Back (背面)",
-                _ => "不明",
-            }
+            Face::ALL
+                .get(face_index)
+                .map_or("不明", |face| face.label())
         } else {
             "不明"
         }
@@ -731,9 +1043,22 @@ impl CubeApp {
         // 妥当性チェック
         if let Err(e) = Cube::validate_colors(&colors) {
             self.input_error_message = e.to_string();
+            self.repairs = repair::find_repairs(&self.color_scheme.to_standard(&colors));
             return;
         }
 
+        // 現在の配色では無効な場合は、入力から配色を推定する
+        let mut scheme_message = String::new();
+        let mut colors = self.color_scheme.to_standard(&colors);
+        if !self.skip_parity_check && crate::cube::validation::check_corners(&colors).is_err() {
+            let scanned = self.color_scheme.from_standard(&colors);
+            if let Ok(inferred) = ColorScheme::infer(&scanned) {
+                scheme_message = format!("配色を{}と推定しました", inferred.name());
+                self.color_scheme = inferred;
+                colors = inferred.to_standard(&scanned);
+            }
+        }
+
         // キューブに反映
         let new_cube = match Cube::from_colors(&colors) {
             Ok(cube) => cube,
@@ -747,6 +1072,20 @@ impl CubeApp {
         if !self.skip_parity_check {
             if let Err(e) = new_cube.is_valid_state() {
                 self.input_error_message = format!("無効なキューブ状態: {}", e);
+
+                // 面の向きの入力ミスで説明できるか調べる
+                let mut fixes = scan::find_orientation_fixes(&colors);
+                if fixes.len() == 1 {
+                    self.scan_fix = fixes.pop();
+                } else if fixes.len() > 1 {
+                    self.input_error_message.push_str(&format!(
+                        "\n面の向きの解釈が{}通り考えられるため、自動修正できません",
+                        fixes.len()
+                    ));
+                }
+                if self.scan_fix.is_none() {
+                    self.repairs = repair::find_repairs(&colors);
+                }
                 return;
             }
         }
@@ -754,7 +1093,9 @@ impl CubeApp {
         self.cube = new_cube;
         self.input_state = InputState::Normal;
         self.input_buffer = [None; 24];
-        self.input_error_message.clear();
+        self.input_error_message = scheme_message;
+        self.scan_fix = None;
+        self.repairs.clear();
 
         // 向きの自動復元を開始（非同期）
         self.start_restore_orientation();
@@ -766,9 +1107,56 @@ impl CubeApp {
         self.move_queue.clear();
     }
 
+    /// 面の向きの修正案を適用してスキャンを完了
+    pub fn apply_scan_fix(&mut self) {
+        if let Some(fix) = self.scan_fix.take() {
+            self.input_buffer = self.color_scheme.from_standard(&fix.colors).map(Some);
+            self.input_error_message.clear();
+            self.finish_scanning();
+        }
+    }
+
+    /// 修正案を適用
+    ///
+    /// スキャン中は入力内容を修正してスキャンを完了し、それ以外は現在のキューブを修正します。
+    pub fn apply_repair(&mut self, index: usize) {
+        let Some(repair) = self.repairs.get(index).cloned() else {
+            return;
+        };
+        self.repairs.clear();
+
+        if matches!(self.input_state, InputState::Scanning { .. }) {
+            self.input_buffer = self.color_scheme.from_standard(&repair.colors).map(Some);
+            self.input_error_message.clear();
+            self.finish_scanning();
+            return;
+        }
+
+        match Cube::from_colors(&repair.colors) {
+            Ok(cube) => {
+                self.cube = cube;
+                self.solution = None;
+                self.solution_text.clear();
+                self.animation = None;
+                self.move_queue.clear();
+                self.input_error_message = format!("修正しました: {}", repair.describe());
+                self.start_restore_orientation();
+            }
+            Err(e) => {
+                self.input_error_message = format!("修正に失敗しました: {}", e);
+            }
+        }
+    }
+
     /// キューブの状態をファイルに保存
+    ///
+    /// 向き（矢印）と保存した日付を含むv2形式で保存します。
     pub fn save_to_file(&self, path: &str) -> Result<(), String> {
-        let content = self.cube.to_file_format();
+        let mut file = CubeFile::new(self.cube.clone());
+        file.comments
+            .push(format!("{} で保存", env!("CARGO_PKG_NAME")));
+        file.set_metadata("date", &today());
+        let content = file.to_file_format_with_scheme(&self.color_scheme);
         std::fs::write(path, content).map_err(|e| format!("ファイルの保存に失敗しました: {}", e))
     }
 
@@ -777,17 +1165,43 @@ impl CubeApp {
         let content = std::fs::read_to_string(path)
             .map_err(|e| format!("ファイルの読み込みに失敗しました: {}", e))?;
 
-        let new_cube = Cube::from_file_format(&content).map_err(|e| e.to_string())?;
+        let file =
+            CubeFile::parse_with_scheme(&content, &self.color_scheme).map_err(|e| e.to_string())?;
+        let mut new_cube = file.cube.clone();
 
         let mut warning = String::new();
 
         // パリティチェック（skip_parity_checkフラグで制御）
         if !self.skip_parity_check {
+            // 現在の配色では無効な場合は、ファイルの色から配色を推定する
+            if new_cube.is_valid_state().is_err() {
+                let colors = self
+                    .color_scheme
+                    .from_standard(&new_cube.stickers.map(|s| s.color));
+                if let Ok(inferred) = ColorScheme::infer(&colors) {
+                    let orientations = new_cube.stickers.map(|s| s.orientation);
+                    new_cube = Cube::from_colors(&inferred.to_standard(&colors))
+                        .map_err(|e| e.to_string())?;
+                    if file.has_orientations {
+                        for (sticker, orientation) in new_cube.stickers.iter_mut().zip(orientations)
+                        {
+                            sticker.orientation = orientation;
+                        }
+                    }
+                    self.color_scheme = inferred;
+                    warning = format!("配色を{}と推定しました", inferred.name());
+                }
+            }
             if let Err(e) = new_cube.is_valid_state() {
                 warning = format!("警告: 無効なキューブ状態です ({})", e);
+                let colors = new_cube.stickers.map(|s| s.color);
+                self.repairs = repair::find_repairs(&colors);
             }
         }
 
+        if new_cube.is_valid_state().is_ok() {
+            self.repairs.clear();
+        }
         self.cube = new_cube;
         self.solution = None;
         self.solution_text.clear();
@@ -799,10 +1213,79 @@ impl CubeApp {
         self.input_buffer = [None; 24];
         self.input_error_message.clear(); // コントロール側で上書きされるが念のため
 
+        // 向きを保存していないファイルは、色の並びから向きを復元
+        if !file.has_orientations {
+            self.start_restore_orientation();
+        }
+
+        // メタデータ（スクランブル・作成者・日付など）を表示する
+        for (key, value) in &file.metadata {
+            if !warning.is_empty() {
+                warning.push_str(", ");
+            }
+            warning.push_str(&format!("{key}: {value}"));
+        }
+
+        Ok(warning)
+    }
+
+    /// 入力中のフェイスレット文字列を読み込む
+    ///
+    /// 色の文字は現在の配色の色として読みます。結果は `input_error_message` に表示します。
+    pub fn load_facelet_input(&mut self) {
+        let loaded = self.facelet_order().and_then(|order| {
+            Cube::from_facelets_with_scheme(&self.facelet_input, &order, &self.color_scheme)
+        });
+        let cube = match loaded {
+            Ok(cube) => cube,
+            Err(e) => {
+                self.input_error_message = format!("フェイスレットの読み込みエラー: {}", e);
+                return;
+            }
+        };
+
+        self.input_error_message = "フェイスレットを読み込みました".to_string();
+        self.repairs.clear();
+        if let Err(e) = cube.is_valid_state() {
+            self.input_error_message = format!("警告: 無効なキューブ状態です ({})", e);
+            self.repairs = repair::find_repairs(&cube.stickers.map(|s| s.color));
+        }
+        self.cube = cube;
+        self.solution = None;
+        self.solution_text.clear();
+        self.animation = None;
+        self.move_queue.clear();
+        self.input_state = InputState::Normal;
+        self.input_buffer = [None; 24];
+
         // 向きの自動復元を開始（非同期）
         self.start_restore_orientation();
+    }
 
-        Ok(warning)
+    /// 入力中の面の順と面ごとの読み方
+    fn facelet_order(&self) -> crate::error::Result<FaceletOrder> {
+        let order = FaceletOrder::from_face_letters(&self.facelet_order_input)?;
+        Ok(Face::ALL
+            .into_iter()
+            .zip(self.facelet_reading)
+            .fold(order, |order, (face, reading)| {
+                order.with_reading(face, reading)
+            }))
+    }
+
+    /// 現在の局面のフェイスレット文字列をクリップボードにコピー
+    pub fn copy_facelets(&mut self, ctx: &egui::Context) {
+        match self.facelet_order() {
+            Ok(order) => {
+                let facelets = self.cube.to_facelets(&order);
+                ctx.copy_text(facelets.clone());
+                self.input_error_message = format!("コピーしました: {}", facelets);
+                self.facelet_input = facelets;
+            }
+            Err(e) => {
+                self.input_error_message = format!("面の順が正しくありません: {}", e);
+            }
+        }
     }
 
     /// 保存ダイアログを表示して保存
@@ -856,6 +1339,219 @@ impl CubeApp {
         }
     }
 
+    /// 保存する手順ファイル
+    ///
+    /// 解法があれば解法を（手法で解いた場合はステップごとに、名前をコメントにして）保存し、
+    /// 開始局面を作る手順として解法の逆手順を付けます。解法がなければ入力中の手順を保存します。
+    #[must_use]
+    pub fn current_alg_file(&self) -> Option<AlgFile> {
+        if let Some(loaded) = self.alg_file.as_ref().filter(|_| self.solution.is_some()) {
+            return Some(loaded.clone());
+        }
+        if let Some(solution) = &self.solution {
+            let algorithm = Algorithm::new(solution.clone());
+            let mut file = match &self.method_solution {
+                Some(m) => m
+                    .steps
+                    .iter()
+                    .fold(AlgFile::new(m.method.name()), |file, step| {
+                        file.with_step(step.algorithm.clone(), Some(&step.label))
+                    }),
+                None => AlgFile::new("解法").with_step(algorithm.clone(), None),
+            };
+            file.setup = Some(algorithm.inverse());
+            return Some(file);
+        }
+        let algorithm = Algorithm::parse(&self.algorithm_input)
+            .ok()
+            .filter(|a| !a.is_empty())?;
+        Some(AlgFile::new("手順").with_step(algorithm, None))
+    }
+
+    /// 手順ファイルを読み込み、解法ステップ操作で再生できるようにする
+    ///
+    /// 開始局面を作る手順（`setup:`）があれば完成状態から作った局面を、
+    /// なければ現在の局面を開始局面にします。読み込んだ題名を返します。
+    pub fn load_alg_file(&mut self, path: &str) -> Result<String, String> {
+        let file = AlgFile::load(path).map_err(|e| e.to_string())?;
+
+        self.cancel_solve();
+        self.animation = None;
+        self.pending_solution_update = None;
+        if let Some(start) = file.start_cube() {
+            self.cube = start;
+            self.history.clear();
+            self.repairs.clear();
+        }
+
+        let algorithm = file.algorithm();
+        let title = if file.title.is_empty() {
+            "手順".to_string()
+        } else {
+            file.title.clone()
+        };
+        let mut text = format!("{}: {} 手", title, algorithm.len());
+        for (i, step) in file.steps.iter().enumerate() {
+            text.push_str(&format!("\nステップ{}: {}", i + 1, step.algorithm));
+            if let Some(comment) = &step.comment {
+                text.push_str(&format!("  # {}", comment));
+            }
+        }
+        self.solution_text = text;
+        self.solution = Some(algorithm.moves);
+        self.solution_cube_state = Some(self.cube.clone());
+        self.solution_step = 0;
+        self.alg_file = Some(file);
+        Ok(title)
+    }
+
+    /// 手順ファイルを開くダイアログを表示して読み込み
+    pub fn open_alg_with_dialog(&mut self) {
+        let task = rfd::FileDialog::new()
+            .set_directory(".")
+            .add_filter("Algorithm files", &[ALG_FILE_EXTENSION])
+            .pick_file();
+
+        if let Some(path) = task {
+            let path_str = path.to_string_lossy();
+            match self.load_alg_file(&path_str) {
+                Ok(title) => {
+                    self.input_error_message = format!("手順を読み込みました: {}", title);
+                }
+                Err(e) => {
+                    self.input_error_message = format!("手順の読み込みエラー: {}", e);
+                }
+            }
+        }
+    }
+
+    /// 手順ファイルの保存ダイアログを表示して保存
+    pub fn save_alg_with_dialog(&mut self) {
+        let Some(file) = self.current_alg_file() else {
+            self.input_error_message = "保存する解法・手順がありません".to_string();
+            return;
+        };
+        let task = rfd::FileDialog::new()
+            .set_directory(".")
+            .add_filter("Algorithm files", &[ALG_FILE_EXTENSION])
+            .set_file_name(format!("solution.{ALG_FILE_EXTENSION}"))
+            .save_file();
+
+        if let Some(path) = task {
+            match file.save(&path) {
+                Ok(()) => {
+                    self.input_error_message = format!(
+                        "手順を保存しました: {}",
+                        path.file_name().unwrap_or_default().to_string_lossy()
+                    );
+                }
+                Err(e) => {
+                    self.input_error_message = format!("手順の保存エラー: {}", e);
+                }
+            }
+        }
+    }
+
+    /// 現在の状態をセッションとして取り出す
+    #[must_use]
+    pub fn session(&self) -> Session {
+        Session {
+            cube: self.cube.clone(),
+            history: self.history.clone(),
+            solution: self.solution.clone(),
+            solution_cube_state: self.solution_cube_state.clone(),
+            solution_step: self.solution_step,
+            solution_text: self.solution_text.clone(),
+            statistics: self.statistics.clone(),
+            view_mode: self.view_mode,
+            view_3d: self.view_3d,
+            animation_speed: self.animation_speed,
+            ignore_orientation: self.ignore_orientation,
+            solve_target: self.solve_target.clone(),
+            method: self.method,
+            method_solution: self.method_solution.clone(),
+            alg_file: self.alg_file.clone(),
+            color_scheme: self.color_scheme,
+            autosave: self.autosave,
+        }
+    }
+
+    /// セッションの状態に戻す
+    ///
+    /// 探索中の処理やアニメーション、スキャン入力は取り消します。
+    /// 自動保存の設定はアプリの設定のまま変えません（起動時に `CubeApp::new` で読みます）。
+    pub fn restore_session(&mut self, session: Session) {
+        self.cancel_solve();
+        self.animation = None;
+        self.pending_solution_update = None;
+        self.input_state = InputState::Normal;
+        self.input_buffer = [None; 24];
+        self.repairs.clear();
+
+        self.cube = session.cube;
+        self.history = session.history;
+        self.solution = session.solution;
+        self.solution_cube_state = session.solution_cube_state;
+        self.solution_step = session.solution_step;
+        self.solution_text = session.solution_text;
+        self.statistics = session.statistics;
+        self.view_mode = session.view_mode;
+        self.view_3d = session.view_3d;
+        self.animation_speed = session.animation_speed;
+        self.ignore_orientation = session.ignore_orientation;
+        self.solve_target = session.solve_target;
+        self.method = session.method;
+        self.method_solution = session.method_solution;
+        self.alg_file = session.alg_file;
+        self.color_scheme = session.color_scheme;
+    }
+
+    /// セッションの保存ダイアログを表示して保存
+    pub fn save_session_with_dialog(&mut self) {
+        let task = rfd::FileDialog::new()
+            .set_directory(".")
+            .add_filter("Session files", &[SESSION_EXTENSION])
+            .set_file_name(format!("cube.{SESSION_EXTENSION}"))
+            .save_file();
+
+        if let Some(path) = task {
+            match self.session().save(&path) {
+                Ok(()) => {
+                    self.input_error_message = format!(
+                        "セッションを保存しました: {}",
+                        path.file_name().unwrap_or_default().to_string_lossy()
+                    );
+                }
+                Err(e) => {
+                    self.input_error_message = format!("セッションの保存エラー: {}", e);
+                }
+            }
+        }
+    }
+
+    /// セッションを開くダイアログを表示して復元
+    pub fn load_session_with_dialog(&mut self) {
+        let task = rfd::FileDialog::new()
+            .set_directory(".")
+            .add_filter("Session files", &[SESSION_EXTENSION])
+            .pick_file();
+
+        if let Some(path) = task {
+            match Session::load(&path) {
+                Ok(session) => {
+                    self.restore_session(session);
+                    self.input_error_message = format!(
+                        "セッションを復元しました: {}",
+                        path.file_name().unwrap_or_default().to_string_lossy()
+                    );
+                }
+                Err(e) => {
+                    self.input_error_message = format!("セッションの読み込みエラー: {}", e);
+                }
+            }
+        }
+    }
+
     /// キーボード入力を処理
     fn handle_input(&mut self, ctx: &egui::Context) {
         // アニメーション中やソルブ中は入力を受け付けない（オプション）
@@ -893,6 +1589,20 @@ impl CubeApp {
             self.queue_move(mv);
         }
 
+        // 入力欄以外での貼り付けは、フェイスレット文字列として読み込む
+        let pasted = ctx.input(|i| {
+            i.events.iter().find_map(|event| match event {
+                egui::Event::Paste(text) => Some(text.trim().to_string()),
+                _ => None,
+            })
+        });
+        if let Some(text) = pasted {
+            if ctx.memory(|m| m.focused().is_none()) {
+                self.facelet_input = text;
+                self.load_facelet_input();
+            }
+        }
+
         // 機能キー
         if ctx.input(|i| i.key_pressed(egui::Key::S)) {
             self.scramble();
@@ -907,9 +1617,29 @@ impl CubeApp {
 }
 
 impl eframe::App for CubeApp {
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        let Some(path) = Session::autosave_path() else {
+            return;
+        };
+        // オフのときは前回の局面を残さず、オフにしたことだけを保存する
+        let session = if self.autosave {
+            self.session()
+        } else {
+            Session {
+                autosave: false,
+                ..Session::default()
+            }
+        };
+        match session.save(&path) {
+            Ok(()) => tracing::info!("セッションを自動保存しました: {}", path.display()),
+            Err(e) => tracing::warn!("セッションを自動保存できません: {}", e),
+        }
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         self.check_solver_result();
         self.check_progress();
+        self.check_search_result();
         self.update_animation();
         self.handle_input(ctx);
 
@@ -922,7 +1652,7 @@ impl eframe::App for CubeApp {
             .default_width(250.0)
             .resizable(false)
             .show(ctx, |ui| {
-                ui.vertical(|ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
                     ui.add_space(10.0);
                     crate::gui::controls::draw_controls(self, ui);
                 });
@@ -970,3 +1700,27 @@ impl eframe::App for CubeApp {
         });
     }
 }
+
+/// 今日の日付（UTC、`YYYY-MM-DD`）
+fn today() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    // 1970-01-01 からの日数を暦の日付に変換（3月始まりの400年周期で数える）
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -1,5 +1,8 @@
-use crate::cube::{Color, Move};
+use crate::algorithm::{Algorithm, MirrorPlane, Rotation};
+use crate::cube::{Color, ColorScheme, Corner, Face, Move, ReadingDirection};
 use crate::gui::app::{CubeApp, InputState};
+use crate::method::Method;
+use crate::solver::{CubeOrientation, TargetOrientation};
 
 /// コントロールパネルを描画
 pub fn draw_controls(app: &mut CubeApp, ui: &mut egui::Ui) {
@@ -27,19 +30,10 @@ pub fn draw_controls(app: &mut CubeApp, ui: &mut egui::Ui) {
             // 色選択パレット
             ui.label("色を選択:");
             ui.horizontal(|ui| {
-                let colors = [
-                    (Color::White, "白", egui::Color32::from_rgb(255, 255, 255)),
-                    (Color::Yellow, "黄", egui::Color32::from_rgb(255, 255, 0)),
-                    (Color::Green, "緑", egui::Color32::from_rgb(0, 200, 0)),
-                    (Color::Blue, "青", egui::Color32::from_rgb(0, 100, 255)),
-                    (Color::Red, "赤", egui::Color32::from_rgb(255, 0, 0)),
-                    (Color::Orange, "橙", egui::Color32::from_rgb(255, 140, 0)),
-                ];
-
-                for (color, label, rgb) in colors {
+                for color in Color::ALL {
                     let is_selected = app.selected_input_color == color;
-                    let button = egui::Button::new(label)
-                        .fill(rgb)
+                    let button = egui::Button::new(color.label())
+                        .fill(scheme_color32(&app.color_scheme, color))
                         .stroke(if is_selected {
                             egui::Stroke::new(3.0, egui::Color32::BLACK)
                         } else {
@@ -68,15 +62,7 @@ pub fn draw_controls(app: &mut CubeApp, ui: &mut egui::Ui) {
                             let current_color = app.get_current_face_sticker(position);
 
                             let button_color = if let Some(color) = current_color {
-                                match color {
-                                    Color::White => egui::Color32::from_rgb(255, 255, 255),
-                                    Color::Yellow => egui::Color32::from_rgb(255, 255, 0),
-                                    Color::Green => egui::Color32::from_rgb(0, 200, 0),
-                                    Color::Blue => egui::Color32::from_rgb(0, 100, 255),
-                                    Color::Red => egui::Color32::from_rgb(255, 0, 0),
-                                    Color::Orange => egui::Color32::from_rgb(255, 140, 0),
-                                    Color::Gray => egui::Color32::from_rgb(180, 180, 180),
-                                }
+                                scheme_color32(&app.color_scheme, color)
                             } else {
                                 egui::Color32::from_rgb(200, 200, 200) // 未設定
                             };
@@ -102,6 +88,17 @@ pub fn draw_controls(app: &mut CubeApp, ui: &mut egui::Ui) {
                 ui.add_space(5.0);
             }
 
+            // 面の向きの修正案
+            if let Some(description) = app.scan_fix.as_ref().map(|fix| fix.describe()) {
+                ui.label(description);
+                if ui.button("🔧 修正を適用").clicked() {
+                    app.apply_scan_fix();
+                }
+                ui.add_space(5.0);
+            }
+
+            show_repairs(ui, app);
+
             // ナビゲーションボタン
             ui.horizontal(|ui| {
                 // 前の面へ
@@ -171,6 +168,72 @@ pub fn draw_controls(app: &mut CubeApp, ui: &mut egui::Ui) {
                 app.load_with_dialog();
             }
         });
+        ui.horizontal(|ui| {
+            if ui.button("📜 手順を開く").clicked() {
+                app.open_alg_with_dialog();
+            }
+            if ui.button("📝 手順を保存").clicked() {
+                app.save_alg_with_dialog();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.menu_button("🗂 セッション", |ui| {
+                if ui.button("セッションを保存...").clicked() {
+                    app.save_session_with_dialog();
+                    ui.close_menu();
+                }
+                if ui.button("セッションを開く...").clicked() {
+                    app.load_session_with_dialog();
+                    ui.close_menu();
+                }
+                ui.checkbox(&mut app.autosave, "終了時に自動保存");
+            });
+        });
+
+        // フェイスレット文字列（他のツールとの受け渡し）
+        ui.label("フェイスレット（Ctrl+V で貼り付け）:");
+        ui.horizontal(|ui| {
+            ui.label("面の順:");
+            ui.add(egui::TextEdit::singleline(&mut app.facelet_order_input).desired_width(60.0));
+        });
+        ui.collapsing("面ごとの読み方", |ui| {
+            for face in Face::ALL {
+                let reading = &mut app.facelet_reading[face.index()];
+                ui.horizontal(|ui| {
+                    ui.label(face.label());
+                    egui::ComboBox::from_id_salt(("facelet_reading", face.index()))
+                        .selected_text(reading.label())
+                        .show_ui(ui, |ui| {
+                            for r in ReadingDirection::ALL {
+                                ui.selectable_value(reading, r, r.label());
+                            }
+                        });
+                });
+            }
+        });
+        ui.text_edit_singleline(&mut app.facelet_input);
+        ui.horizontal(|ui| {
+            if ui.button("📥 読み込み").clicked() {
+                app.load_facelet_input();
+            }
+            if ui.button("📋 コピー").clicked() {
+                app.copy_facelets(ui.ctx());
+            }
+        });
+
+        show_repairs(ui, app);
+
+        // 配色
+        ui.horizontal(|ui| {
+            ui.label("配色:");
+            egui::ComboBox::from_id_salt("color_scheme")
+                .selected_text(app.color_scheme.name())
+                .show_ui(ui, |ui| {
+                    for (name, scheme) in ColorScheme::presets() {
+                        ui.selectable_value(&mut app.color_scheme, scheme, name);
+                    }
+                });
+        });
 
         ui.add_space(10.0);
 
@@ -245,6 +308,36 @@ pub fn draw_controls(app: &mut CubeApp, ui: &mut egui::Ui) {
 
         ui.add_space(5.0);
 
+        // 手順の入力
+        ui.label("手順入力:");
+        ui.text_edit_singleline(&mut app.algorithm_input);
+        ui.horizontal(|ui| {
+            if ui.button("📊 解析").clicked() {
+                app.analyze_algorithm();
+            }
+            if ui.button("▶ 実行").clicked() {
+                app.apply_algorithm();
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui.button("鏡像 (M)").clicked() {
+                app.transform_algorithm(|a| a.mirror(MirrorPlane::M));
+            }
+            if ui.button("逆手順").clicked() {
+                app.transform_algorithm(Algorithm::inverse);
+            }
+            if ui.button("y").clicked() {
+                app.transform_algorithm(|a| a.rotated(Rotation::Y));
+            }
+        });
+        if !app.algorithm_text.is_empty() {
+            ui.label(&app.algorithm_text);
+        }
+
+        show_algorithm_search(ui, app);
+
+        ui.add_space(5.0);
+
         // Undo/Redo ボタン
         ui.horizontal(|ui| {
             if ui
@@ -281,6 +374,9 @@ pub fn draw_controls(app: &mut CubeApp, ui: &mut egui::Ui) {
             ui.radio_value(&mut app.ignore_orientation, false, "向きも揃える");
         });
     });
+    ui.add_enabled_ui(!app.solving, |ui| {
+        show_solve_target(ui, app);
+    });
 
     if app.solving {
         // 探索中: プログレスバーと経過時間を表示
@@ -305,6 +401,18 @@ pub fn draw_controls(app: &mut CubeApp, ui: &mut egui::Ui) {
         if ui.button("解法を探す").clicked() {
             app.solve();
         }
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("method")
+                .selected_text(app.method.name())
+                .show_ui(ui, |ui| {
+                    for method in Method::ALL {
+                        ui.selectable_value(&mut app.method, method, method.name());
+                    }
+                });
+            if ui.button("手法で解く").clicked() {
+                app.solve_with_method();
+            }
+        });
     }
 
     if !app.solution_text.is_empty() {
@@ -362,6 +470,36 @@ pub fn draw_controls(app: &mut CubeApp, ui: &mut egui::Ui) {
             ui.colored_label(egui::Color32::GREEN, "完了!");
         }
 
+        // 手法で解いた場合は、いまのステップの説明を表示（ガイド）
+        if let Some((index, step)) = app
+            .method_solution
+            .as_ref()
+            .and_then(|m| m.step_at(app.solution_step))
+        {
+            ui.add_space(5.0);
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 200, 0),
+                format!("ステップ{}: {}", index + 1, step.label),
+            );
+            ui.label(&step.explanation);
+        }
+
+        // 手順ファイルを再生している場合は、いまのステップのコメントを表示
+        if let Some((index, step)) = app
+            .alg_file
+            .as_ref()
+            .and_then(|file| file.step_at(app.solution_step))
+        {
+            ui.add_space(5.0);
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 200, 0),
+                format!("ステップ{}: {}", index + 1, step.algorithm),
+            );
+            if let Some(comment) = &step.comment {
+                ui.label(comment);
+            }
+        }
+
         ui.add_space(5.0);
 
         // ステップ操作ボタン
@@ -411,4 +549,151 @@ pub fn draw_controls(app: &mut CubeApp, ui: &mut egui::Ui) {
     } else {
         ui.label("未完成");
     }
+
+    ui.add_space(10.0);
+
+    // 対称性
+    ui.label("対称性:");
+    ui.horizontal(|ui| {
+        if ui.button("🔍 対称性を調べる").clicked() {
+            app.analyze_symmetry();
+        }
+        if ui.button("📌 基準にする").clicked() {
+            app.set_symmetry_reference();
+        }
+    });
+    if !app.symmetry_text.is_empty() {
+        ui.label(&app.symmetry_text);
+    }
+}
+
+/// 無効な状態に対する修正案を表示
+fn show_repairs(ui: &mut egui::Ui, app: &mut CubeApp) {
+    if app.repairs.is_empty() {
+        return;
+    }
+
+    ui.label("修正案:");
+    let descriptions: Vec<String> = app.repairs.iter().map(|r| r.describe()).collect();
+    for (index, description) in descriptions.iter().enumerate() {
+        if ui.button(format!("🔧 {}", description)).clicked() {
+            app.apply_repair(index);
+        }
+    }
+    ui.add_space(5.0);
+}
+
+/// 配色の色（スキャン入力で選ぶ実際の色）をegui Color32に変換
+fn scheme_color32(scheme: &ColorScheme, color: Color) -> egui::Color32 {
+    let [r, g, b] = scheme.rgb(color);
+    egui::Color32::from_rgb(r, g, b)
+}
+
+/// 完成とみなす向きの選択
+fn show_solve_target(ui: &mut egui::Ui, app: &mut CubeApp) {
+    // 下面の色は内部表現（標準配色）で保持し、表示は現在の配色の色名にする
+    let mut options = vec![
+        (
+            TargetOrientation::Any,
+            "指定なし（カラーニュートラル）".to_string(),
+        ),
+        (
+            TargetOrientation::Fixed(CubeOrientation::HOME),
+            "初期状態の向き".to_string(),
+        ),
+        (
+            TargetOrientation::BottomColors(vec![Color::White, Color::Yellow]),
+            format!(
+                "下面: {}か{}",
+                app.color_label(Color::White),
+                app.color_label(Color::Yellow)
+            ),
+        ),
+    ];
+    for color in Color::ALL {
+        options.push((
+            TargetOrientation::BottomColors(vec![color]),
+            format!("下面: {}", app.color_label(color)),
+        ));
+    }
+
+    let selected = options
+        .iter()
+        .find(|(target, _)| *target == app.solve_target)
+        .map_or_else(|| "カスタム".to_string(), |(_, label)| label.clone());
+
+    ui.horizontal(|ui| {
+        ui.label("完成の向き:");
+        egui::ComboBox::from_id_salt("solve_target")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                for (target, label) in options {
+                    ui.selectable_value(&mut app.solve_target, target, label);
+                }
+            });
+    });
+}
+
+/// 手順の検索（条件に合う手順を短い順に一覧）
+fn show_algorithm_search(ui: &mut egui::Ui, app: &mut CubeApp) {
+    ui.collapsing("🔎 手順検索", |ui| {
+        ui.horizontal(|ui| {
+            ui.label("使う面:");
+            for face in Face::ALL {
+                let name = &face.label()[..1];
+                ui.checkbox(&mut app.search_faces[face.index()], name);
+            }
+        });
+        ui.add(egui::Slider::new(&mut app.search_max_length, 1..=10).text("最大手数"));
+        ui.checkbox(
+            &mut app.search_preserve_first_layer,
+            "1層目（D面の層）を保つ",
+        );
+
+        let mut swap = app.search_swap.is_some();
+        ui.checkbox(&mut swap, "2つのコーナーを入れ替える");
+        if swap {
+            let (mut a, mut b) = app.search_swap.unwrap_or((Corner::Ufr, Corner::Ubr));
+            ui.horizontal(|ui| {
+                corner_combo(ui, "search_swap_a", &mut a);
+                ui.label("↔");
+                corner_combo(ui, "search_swap_b", &mut b);
+            });
+            app.search_swap = Some((a, b));
+        } else {
+            app.search_swap = None;
+        }
+
+        ui.add_enabled_ui(!app.is_searching(), |ui| {
+            if ui.button("🔎 検索").clicked() {
+                app.start_algorithm_search();
+            }
+        });
+        if !app.search_text.is_empty() {
+            ui.label(&app.search_text);
+        }
+
+        // 結果（クリックで手順入力欄に設定）
+        let mut selected = None;
+        for (i, algorithm) in app.search_results.iter().enumerate().take(30) {
+            let text = format!("{}手: {}", algorithm.len(), algorithm);
+            if ui.small_button(text).clicked() {
+                selected = Some(i);
+            }
+        }
+        if let Some(i) = selected {
+            app.select_search_result(i);
+        }
+    });
+}
+
+/// コーナーの選択
+fn corner_combo(ui: &mut egui::Ui, id: &str, corner: &mut Corner) {
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(corner.to_string())
+        .show_ui(ui, |ui| {
+            for c in Corner::ALL {
+                ui.selectable_value(corner, c, c.to_string());
+            }
+        });
 }
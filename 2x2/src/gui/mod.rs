@@ -2,6 +2,7 @@ pub mod app;
 pub mod controls;
 pub mod renderer;
 pub mod renderer_3d;
+pub mod session;
 pub mod statistics_panel;
 
 pub use app::CubeApp;
@@ -1,17 +1,21 @@
-use crate::cube::{Color, Cube, Move, Sticker};
+use crate::cube::{ColorScheme, Cube, Face, Move, Sticker};
+use crate::geometry::{self, color_to_color32};
 use crate::gui::app::AnimationState;
 use egui::{Color32, Painter, Pos2, Rect, Stroke, Vec2};
 
-/// ステッカーの色をegui Color32に変換
-fn color_to_color32(color: Color) -> Color32 {
-    match color {
-        Color::White => Color32::from_rgb(255, 255, 255),
-        Color::Yellow => Color32::from_rgb(255, 255, 0),
-        Color::Green => Color32::from_rgb(0, 200, 0),
-        Color::Blue => Color32::from_rgb(0, 100, 255),
-        Color::Red => Color32::from_rgb(255, 50, 50),
-        Color::Orange => Color32::from_rgb(255, 165, 0),
-        Color::Gray => Color32::from_rgb(180, 180, 180), // 未設定用グレー
+/// 描画するステッカーの塗り色と向き
+#[derive(Clone, Copy)]
+struct StickerPaint {
+    fill: Color32,
+    orientation: u8,
+}
+
+impl StickerPaint {
+    fn new(scheme: &ColorScheme, sticker: Sticker) -> Self {
+        Self {
+            fill: color_to_color32(scheme, sticker.color),
+            orientation: sticker.orientation,
+        }
     }
 }
 
@@ -20,12 +24,12 @@ fn draw_sticker(
     painter: &Painter,
     center: Pos2,
     size: f32,
-    sticker: Sticker,
+    sticker: StickerPaint,
     rotation_offset_deg: f32,
     alpha: f32,
     shadow_offset: Vec2,
 ) {
-    let color = color_to_color32(sticker.color).linear_multiply(alpha);
+    let color = sticker.fill.linear_multiply(alpha);
     let stroke_color = Color32::BLACK.linear_multiply(alpha);
 
     // 影の描画 (もしあれば)
@@ -114,31 +118,8 @@ fn draw_arrow(painter: &Painter, center: Pos2, length: f32, rotation: f32, alpha
 
 /// インデックスに対応するグリッド座標 (col, row) を取得
 fn get_grid_coords(index: usize) -> Pos2 {
-    let (col, row) = match index {
-        0..=3 => (2.0 + (index % 2) as f32, 0.0 + (index / 2) as f32), // U
-        4..=7 => (
-            2.0 + ((index - 4) % 2) as f32,
-            4.0 + ((index - 4) / 2) as f32,
-        ), // D
-        8..=11 => (
-            0.0 + ((index - 8) % 2) as f32,
-            2.0 + ((index - 8) / 2) as f32,
-        ), // L
-        12..=15 => (
-            4.0 + ((index - 12) % 2) as f32,
-            2.0 + ((index - 12) / 2) as f32,
-        ), // R
-        16..=19 => (
-            2.0 + ((index - 16) % 2) as f32,
-            2.0 + ((index - 16) / 2) as f32,
-        ), // F
-        20..=23 => (
-            6.0 + ((index - 20) % 2) as f32,
-            2.0 + ((index - 20) / 2) as f32,
-        ), // B
-        _ => (0.0, 0.0),
-    };
-    Pos2::new(col, row)
+    let (col, row) = geometry::net_cell(index);
+    Pos2::new(col as f32, row as f32)
 }
 
 /// アニメーション情報の型エイリアス: (移動マッピング, 回転面情報)
@@ -369,13 +350,14 @@ pub fn draw_cube(
     ui: &mut egui::Ui,
     rect: Rect,
     cube: &Cube,
+    scheme: &ColorScheme,
     animation: Option<&AnimationState>,
     highlight_face_index: Option<usize>,
 ) {
     let painter = ui.painter();
 
-    let grid_cols = 8.0;
-    let grid_rows = 6.0;
+    let grid_cols = geometry::NET_COLUMNS as f32;
+    let grid_rows = geometry::NET_ROWS as f32;
 
     // グリッドサイズ計算
     let grid_size = (rect.width() / grid_cols).min(rect.height() / grid_rows) * 0.95;
@@ -586,7 +568,7 @@ pub fn draw_cube(
                             painter,
                             ghost_pos + ghost_arc,
                             sticker_size * (1.0 - ghost_t * 2.0),
-                            sticker,
+                            StickerPaint::new(scheme, sticker),
                             rotation,
                             0.3 * current_alpha * (1.0 - ghost_t * 5.0),
                             Vec2::ZERO,
@@ -598,7 +580,7 @@ pub fn draw_cube(
                     painter,
                     screen_pos,
                     current_sticker_size,
-                    sticker,
+                    StickerPaint::new(scheme, sticker),
                     rotation,
                     current_alpha,
                     shadow_offset,
@@ -612,7 +594,7 @@ pub fn draw_cube(
                 painter,
                 screen_pos,
                 sticker_size,
-                sticker,
+                StickerPaint::new(scheme, sticker),
                 rotation,
                 1.0,
                 Vec2::ZERO,
@@ -671,15 +653,10 @@ pub fn draw_cube(
 
 /// インデックスに対応する面全体のグリッド領域を取得
 fn get_face_grid_rect(index: usize) -> Rect {
-    let (min_col, min_row) = match index {
-        0..=3 => (2.0, 0.0),   // U
-        4..=7 => (2.0, 4.0),   // D
-        8..=11 => (0.0, 2.0),  // L
-        12..=15 => (4.0, 2.0), // R
-        16..=19 => (2.0, 2.0), // F
-        20..=23 => (6.0, 2.0), // B
-        _ => (0.0, 0.0),
-    };
+    let (min_col, min_row) = geometry::net_face_origin(Face::ALL[index / 4]);
     // 2x2なのでサイズは2.0x2.0
-    Rect::from_min_size(Pos2::new(min_col, min_row), Vec2::new(2.0, 2.0))
+    Rect::from_min_size(
+        Pos2::new(min_col as f32, min_row as f32),
+        Vec2::new(2.0, 2.0),
+    )
 }
@@ -0,0 +1,539 @@
+use crate::alg_file::AlgFile;
+use crate::cube::io::content_lines;
+use crate::cube::{Color, ColorScheme, Cube, Face, Move};
+use crate::error::{parse_error, Result};
+use crate::geometry::View3D;
+use crate::gui::app::ViewMode;
+use crate::history::History;
+use crate::method::{self, Method, MethodSolution};
+use crate::solver::{CubeOrientation, TargetOrientation};
+use crate::statistics::Statistics;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+/// セッションファイルの先頭行
+pub const SESSION_HEADER: &str = "2x2session v1";
+
+/// セッションファイルの拡張子
+pub const SESSION_EXTENSION: &str = "session";
+
+/// 終了時に自動保存するファイル名（ホームディレクトリに置く）
+const AUTOSAVE_FILE_NAME: &str = ".rubiks-cube-2x2.session";
+
+/// 表示用のRGB値を保存する色の順（最後は未設定用のグレー）
+const PALETTE_COLORS: [Color; 7] = [
+    Color::White,
+    Color::Yellow,
+    Color::Green,
+    Color::Blue,
+    Color::Red,
+    Color::Orange,
+    Color::Gray,
+];
+
+/// アプリの状態のうち、終了しても残したいもの
+///
+/// 局面（向きを含む）・操作履歴（Undo/Redo）・解法とステップの位置・統計情報・表示設定を
+/// `キー: 値` の行からなるテキストで保存し、そのまま復元できます。
+/// 探索中の処理・アニメーション・スキャン入力の途中経過は保存しません。
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub cube: Cube,
+    pub history: History,
+    pub solution: Option<Vec<Move>>,
+    /// 解法を始めた局面
+    pub solution_cube_state: Option<Cube>,
+    pub solution_step: usize,
+    pub solution_text: String,
+    pub statistics: Statistics,
+    pub view_mode: ViewMode,
+    pub view_3d: View3D,
+    pub animation_speed: f32,
+    pub ignore_orientation: bool,
+    /// 完成とみなすキューブ全体の向き
+    pub solve_target: TargetOrientation,
+    /// 手法で解くときの解法
+    pub method: Method,
+    /// 手法で解いた結果
+    ///
+    /// 保存するのは解法の名前だけで、読み込むときに解法の開始局面から求め直します
+    /// （保存した解法と一致しない場合は `None`）。
+    pub method_solution: Option<MethodSolution>,
+    /// 読み込んだ手順ファイル
+    pub alg_file: Option<AlgFile>,
+    pub color_scheme: ColorScheme,
+    /// 終了時に自動保存する
+    ///
+    /// オフにして終了したときは、局面などは保存せずこの設定だけを自動保存のファイルに残し、
+    /// 次回の起動時には復元しません。
+    pub autosave: bool,
+}
+
+impl Session {
+    /// 終了時の自動保存に使うファイル（ホームディレクトリが分からない場合は `None`）
+    #[must_use]
+    pub fn autosave_path() -> Option<PathBuf> {
+        std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(|home| PathBuf::from(home).join(AUTOSAVE_FILE_NAME))
+    }
+
+    /// セッションファイルの文字列に変換します。
+    #[must_use]
+    pub fn to_file_format(&self) -> String {
+        let mut lines = vec![SESSION_HEADER.to_string()];
+        let mut push = |key: &str, value: String| lines.push(format!("{key}: {value}"));
+
+        push("cube", cube_colors(&self.cube));
+        push("cube_arrows", cube_arrows(&self.cube));
+        push("undo", moves_text(self.history.undo_moves()));
+        push("redo", moves_text(self.history.redo_moves()));
+        if let Some(solution) = &self.solution {
+            push("solution", moves_text(solution));
+            push("solution_step", self.solution_step.to_string());
+        }
+        if let Some(start) = &self.solution_cube_state {
+            push("solution_start", cube_colors(start));
+            push("solution_start_arrows", cube_arrows(start));
+        }
+        if !self.solution_text.is_empty() {
+            push("solution_text", escape(&self.solution_text));
+        }
+        if let Some(solution) = &self.method_solution {
+            push("method_solution", method_key(solution.method).to_string());
+        }
+        if let Some(file) = &self.alg_file {
+            push("alg_file", escape(&file.to_file_format()));
+        }
+
+        let stats = &self.statistics;
+        push("total_solves", stats.total_solves.to_string());
+        push("successful_solves", stats.successful_solves.to_string());
+        push("total_solve_time", duration_text(stats.total_solve_time));
+        if let Some(best) = stats.best_solve_time {
+            push("best_solve_time", duration_text(best));
+        }
+        push("total_manual_moves", stats.total_manual_moves.to_string());
+        let start = stats
+            .session_start
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO);
+        push("session_start", duration_text(start));
+
+        push("view_mode", view_mode_name(self.view_mode).to_string());
+        push("view_yaw", self.view_3d.yaw.to_string());
+        push("view_pitch", self.view_3d.pitch.to_string());
+        push("view_scale", self.view_3d.scale.to_string());
+        push("animation_speed", self.animation_speed.to_string());
+        push("ignore_orientation", self.ignore_orientation.to_string());
+        push("solve_target", target_text(&self.solve_target));
+        push("method", method_key(self.method).to_string());
+        push(
+            "scheme",
+            Face::ALL
+                .iter()
+                .map(|&face| self.color_scheme.face_color(face).letter())
+                .collect(),
+        );
+        push(
+            "palette",
+            PALETTE_COLORS
+                .iter()
+                .map(|&color| {
+                    let [r, g, b] = self.color_scheme.rgb(color);
+                    format!("#{r:02x}{g:02x}{b:02x}")
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+        push("autosave", self.autosave.to_string());
+
+        lines.join("\n") + "\n"
+    }
+
+    /// セッションファイルの文字列を読み込みます。
+    ///
+    /// 書かれていない項目は初期状態のままにし、知らないキーは無視します
+    /// （新しい版で保存したファイルも読めるように）。
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut lines = content_lines(s);
+
+        let (number, header) = lines.next().unwrap_or((1, ""));
+        if header.trim() != SESSION_HEADER {
+            return Err(parse_error(
+                number,
+                1,
+                &format!("`{SESSION_HEADER}`"),
+                format!("`{}`", header.trim()),
+            ));
+        }
+
+        let mut session = Self::default();
+        let mut cube = CubeText::default();
+        let mut start = CubeText::default();
+        let mut undo = Vec::new();
+        let mut redo = Vec::new();
+        let mut method_solution = None;
+
+        for (number, line) in lines {
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(parse_error(
+                    number,
+                    1,
+                    "`キー: 値`",
+                    format!("`{}`", line.trim()),
+                ));
+            };
+            // 値の始まる列（キーと `:` と空白の後）
+            let column =
+                key.chars().count() + value.chars().take_while(|c| c.is_whitespace()).count() + 2;
+            let value = value.trim();
+            let invalid =
+                |expected: &str| parse_error(number, column, expected, format!("`{value}`"));
+            let moves = || parse_moves(value).map_err(|_| invalid("回転記号"));
+            let number_value = || value.parse::<usize>().map_err(|_| invalid("0以上の整数"));
+            let float = || value.parse::<f32>().map_err(|_| invalid("数値"));
+            let duration = || parse_duration(value).ok_or_else(|| invalid("秒数"));
+            let boolean = || {
+                value
+                    .parse::<bool>()
+                    .map_err(|_| invalid("`true` または `false`"))
+            };
+            let method = || parse_method(value).ok_or_else(|| invalid(METHOD_KEYS));
+
+            match key.trim() {
+                "cube" => cube.colors = Some((number, value.to_string())),
+                "cube_arrows" => cube.arrows = Some((number, value.to_string())),
+                "undo" => undo = moves()?,
+                "redo" => redo = moves()?,
+                "solution" => session.solution = Some(moves()?),
+                "solution_step" => session.solution_step = number_value()?,
+                "solution_start" => start.colors = Some((number, value.to_string())),
+                "solution_start_arrows" => start.arrows = Some((number, value.to_string())),
+                "solution_text" => session.solution_text = unescape(value),
+                "method_solution" => method_solution = Some(method()?),
+                "alg_file" => {
+                    let file =
+                        AlgFile::parse(&unescape(value)).map_err(|_| invalid("手順ファイル"))?;
+                    session.alg_file = Some(file);
+                }
+                "total_solves" => session.statistics.total_solves = number_value()?,
+                "successful_solves" => session.statistics.successful_solves = number_value()?,
+                "total_solve_time" => session.statistics.total_solve_time = duration()?,
+                "best_solve_time" => session.statistics.best_solve_time = Some(duration()?),
+                "total_manual_moves" => session.statistics.total_manual_moves = number_value()?,
+                "session_start" => session.statistics.session_start = UNIX_EPOCH + duration()?,
+                "view_mode" => {
+                    session.view_mode = parse_view_mode(value)
+                        .ok_or_else(|| invalid("`2d`・`3d`・`both` のいずれか"))?;
+                }
+                "view_yaw" => session.view_3d.yaw = float()?,
+                "view_pitch" => session.view_3d.pitch = float()?,
+                "view_scale" => session.view_3d.scale = float()?,
+                "animation_speed" => session.animation_speed = float()?,
+                "ignore_orientation" => session.ignore_orientation = boolean()?,
+                "solve_target" => {
+                    session.solve_target = parse_target(value)
+                        .ok_or_else(|| invalid("`any`・`fixed 上と前の色`・`bottom 下の色`"))?;
+                }
+                "method" => session.method = method()?,
+                "autosave" => session.autosave = boolean()?,
+                "scheme" => {
+                    let faces = parse_colors::<6>(value)
+                        .ok_or_else(|| invalid("6面の色の文字（W/Y/G/B/R/O）"))?;
+                    let palette = PALETTE_COLORS.map(|color| session.color_scheme.rgb(color));
+                    session.color_scheme = ColorScheme::new(faces)?;
+                    for (color, rgb) in PALETTE_COLORS.into_iter().zip(palette) {
+                        session.color_scheme.set_rgb(color, rgb);
+                    }
+                }
+                "palette" => {
+                    let palette = parse_palette(value)
+                        .ok_or_else(|| invalid("7色のRGB値（例: `#ffffff`）"))?;
+                    for (color, rgb) in PALETTE_COLORS.into_iter().zip(palette) {
+                        session.color_scheme.set_rgb(color, rgb);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(cube) = cube.to_cube()? {
+            session.cube = cube;
+        }
+        session.solution_cube_state = start.to_cube()?;
+        session.history = History::from_stacks(undo, redo);
+        let solution_len = session.solution.as_ref().map_or(0, Vec::len);
+        session.solution_step = session.solution_step.min(solution_len);
+        session.method_solution = method_solution.and_then(|method| {
+            let solution = method::solve(session.solution_cube_state.as_ref()?, method).ok()?;
+            (Some(&solution.algorithm().moves) == session.solution.as_ref()).then_some(solution)
+        });
+        Ok(session)
+    }
+
+    /// ファイルから読み込みます。
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// ファイルに保存します。
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_file_format())?;
+        Ok(())
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            cube: Cube::new(),
+            history: History::new(),
+            solution: None,
+            solution_cube_state: None,
+            solution_step: 0,
+            solution_text: String::new(),
+            statistics: Statistics::new(),
+            view_mode: ViewMode::Both,
+            view_3d: View3D::default(),
+            animation_speed: crate::geometry::DEFAULT_ANIMATION_DURATION,
+            ignore_orientation: false,
+            solve_target: TargetOrientation::Any,
+            method: Method::Beginner,
+            method_solution: None,
+            alg_file: None,
+            color_scheme: ColorScheme::standard(),
+            autosave: true,
+        }
+    }
+}
+
+/// 空白を除いた `N` 文字の色の文字
+fn parse_colors<const N: usize>(s: &str) -> Option<[Color; N]> {
+    let colors: Vec<Color> = s
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(Color::from_letter)
+        .collect::<Option<_>>()?;
+    colors.try_into().ok()
+}
+
+/// 内部順序（U, D, L, R, F, B）の色を面ごとに区切った文字列（色は標準配色）
+fn cube_colors(cube: &Cube) -> String {
+    cube.stickers
+        .chunks(4)
+        .map(|face| face.iter().map(|s| s.color.letter()).collect())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// 内部順序の向き（0-3）を面ごとに区切った文字列
+fn cube_arrows(cube: &Cube) -> String {
+    cube.stickers
+        .chunks(4)
+        .map(|face| {
+            face.iter()
+                .map(|s| char::from(b'0' + s.orientation % 4))
+                .collect()
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// 局面の色の行と向きの行（行番号と値）
+#[derive(Default)]
+struct CubeText {
+    colors: Option<(usize, String)>,
+    arrows: Option<(usize, String)>,
+}
+
+impl CubeText {
+    /// 局面を作る（色の行がなければ `None`、向きの行がなければ時計回りの向き）
+    ///
+    /// 保存したときの局面をそのまま戻すため、色の並びの妥当性は問いません。
+    fn to_cube(&self) -> Result<Option<Cube>> {
+        let Some((line, colors)) = &self.colors else {
+            return Ok(None);
+        };
+        let colors = parse_colors::<24>(colors).ok_or_else(|| {
+            parse_error(
+                *line,
+                1,
+                "24枚の色の文字（W/Y/G/B/R/O）",
+                format!("`{colors}`"),
+            )
+        })?;
+        let mut cube = Cube::new();
+        for (sticker, color) in cube.stickers.iter_mut().zip(colors) {
+            sticker.color = color;
+        }
+        let Some((line, arrows)) = &self.arrows else {
+            return Ok(Some(cube.with_clockwise_orientations()));
+        };
+        let orientations: [u8; 24] = arrows
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_digit(10).filter(|&d| d < 4).map(|d| d as u8))
+            .collect::<Option<Vec<u8>>>()
+            .and_then(|arrows| arrows.try_into().ok())
+            .ok_or_else(|| {
+                parse_error(*line, 1, "24枚の向きの数字（0-3）", format!("`{arrows}`"))
+            })?;
+        for (sticker, orientation) in cube.stickers.iter_mut().zip(orientations) {
+            sticker.orientation = orientation;
+        }
+        Ok(Some(cube))
+    }
+}
+
+fn moves_text(moves: &[Move]) -> String {
+    moves
+        .iter()
+        .map(Move::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_moves(s: &str) -> Result<Vec<Move>> {
+    s.split_whitespace().map(str::parse).collect()
+}
+
+/// 秒数（小数点以下9桁まで、ナノ秒単位で正確に保存する）
+fn duration_text(duration: Duration) -> String {
+    format!("{}.{:09}", duration.as_secs(), duration.subsec_nanos())
+}
+
+fn parse_duration(s: &str) -> Option<Duration> {
+    let (secs, nanos) = s.split_once('.').unwrap_or((s, "0"));
+    if nanos.len() > 9 {
+        return None;
+    }
+    let nanos: u32 = format!("{nanos:0<9}").parse().ok()?;
+    Some(Duration::new(secs.parse().ok()?, nanos))
+}
+
+fn view_mode_name(mode: ViewMode) -> &'static str {
+    match mode {
+        ViewMode::TwoD => "2d",
+        ViewMode::ThreeD => "3d",
+        ViewMode::Both => "both",
+    }
+}
+
+fn parse_view_mode(s: &str) -> Option<ViewMode> {
+    [ViewMode::TwoD, ViewMode::ThreeD, ViewMode::Both]
+        .into_iter()
+        .find(|&mode| view_mode_name(mode) == s)
+}
+
+/// 解法の名前（`beginner`・`ortega`・`cll`・`eg`）
+fn method_key(method: Method) -> &'static str {
+    match method {
+        Method::Beginner => "beginner",
+        Method::Ortega => "ortega",
+        Method::Cll => "cll",
+        Method::Eg => "eg",
+    }
+}
+
+const METHOD_KEYS: &str = "`beginner`・`ortega`・`cll`・`eg` のいずれか";
+
+fn parse_method(s: &str) -> Option<Method> {
+    Method::ALL
+        .into_iter()
+        .find(|&method| method_key(method) == s)
+}
+
+/// 完成とみなす向き（`any`、U面とF面の色の `fixed WR`、D面の色の候補の `bottom YW`）
+fn target_text(target: &TargetOrientation) -> String {
+    match target {
+        TargetOrientation::Any => "any".to_string(),
+        TargetOrientation::Fixed(orientation) => {
+            format!(
+                "fixed {}{}",
+                orientation.up.letter(),
+                orientation.front.letter()
+            )
+        }
+        TargetOrientation::BottomColors(colors) => {
+            format!(
+                "bottom {}",
+                colors.iter().map(|c| c.letter()).collect::<String>()
+            )
+        }
+    }
+}
+
+fn parse_target(s: &str) -> Option<TargetOrientation> {
+    let (kind, colors) = s.split_once(' ').unwrap_or((s, ""));
+    let colors: Vec<Color> = colors
+        .trim()
+        .chars()
+        .map(Color::from_letter)
+        .collect::<Option<_>>()?;
+    match kind {
+        "any" if colors.is_empty() => Some(TargetOrientation::Any),
+        "fixed" => match colors[..] {
+            [up, front] => Some(TargetOrientation::Fixed(CubeOrientation { up, front })),
+            _ => None,
+        },
+        "bottom" if !colors.is_empty() => Some(TargetOrientation::BottomColors(colors)),
+        _ => None,
+    }
+}
+
+fn parse_palette(s: &str) -> Option<[[u8; 3]; 7]> {
+    let palette: Vec<[u8; 3]> = s
+        .split_whitespace()
+        .map(|hex| {
+            let hex = hex.strip_prefix('#')?;
+            if hex.len() != 6 {
+                return None;
+            }
+            let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+            Some([channel(0)?, channel(2)?, channel(4)?])
+        })
+        .collect::<Option<_>>()?;
+    palette.try_into().ok()
+}
+
+/// 改行を含む文字列を1行にする（`\` と改行をエスケープ）
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut result = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => result.push('\n'),
+                Some(other) => result.push(other),
+                None => result.push('\\'),
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// 終了時に自動保存したセッション（なければ `None`）
+///
+/// 自動保存をオフにして終了した場合も、その設定を読めるように返します。
+/// 復元するかどうかは `Session::autosave` で判断してください。
+#[must_use]
+pub fn load_autosave() -> Option<Session> {
+    let path = Session::autosave_path()?;
+    if !path.exists() {
+        return None;
+    }
+    match Session::load(&path) {
+        Ok(session) => Some(session),
+        Err(e) => {
+            tracing::warn!("自動保存したセッションを読み込めません: {}", e);
+            None
+        }
+    }
+}
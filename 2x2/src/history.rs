@@ -2,6 +2,7 @@ use crate::cube::Move;
 
 /// 操作履歴を管理する構造体
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct History {
     /// Undo用スタック（実行済み操作）
     undo_stack: Vec<Move>,
@@ -34,6 +35,18 @@ impl History {
         }
     }
 
+    /// Undo用・Redo用のスタックから履歴を復元（デフォルトサイズを超える古い操作は捨てる）
+    ///
+    /// どちらのスタックも末尾が次に取り出す操作です。
+    pub fn from_stacks(mut undo_stack: Vec<Move>, redo_stack: Vec<Move>) -> Self {
+        let mut history = Self::new();
+        let excess = undo_stack.len().saturating_sub(history.max_size);
+        undo_stack.drain(..excess);
+        history.undo_stack = undo_stack;
+        history.redo_stack = redo_stack;
+        history
+    }
+
     /// 操作を履歴に追加
     pub fn push(&mut self, mv: Move) {
         self.undo_stack.push(mv);
@@ -85,6 +98,16 @@ impl History {
         self.redo_stack.clear();
     }
 
+    /// Undo用スタック（古い順、末尾が次に取り消す操作）
+    pub fn undo_moves(&self) -> &[Move] {
+        &self.undo_stack
+    }
+
+    /// Redo用スタック（末尾が次にやり直す操作）
+    pub fn redo_moves(&self) -> &[Move] {
+        &self.redo_stack
+    }
+
     /// Undo可能な操作数
     pub fn undo_count(&self) -> usize {
         self.undo_stack.len()
@@ -1,6 +1,19 @@
+pub mod alg_file;
+pub mod algorithm;
+pub mod batch;
+pub mod beginner;
+pub mod cases;
 pub mod cube;
 pub mod error;
+pub mod geometry;
 pub mod gui;
 pub mod history;
+pub mod method;
+pub mod model;
+pub mod raster;
+pub mod search;
+#[cfg(feature = "serde")]
+mod serialization;
 pub mod solver;
 pub mod statistics;
+pub mod svg;
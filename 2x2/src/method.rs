@@ -0,0 +1,390 @@
+use crate::algorithm::Algorithm;
+use crate::beginner;
+use crate::cases::{self, CaseSet, RecognizedCase};
+use crate::cube::symmetry::{StickerMap, Symmetry};
+use crate::cube::{Corner, Cube, Face, Move};
+use crate::error::{CubeError, Result};
+use std::sync::OnceLock;
+
+/// 1面・1層を作るときに使う回転操作（DBLのコーナーは動かない）
+const BUILD_MOVES: [Move; 9] = [
+    Move::R,
+    Move::Rp,
+    Move::R2,
+    Move::U,
+    Move::Up,
+    Move::U2,
+    Move::F,
+    Move::Fp,
+    Move::F2,
+];
+
+/// DBLのコーナーのD面・L面・B面のステッカー
+const DBL_DOWN: usize = 6;
+const DBL_LEFT: usize = 10;
+const DBL_BACK: usize = 23;
+
+/// D面のDBL以外のステッカー（DFR, DFL, DBR の順）
+const DOWN_STICKERS: [usize; 3] = [5, 4, 7];
+
+/// 人が覚えて使う解法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    /// 1面 → OLL → PBL
+    Ortega,
+    /// 1層 → CLL
+    Cll,
+    /// 1面 → EG（CLL・EG-1・EG-2）
+    Eg,
+    /// 初心者向けの層ごとの解法（1層目 → 上の面の色 → 上の層の並び）
+    Beginner,
+}
+
+impl Method {
+    /// すべての解法
+    pub const ALL: [Method; 4] = [Method::Beginner, Method::Ortega, Method::Cll, Method::Eg];
+
+    /// 表示用の名前
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Method::Ortega => "Ortega",
+            Method::Cll => "CLL",
+            Method::Eg => "EG",
+            Method::Beginner => "初心者向け",
+        }
+    }
+}
+
+impl std::fmt::Display for Method {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// 解法の1ステップ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodStep {
+    /// ステップの名前（例: `1面`, `OLL Sune`, `PBL Adj/-`）
+    pub label: String,
+    /// このステップで何をするかの説明
+    pub explanation: String,
+    pub algorithm: Algorithm,
+}
+
+/// 解法に沿った手順
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodSolution {
+    pub method: Method,
+    /// 手を動かすステップ（何もしないステップは含みません）
+    pub steps: Vec<MethodStep>,
+}
+
+impl MethodSolution {
+    /// すべてのステップをつなげた手順
+    #[must_use]
+    pub fn algorithm(&self) -> Algorithm {
+        Algorithm::new(
+            self.steps
+                .iter()
+                .flat_map(|step| step.algorithm.moves.iter().copied())
+                .collect(),
+        )
+    }
+
+    /// 手順の `index` 手目（0始まり）を含むステップと、その番号（0始まり）
+    #[must_use]
+    pub fn step_at(&self, index: usize) -> Option<(usize, &MethodStep)> {
+        let mut end = 0;
+        self.steps.iter().enumerate().find(|(_, step)| {
+            end += step.algorithm.len();
+            index < end
+        })
+    }
+
+    /// 表示用の説明（例: `ステップ1: 1面 (4手) / ステップ2: OLL Sune`）
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let steps: Vec<String> = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, step)| {
+                format!(
+                    "ステップ{}: {} ({}手)",
+                    i + 1,
+                    step.label,
+                    step.algorithm.len()
+                )
+            })
+            .collect();
+        steps.join(" / ")
+    }
+}
+
+/// 解法に沿って、ステップごとの手順を求めます。
+///
+/// 1面・1層は、DBLのコーナーを含む3面（D面・L面・B面）のうち最も短く作れる面に作ります。
+/// 2ステップ目以降は同梱のケース表（`cases`）の手順を使います。
+/// 無効な局面ではエラーを返します。
+pub fn solve(cube: &Cube, method: Method) -> Result<MethodSolution> {
+    cube.corner_state()?;
+
+    let mut steps = Vec::new();
+    let mut current = cube.normalized();
+    let mut push =
+        |label: String, explanation: String, algorithm: Algorithm, current: &mut Cube| {
+            if !algorithm.is_empty() {
+                algorithm.apply_to(current);
+                steps.push(MethodStep {
+                    label,
+                    explanation,
+                    algorithm,
+                });
+            }
+        };
+    let case_step =
+        |case: &RecognizedCase| format!("{} と認識し、ケース表の手順を回します。", case.describe());
+
+    match method {
+        Method::Ortega | Method::Eg => {
+            let face = build(&current, Goal::Face);
+            let explanation = format!(
+                "{}面を{}手で揃えます。",
+                built_color(&current, &face),
+                face.len()
+            );
+            push("1面".to_string(), explanation, face, &mut current);
+            let sets: &[CaseSet] = if method == Method::Ortega {
+                &[CaseSet::OrtegaOll]
+            } else {
+                &[CaseSet::Cll, CaseSet::Eg1, CaseSet::Eg2]
+            };
+            if let Some(case) = find_case(&current, sets) {
+                let label = match case.case.set {
+                    CaseSet::OrtegaOll => format!("OLL {}", case.case.name),
+                    set => format!("{set} {}", case.case.name),
+                };
+                push(label, case_step(&case), case.solution(), &mut current);
+            }
+            if method == Method::Ortega {
+                if let Some(case) = find_case(&current, &[CaseSet::OrtegaPbl]) {
+                    let label = format!("PBL {}", case.case.name);
+                    push(label, case_step(&case), case.solution(), &mut current);
+                }
+            }
+        }
+        Method::Cll => {
+            let layer = build(&current, Goal::Layer);
+            let explanation = format!(
+                "{}面の層を{}手で揃えます。",
+                built_color(&current, &layer),
+                layer.len()
+            );
+            push("1層".to_string(), explanation, layer, &mut current);
+            if let Some(case) = find_case(&current, &[CaseSet::Cll]) {
+                let label = format!("CLL {}", case.case.name);
+                push(label, case_step(&case), case.solution(), &mut current);
+            }
+        }
+        Method::Beginner => return beginner::solve(cube),
+    }
+
+    if !current.is_solved() {
+        let auf = finishing_turns(&current).ok_or_else(|| {
+            CubeError::Internal(format!("{method} の手順で完成状態に到達しません"))
+        })?;
+        let explanation = format!("{auf} を回して上下の層を合わせます。");
+        push("AUF".to_string(), explanation, auf, &mut current);
+    }
+    Ok(MethodSolution { method, steps })
+}
+
+/// 手順を適用したときに揃う面の色の名前
+fn built_color(cube: &Cube, algorithm: &Algorithm) -> &'static str {
+    let mut built = cube.clone();
+    algorithm.apply_to(&mut built);
+    Face::ALL
+        .into_iter()
+        .map(|face| &built.stickers[face.index() * 4..][..4])
+        .find(|stickers| stickers.iter().all(|s| s.color == stickers[0].color))
+        .map_or("", |stickers| stickers[0].color.label())
+}
+
+/// 指定したセットのうち、最初に当てはまるケース
+fn find_case(cube: &Cube, sets: &[CaseSet]) -> Option<RecognizedCase> {
+    cases::recognize(cube)
+        .into_iter()
+        .find(|case| sets.contains(&case.case.set))
+}
+
+/// 向かい合う2面を回すだけで完成する場合の、その回転
+fn finishing_turns(cube: &Cube) -> Option<Algorithm> {
+    let turns = |face: Face| (0..4u8).map(move |qt| Move::from_face(face, qt));
+    [Face::Up, Face::Left, Face::Front]
+        .into_iter()
+        .find_map(|face| {
+            turns(face).find_map(|a| {
+                turns(face.opposite()).find_map(|b| {
+                    let algorithm = Algorithm::new(a.into_iter().chain(b).collect());
+                    let mut turned = cube.clone();
+                    algorithm.apply_to(&mut turned);
+                    turned.is_solved().then_some(algorithm)
+                })
+            })
+        })
+}
+
+/// 1ステップ目の目標
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Goal {
+    /// D面の色だけを揃える
+    Face,
+    /// D面の層（4個のコーナーの位置と向き）を揃える
+    Layer,
+}
+
+/// DBLを含む3面のうち、最も短い手順で1面（1層）を作る
+fn build(cube: &Cube, goal: Goal) -> Algorithm {
+    holds_fixing_dbl()
+        .map(|hold| {
+            let view = hold.transform(cube);
+            cases::unheld(hold, &build_down(&view, goal))
+        })
+        .min_by_key(Algorithm::len)
+        .expect("DBLを動かさない持ち替えは3通りあります")
+}
+
+/// DBLのコーナーの位置を変えない持ち替え（D面・L面・B面をD面にする3通り）
+fn holds_fixing_dbl() -> impl Iterator<Item = &'static Symmetry> {
+    Symmetry::rotations().iter().filter(|s| {
+        let map = s.face_map();
+        [Face::Down, Face::Left, Face::Back]
+            .iter()
+            .all(|f| matches!(map[f.index()], Face::Down | Face::Left | Face::Back))
+    })
+}
+
+/// D面を作る最短手順（<R, U, F> のみ）
+fn build_down(cube: &Cube, goal: Goal) -> Algorithm {
+    let table = match goal {
+        Goal::Face => &build_tables().face,
+        Goal::Layer => &build_tables().layer,
+    };
+    let destinations = &build_tables().destinations;
+
+    let mut state = piece_positions(cube, goal);
+    let mut moves = Vec::new();
+    while table[state_index(state)] > 0 {
+        let distance = table[state_index(state)];
+        let (mv, next) = BUILD_MOVES
+            .iter()
+            .zip(destinations)
+            .map(|(&mv, dst)| (mv, step(state, dst, goal)))
+            .find(|&(_, next)| table[state_index(next)] == distance - 1)
+            .expect("距離が1つ小さい局面が必ずあります");
+        moves.push(mv);
+        state = next;
+    }
+    Algorithm::new(moves)
+}
+
+/// D面に入る3個のピースの、D面の色のステッカーの位置
+///
+/// `Goal::Layer` では DFR・DFL・DBR に入るピースの順、`Goal::Face` では位置の小さい順です。
+fn piece_positions(cube: &Cube, goal: Goal) -> [u8; 3] {
+    let down = cube.stickers[DBL_DOWN].color;
+    let mut positions: Vec<usize> = (0..24)
+        .filter(|&i| i != DBL_DOWN && cube.stickers[i].color == down)
+        .collect();
+    if goal == Goal::Layer {
+        // DFL には DBL の L面の色、DBR には B面の色を持つピースが入る
+        let has_color = |pos: usize, index: usize| {
+            let (corner, _) = Corner::from_sticker(pos)
+                .expect("すべてのステッカーはいずれかのコーナーに属します");
+            corner
+                .stickers()
+                .iter()
+                .any(|&i| cube.stickers[i].color == cube.stickers[index].color)
+        };
+        positions.sort_by_key(|&pos| {
+            if has_color(pos, DBL_LEFT) {
+                1
+            } else if has_color(pos, DBL_BACK) {
+                2
+            } else {
+                0
+            }
+        });
+    }
+    std::array::from_fn(|i| positions[i] as u8)
+}
+
+/// 3個のステッカーの位置を表の番号にする
+fn state_index(state: [u8; 3]) -> usize {
+    (state[0] as usize * 24 + state[1] as usize) * 24 + state[2] as usize
+}
+
+/// 回転操作でステッカーを移す（`Goal::Face` ではピースを区別しないので並べ直す）
+fn step(state: [u8; 3], destinations: &[u8; 24], goal: Goal) -> [u8; 3] {
+    let mut next = state.map(|pos| destinations[pos as usize]);
+    if goal == Goal::Face {
+        next.sort_unstable();
+    }
+    next
+}
+
+/// 1面・1層を作るための距離の表
+struct BuildTables {
+    /// 回転操作ごとの、各位置のステッカーの移動先（`BUILD_MOVES` の順）
+    destinations: Vec<[u8; 24]>,
+    /// D面の色を揃えるまでの手数（到達しない番号は `u8::MAX`）
+    face: Vec<u8>,
+    /// D面の層を揃えるまでの手数
+    layer: Vec<u8>,
+}
+
+static BUILD_TABLES: OnceLock<BuildTables> = OnceLock::new();
+
+fn build_tables() -> &'static BuildTables {
+    BUILD_TABLES.get_or_init(|| {
+        let destinations: Vec<[u8; 24]> = BUILD_MOVES
+            .iter()
+            .map(|&mv| {
+                let map = StickerMap::from_move(mv);
+                std::array::from_fn(|i| map.destination(i) as u8)
+            })
+            .collect();
+        let goal = DOWN_STICKERS.map(|i| i as u8);
+        let mut sorted_goal = goal;
+        sorted_goal.sort_unstable();
+        BuildTables {
+            face: distances(sorted_goal, &destinations, Goal::Face),
+            layer: distances(goal, &destinations, Goal::Layer),
+            destinations,
+        }
+    })
+}
+
+/// 目標の状態からの幅優先探索（<R, U, F> は逆操作も含むので、距離は目標までの手数と同じ）
+fn distances(goal: [u8; 3], destinations: &[[u8; 24]], kind: Goal) -> Vec<u8> {
+    let mut table = vec![u8::MAX; 24 * 24 * 24];
+    table[state_index(goal)] = 0;
+    let mut frontier = vec![goal];
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        depth += 1;
+        let mut next = Vec::new();
+        for state in frontier {
+            for dst in destinations {
+                let moved = step(state, dst, kind);
+                if table[state_index(moved)] == u8::MAX {
+                    table[state_index(moved)] = depth;
+                    next.push(moved);
+                }
+            }
+        }
+        frontier = next;
+    }
+    table
+}
@@ -0,0 +1,260 @@
+//! 3Dモデル（OBJ/MTL）の出力
+//!
+//! 3Dプリントする教材やBlenderでのレンダリングのために、局面をメッシュとして書き出します。
+//! 8個のキューブ（コーナーピース）はそれぞれ別のオブジェクトで、本体の箱と、
+//! GUIの3D表示と同じ配置（`geometry`）のステッカーの四角形を持ちます。
+//! ステッカーの向きを示す矢印の板も付けられます。
+
+use crate::cube::{Color, ColorScheme, Corner, Cube};
+use crate::error::Result;
+use crate::geometry::{self, Sticker3D};
+use glam::Vec3;
+use std::path::Path;
+
+/// キューブどうしの隙間（中心側の面を引っ込める量の2倍）
+const CUBIE_GAP: f32 = 0.02;
+/// 本体の面からステッカーを浮かせる距離
+const STICKER_LIFT: f32 = 0.005;
+/// 本体の面から矢印を浮かせる距離（ステッカーより手前）
+const ARROW_LIFT: f32 = 0.01;
+/// 矢印の軸の太さ
+const ARROW_SHAFT_WIDTH: f32 = 0.06;
+/// 直方体の各面の法線と4頂点の番号（番号のビット x=1・y=2・z=4 が立っていれば最大側の角）
+const CUBOID_FACES: [(Vec3, [usize; 4]); 6] = [
+    (Vec3::X, [1, 3, 7, 5]),
+    (Vec3::NEG_X, [0, 2, 6, 4]),
+    (Vec3::Y, [2, 3, 7, 6]),
+    (Vec3::NEG_Y, [0, 1, 5, 4]),
+    (Vec3::Z, [4, 5, 7, 6]),
+    (Vec3::NEG_Z, [0, 1, 3, 2]),
+];
+/// 本体のマテリアル名
+const BODY_MATERIAL: &str = "body";
+/// 矢印のマテリアル名
+const ARROW_MATERIAL: &str = "arrow";
+
+/// 3Dモデルの出力の設定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelOptions {
+    /// ステッカーの色に使う配色
+    pub scheme: ColorScheme,
+    /// キューブ全体の1辺の長さ（モデルの単位。3Dプリントならmmなど）
+    pub size: f32,
+    /// 本体の色
+    pub body_color: [u8; 3],
+    /// ステッカーの向きの矢印を付ける
+    pub arrows: bool,
+}
+
+impl Default for ModelOptions {
+    fn default() -> Self {
+        Self {
+            scheme: ColorScheme::standard(),
+            size: 2.0,
+            body_color: [25, 25, 25],
+            arrows: false,
+        }
+    }
+}
+
+/// OBJ形式のメッシュと、そのマテリアル（MTL形式）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjModel {
+    /// OBJファイルの内容
+    pub obj: String,
+    /// MTLファイルの内容
+    pub mtl: String,
+}
+
+/// 局面をOBJ/MTL形式のメッシュにします。
+///
+/// オブジェクトはスロットごとのキューブ（`cubie_UFR` など、`Corner::ALL` の順）で、
+/// マテリアルは本体（`body`）・ステッカーの色（`white` など）・矢印（`arrow`）です。
+/// OBJは `mtllib {mtl_file_name}` でマテリアルを参照します。
+#[must_use]
+pub fn obj_model(cube: &Cube, options: &ModelOptions, mtl_file_name: &str) -> ObjModel {
+    let stickers = geometry::get_initial_stickers();
+    let mut obj = ObjWriter::new(options.size / 2.0);
+    obj.line(&format!("mtllib {mtl_file_name}"));
+
+    for corner in Corner::ALL {
+        let indices = corner.stickers();
+        // キューブのある向き（各軸の符号）はどのステッカーの中心でも同じ
+        let side = stickers[indices[0]].center.signum();
+        obj.line(&format!("o cubie_{corner}"));
+        obj.line(&format!("usemtl {BODY_MATERIAL}"));
+        obj.cuboid(side * (CUBIE_GAP / 2.0), side);
+
+        for index in indices {
+            let def = &stickers[index];
+            let sticker = cube.get_sticker(index);
+            obj.line(&format!("usemtl {}", material_name(sticker.color)));
+            let center = def.center + def.normal * STICKER_LIFT;
+            obj.face(
+                &[
+                    center - def.u_vec - def.v_vec,
+                    center + def.u_vec - def.v_vec,
+                    center + def.u_vec + def.v_vec,
+                    center - def.u_vec + def.v_vec,
+                ],
+                def.normal,
+            );
+            if options.arrows {
+                obj.line(&format!("usemtl {ARROW_MATERIAL}"));
+                write_arrow(&mut obj, def, sticker.orientation);
+            }
+        }
+    }
+
+    ObjModel {
+        obj: obj.text,
+        mtl: mtl(cube, options),
+    }
+}
+
+/// 局面をOBJファイルと、拡張子を `.mtl` にしたMTLファイルに保存します。
+///
+/// # Errors
+/// ファイルの書き込みに失敗した場合
+pub fn save_obj(cube: &Cube, options: &ModelOptions, path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let mtl_path = path.with_extension("mtl");
+    let mtl_file_name = mtl_path
+        .file_name()
+        .map_or_else(|| "cube.mtl".into(), |name| name.to_string_lossy());
+    let model = obj_model(cube, options, &mtl_file_name);
+    std::fs::write(path, model.obj)?;
+    std::fs::write(&mtl_path, model.mtl)?;
+    Ok(())
+}
+
+/// 色のマテリアル名（`white` など）
+fn material_name(color: Color) -> String {
+    format!("{color:?}").to_lowercase()
+}
+
+/// 使われているマテリアルのMTL
+fn mtl(cube: &Cube, options: &ModelOptions) -> String {
+    let mut text = String::from("# rubiks-cube-2x2\n");
+    let mut material = |name: &str, [r, g, b]: [u8; 3]| {
+        text.push_str(&format!(
+            "\nnewmtl {name}\nKd {} {} {}\nKs 0 0 0\nillum 1\n",
+            num(f32::from(r) / 255.0),
+            num(f32::from(g) / 255.0),
+            num(f32::from(b) / 255.0),
+        ));
+    };
+    material(BODY_MATERIAL, options.body_color);
+    for color in Color::ALL.into_iter().chain([Color::Gray]) {
+        if (0..24).any(|i| cube.get_sticker(i).color == color) {
+            material(&material_name(color), options.scheme.display_rgb(color));
+        }
+    }
+    if options.arrows {
+        material(ARROW_MATERIAL, [0, 0, 0]);
+    }
+    text
+}
+
+/// 矢印の板（軸の長方形と先端の三角形）を書く
+///
+/// 3D表示と同じく、向き0: 上 (-v), 1: 右 (+u), 2: 下 (+v), 3: 左 (-u) を指します。
+fn write_arrow(obj: &mut ObjWriter, def: &Sticker3D, orientation: u8) {
+    let direction = match orientation % 4 {
+        0 => -def.v_vec,
+        1 => def.u_vec,
+        2 => def.v_vec,
+        _ => -def.u_vec,
+    } * 0.6;
+    let center = def.center + def.normal * ARROW_LIFT;
+    let tip = center + direction;
+    let along = direction.normalize();
+    let across = def.normal.cross(along);
+    let head = direction.length() * 0.6;
+    let base = tip - along * head;
+
+    let half = across * (ARROW_SHAFT_WIDTH / 2.0);
+    obj.face(
+        &[center - half, base - half, base + half, center + half],
+        def.normal,
+    );
+    obj.face(
+        &[
+            tip,
+            base + across * (head * 0.5),
+            base - across * (head * 0.5),
+        ],
+        def.normal,
+    );
+}
+
+/// OBJの書き出し（頂点の番号を数えながら面を追加する）
+struct ObjWriter {
+    text: String,
+    vertices: usize,
+    normals: usize,
+    scale: f32,
+}
+
+impl ObjWriter {
+    fn new(scale: f32) -> Self {
+        Self {
+            text: String::from("# rubiks-cube-2x2\n"),
+            vertices: 0,
+            normals: 0,
+            scale,
+        }
+    }
+
+    fn line(&mut self, line: &str) {
+        self.text.push_str(line);
+        self.text.push('\n');
+    }
+
+    /// 平らな多角形を、法線の側から見て反時計回りになるように書く
+    fn face(&mut self, points: &[Vec3], normal: Vec3) {
+        let mut points = points.to_vec();
+        if (points[1] - points[0])
+            .cross(points[2] - points[0])
+            .dot(normal)
+            < 0.0
+        {
+            points.reverse();
+        }
+        for p in &points {
+            let p = *p * self.scale;
+            let line = format!("v {} {} {}", num(p.x), num(p.y), num(p.z));
+            self.line(&line);
+        }
+        let line = format!("vn {} {} {}", num(normal.x), num(normal.y), num(normal.z));
+        self.line(&line);
+        self.normals += 1;
+
+        let first = self.vertices + 1;
+        self.vertices += points.len();
+        let indices: Vec<String> = (first..=self.vertices)
+            .map(|i| format!("{i}//{}", self.normals))
+            .collect();
+        self.line(&format!("f {}", indices.join(" ")));
+    }
+
+    /// 2つの角で決まる、軸に沿った直方体を書く
+    fn cuboid(&mut self, a: Vec3, b: Vec3) {
+        let (min, max) = (a.min(b), a.max(b));
+        let corners: [Vec3; 8] = std::array::from_fn(|i| {
+            Vec3::new(
+                if i & 1 != 0 { max.x } else { min.x },
+                if i & 2 != 0 { max.y } else { min.y },
+                if i & 4 != 0 { max.z } else { min.z },
+            )
+        });
+        for (normal, square) in CUBOID_FACES {
+            self.face(&square.map(|i| corners[i]), normal);
+        }
+    }
+}
+
+/// 座標の数値（小数第4位まで、末尾の0は省く）
+fn num(value: f32) -> String {
+    geometry::format_number(value, 4)
+}
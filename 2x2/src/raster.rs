@@ -0,0 +1,476 @@
+//! 局面の画像のPNG出力（CPUでの描画）
+//!
+//! GPUやディスプレイのない環境（CIの成果物やレポートの作成）のために、3D表示と展開図を
+//! RGBAの画像に描いてPNGで書き出します。3D表示の投影と展開図の配置は、GUIやSVGと同じもの
+//! （`geometry`）を使います。手順を回すアニメーションはアニメーションPNG
+//! （APNG）で書き出せます。
+
+use crate::cube::{ColorScheme, Cube, Face, Move};
+use crate::error::{CubeError, Result};
+use crate::geometry::{self, View3D, DEFAULT_ANIMATION_DURATION};
+use egui::{Pos2, Rect, Vec2};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// 1ピクセルの縦横それぞれの標本数（縁のアンチエイリアス用）
+const SAMPLES: usize = 4;
+/// 枠線と矢印の色
+const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+/// RGBA（1チャンネル8ビット、アルファは乗算済みでない）の画像
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// 背景色で塗りつぶした画像を作成します（幅・高さは1以上に切り上げます）。
+    #[must_use]
+    pub fn new(width: u32, height: u32, background: [u8; 4]) -> Self {
+        let (width, height) = (width.max(1), height.max(1));
+        Self {
+            width,
+            height,
+            pixels: background.repeat(width as usize * height as usize),
+        }
+    }
+
+    /// 幅（ピクセル）
+    #[must_use]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// 高さ（ピクセル）
+    #[must_use]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// 左上から行ごとに並べたRGBAの値
+    #[must_use]
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// 指定したピクセルのRGBAの値
+    ///
+    /// # Panics
+    /// 座標が画像の外のとき
+    #[must_use]
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        assert!(x < self.width && y < self.height, "画像の外の座標です");
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+
+    /// PNGとして書き出します。
+    ///
+    /// # Errors
+    /// 書き込みに失敗した場合
+    pub fn write_png<W: Write>(&self, writer: W) -> Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::from)?;
+        writer
+            .write_image_data(&self.pixels)
+            .map_err(io::Error::from)?;
+        writer.finish().map_err(io::Error::from)?;
+        Ok(())
+    }
+
+    /// PNGファイルに保存します。
+    ///
+    /// # Errors
+    /// ファイルを作成できない、または書き込みに失敗した場合
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.write_png(BufWriter::new(File::create(path)?))
+    }
+
+    /// 凸多角形を塗る（縁は標本の割合でぼかす）
+    fn fill_convex(&mut self, points: &[Pos2], color: [u8; 4]) {
+        if points.len() < 3 {
+            return;
+        }
+        let edges = || points.iter().zip(points.iter().cycle().skip(1));
+        // 頂点の並びの向き（時計回りか反時計回りか）に合わせて内側を判定する
+        let area: f32 = edges().map(|(a, b)| a.x * b.y - b.x * a.y).sum();
+        if area.abs() < 1e-6 {
+            return;
+        }
+        let inside = |x: f32, y: f32| {
+            edges().all(|(a, b)| {
+                let cross = (b.x - a.x) * (y - a.y) - (b.y - a.y) * (x - a.x);
+                area * cross >= 0.0
+            })
+        };
+
+        let (min, max) = points.iter().fold(
+            (Pos2::new(f32::MAX, f32::MAX), Pos2::new(f32::MIN, f32::MIN)),
+            |(min, max), p| (min.min(*p), max.max(*p)),
+        );
+        let x_range =
+            (min.x.floor().max(0.0) as usize)..(max.x.ceil().min(self.width as f32) as usize);
+        let y_range =
+            (min.y.floor().max(0.0) as usize)..(max.y.ceil().min(self.height as f32) as usize);
+
+        for y in y_range {
+            for x in x_range.clone() {
+                let mut covered = 0;
+                for sy in 0..SAMPLES {
+                    for sx in 0..SAMPLES {
+                        let px = x as f32 + (sx as f32 + 0.5) / SAMPLES as f32;
+                        let py = y as f32 + (sy as f32 + 0.5) / SAMPLES as f32;
+                        if inside(px, py) {
+                            covered += 1;
+                        }
+                    }
+                }
+                if covered > 0 {
+                    self.blend(x, y, color, covered as f32 / (SAMPLES * SAMPLES) as f32);
+                }
+            }
+        }
+    }
+
+    /// 太さのある線分を描く（両端は太さの半分だけ延ばす）
+    fn stroke_segment(&mut self, from: Pos2, to: Pos2, width: f32, color: [u8; 4]) {
+        let direction = to - from;
+        let length = direction.length();
+        if length < 1e-6 {
+            return;
+        }
+        let along = direction / length * (width / 2.0);
+        let across = Vec2::new(-along.y, along.x);
+        let (from, to) = (from - along, to + along);
+        self.fill_convex(
+            &[from + across, to + across, to - across, from - across],
+            color,
+        );
+    }
+
+    /// 多角形の枠線を描く
+    fn stroke_polygon(&mut self, points: &[Pos2], width: f32, color: [u8; 4]) {
+        for (a, b) in points.iter().zip(points.iter().cycle().skip(1)) {
+            self.stroke_segment(*a, *b, width, color);
+        }
+    }
+
+    /// ピクセルに色を重ねる（`coverage` はピクセルを覆う割合）
+    fn blend(&mut self, x: usize, y: usize, [r, g, b, a]: [u8; 4], coverage: f32) {
+        let alpha = f32::from(a) / 255.0 * coverage;
+        let i = (y * self.width as usize + x) * 4;
+        let dst = &mut self.pixels[i..i + 4];
+        let dst_alpha = f32::from(dst[3]) / 255.0;
+        let out_alpha = alpha + dst_alpha * (1.0 - alpha);
+        if out_alpha <= 0.0 {
+            return;
+        }
+        for (channel, src) in dst[..3].iter_mut().zip([r, g, b]) {
+            let value = (f32::from(src) * alpha + f32::from(*channel) * dst_alpha * (1.0 - alpha))
+                / out_alpha;
+            *channel = value.round() as u8;
+        }
+        dst[3] = (out_alpha * 255.0).round() as u8;
+    }
+}
+
+/// 画像の描画の設定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RasterOptions {
+    /// 画像の幅（px）
+    pub width: u32,
+    /// 画像の高さ（px）
+    pub height: u32,
+    /// 表示に使う配色
+    pub scheme: ColorScheme,
+    /// 背景色（RGBA。透明にするならアルファを0に）
+    pub background: [u8; 4],
+    /// ステッカーの向きの矢印を描く
+    pub arrows: bool,
+    /// 枠で強調する面
+    pub highlight_face: Option<Face>,
+}
+
+impl Default for RasterOptions {
+    fn default() -> Self {
+        Self {
+            width: 480,
+            height: 360,
+            scheme: ColorScheme::standard(),
+            background: [255, 255, 255, 255],
+            arrows: true,
+            highlight_face: None,
+        }
+    }
+}
+
+/// 局面の3D表示を描きます。
+///
+/// 画像の中央に、GUIの3D表示と同じカメラ（`View3D`）・投影・枠線・矢印で描きます。
+#[must_use]
+pub fn render_3d(cube: &Cube, view: &View3D, options: &RasterOptions) -> Image {
+    render_3d_frame(cube, None, view, options)
+}
+
+/// 回転の途中の3D表示を描く（`turn` は回転中の手とイージング後の進み具合。なければ静止した局面）
+fn render_3d_frame(
+    cube: &Cube,
+    turn: Option<(Move, f32)>,
+    view: &View3D,
+    options: &RasterOptions,
+) -> Image {
+    let mut image = Image::new(options.width, options.height, options.background);
+    let rect = Rect::from_min_size(
+        Pos2::ZERO,
+        Vec2::new(image.width as f32, image.height as f32),
+    );
+
+    for face in geometry::project_faces(rect, cube, &options.scheme, turn, view) {
+        image.fill_convex(&face.points, face.color.to_array());
+        image.stroke_polygon(&face.points, geometry::OUTLINE_WIDTH, BLACK);
+        if options
+            .highlight_face
+            .is_some_and(|highlight| highlight.index() == face.sticker_index / 4)
+        {
+            image.stroke_polygon(
+                &face.points,
+                geometry::HIGHLIGHT_WIDTH,
+                geometry::HIGHLIGHT_COLOR.to_array(),
+            );
+        }
+        if options.arrows {
+            let orientation = cube.get_sticker(face.sticker_index).orientation;
+            let target = face.center_2d + face.arrow_vector(orientation);
+            if let Some(head) = geometry::arrow_head(face.center_2d, target) {
+                image.stroke_segment(face.center_2d, target, geometry::ARROW_WIDTH, BLACK);
+                image.fill_convex(&head, BLACK);
+            }
+        }
+    }
+    image
+}
+
+/// 局面の展開図を描きます。
+///
+/// SVGの展開図（`svg::net_svg`）と同じ配置・大きさ・矢印で、画像に収まる最大の大きさで中央に描きます。
+#[must_use]
+pub fn render_net(cube: &Cube, options: &RasterOptions) -> Image {
+    let mut image = Image::new(options.width, options.height, options.background);
+    let (columns, rows) = (geometry::NET_COLUMNS as f32, geometry::NET_ROWS as f32);
+    // 強調する面の枠が外にはみ出さないよう、上下左右にマスの10%の余白を取る
+    let cell = (image.width as f32 / (columns + 0.2)).min(image.height as f32 / (rows + 0.2));
+    let origin = Pos2::new(
+        (image.width as f32 - columns * cell) / 2.0,
+        (image.height as f32 - rows * cell) / 2.0,
+    );
+    // SVGの線の太さ（マスが40pxのとき2px）に合わせる
+    let stroke = (cell * 0.05).max(1.0);
+
+    for index in 0..24 {
+        let (col, row) = geometry::net_cell(index);
+        let center = origin + Vec2::new((col as f32 + 0.5) * cell, (row as f32 + 0.5) * cell);
+        let s = cube.get_sticker(index);
+        let square = rect_points(Rect::from_center_size(
+            center,
+            Vec2::splat(cell * geometry::STICKER_SCALE),
+        ));
+        let [r, g, b] = options.scheme.display_rgb(s.color);
+        image.fill_convex(&square, [r, g, b, 255]);
+        image.stroke_polygon(&square, stroke, BLACK);
+        if options.arrows {
+            draw_net_arrow(
+                &mut image,
+                center,
+                cell * geometry::ARROW_SCALE,
+                s.orientation,
+                stroke,
+            );
+        }
+    }
+
+    if let Some(face) = options.highlight_face {
+        let (col, row) = geometry::net_face_origin(face);
+        let padding = cell * 0.05;
+        let frame = Rect::from_min_size(
+            origin + Vec2::new(col as f32 * cell - padding, row as f32 * cell - padding),
+            Vec2::splat(2.0 * cell + 2.0 * padding),
+        );
+        image.stroke_polygon(
+            &rect_points(frame),
+            stroke * 2.0,
+            geometry::HIGHLIGHT_COLOR.to_array(),
+        );
+    }
+    image
+}
+
+/// 矩形の4頂点（左上から時計回り）
+fn rect_points(rect: Rect) -> [Pos2; 4] {
+    [
+        rect.left_top(),
+        rect.right_top(),
+        rect.right_bottom(),
+        rect.left_bottom(),
+    ]
+}
+
+/// 展開図の向きの矢印（SVGと同じく、上向きの矢印を向き×90度だけ時計回りに回す）
+fn draw_net_arrow(image: &mut Image, center: Pos2, length: f32, orientation: u8, width: f32) {
+    let wing = length * 0.4;
+    let wing_angle = 30.0_f32.to_radians();
+    let (sin, cos) = (f32::from(orientation % 4) * std::f32::consts::FRAC_PI_2).sin_cos();
+    // 画面のy軸は下向きなので、この回転は時計回り
+    let point = |x: f32, y: f32| center + Vec2::new(x * cos - y * sin, x * sin + y * cos);
+
+    let tip = point(0.0, -length);
+    let wing_y = -length + wing * wing_angle.cos();
+    image.stroke_segment(point(0.0, length * 0.3), tip, width, BLACK);
+    image.stroke_segment(point(-wing * wing_angle.sin(), wing_y), tip, width, BLACK);
+    image.stroke_segment(point(wing * wing_angle.sin(), wing_y), tip, width, BLACK);
+}
+
+/// アニメーションの1コマ
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    /// コマの画像
+    pub image: Image,
+    /// 次のコマまでの時間（秒）
+    pub delay: f32,
+}
+
+/// 回転のアニメーションの設定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackOptions {
+    /// 1秒あたりのコマ数
+    pub frame_rate: u32,
+    /// 1手の回転にかける時間（秒）
+    pub move_duration: f32,
+    /// 最初と最後の局面を止めて見せる時間（秒）
+    pub pause: f32,
+    /// 書き出したアニメーションを繰り返す回数（0なら無限、`write_apng` が使います）
+    pub loops: u32,
+}
+
+impl Default for PlaybackOptions {
+    fn default() -> Self {
+        Self {
+            frame_rate: 30,
+            move_duration: DEFAULT_ANIMATION_DURATION,
+            pause: 0.5,
+            loops: 0,
+        }
+    }
+}
+
+/// 開始局面から手順を回すアニメーションのコマを描きます。
+///
+/// 各手の回転はGUIと同じイージング（`geometry::ease_in_out`）で、
+/// `move_duration` 秒を `frame_rate` に従ってコマに分けます。最初と最後のコマは
+/// 回転していない局面で、`pause` 秒（1コマより短ければ1コマ分）止めます。
+#[must_use]
+pub fn render_playback(
+    start: &Cube,
+    moves: &[Move],
+    view: &View3D,
+    options: &RasterOptions,
+    playback: &PlaybackOptions,
+) -> Vec<Frame> {
+    let frame_rate = playback.frame_rate.max(1);
+    let frame_delay = 1.0 / frame_rate as f32;
+    let frames_per_move = ((playback.move_duration * frame_rate as f32).round() as usize).max(1);
+    let pause = playback.pause.max(frame_delay);
+
+    let mut cube = start.clone();
+    let mut frames = vec![Frame {
+        image: render_3d(&cube, view, options),
+        delay: pause,
+    }];
+    for (i, &mv) in moves.iter().enumerate() {
+        // 回転の途中のコマ（0と1は回転していない局面と同じ絵なので描かない）
+        for step in 1..frames_per_move {
+            let progress = geometry::ease_in_out(step as f32 / frames_per_move as f32);
+            frames.push(Frame {
+                image: render_3d_frame(&cube, Some((mv, progress)), view, options),
+                delay: frame_delay,
+            });
+        }
+        cube.apply_move(mv);
+        frames.push(Frame {
+            image: render_3d(&cube, view, options),
+            delay: if i + 1 == moves.len() {
+                pause
+            } else {
+                frame_delay
+            },
+        });
+    }
+    frames
+}
+
+/// コマをアニメーションPNG（APNG）として書き出します。
+///
+/// コマはすべて同じ大きさである必要があります（`render_playback` のコマは同じ大きさです）。
+/// 繰り返す回数は、コマを描いたときと同じ `playback` の `loops` を使います。
+///
+/// # Errors
+/// コマがない、コマの大きさがそろっていない、または書き込みに失敗した場合
+pub fn write_apng<W: Write>(frames: &[Frame], playback: &PlaybackOptions, writer: W) -> Result<()> {
+    let Some(first) = frames.first() else {
+        return Err(CubeError::InvalidFormat(
+            "アニメーションのコマがありません".to_string(),
+        ));
+    };
+    let (width, height) = (first.image.width, first.image.height);
+    if frames
+        .iter()
+        .any(|frame| (frame.image.width, frame.image.height) != (width, height))
+    {
+        return Err(CubeError::InvalidFormat(
+            "アニメーションのコマの大きさがそろっていません".to_string(),
+        ));
+    }
+
+    let mut encoder = png::Encoder::new(writer, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .set_animated(frames.len() as u32, playback.loops)
+        .map_err(io::Error::from)?;
+    let mut writer = encoder.write_header().map_err(io::Error::from)?;
+    for frame in frames {
+        // 遅延はミリ秒単位の分数で書く
+        let delay = (frame.delay * 1000.0)
+            .round()
+            .clamp(0.0, f32::from(u16::MAX)) as u16;
+        writer
+            .set_frame_delay(delay, 1000)
+            .map_err(io::Error::from)?;
+        writer
+            .write_image_data(&frame.image.pixels)
+            .map_err(io::Error::from)?;
+    }
+    writer.finish().map_err(io::Error::from)?;
+    Ok(())
+}
+
+/// コマをアニメーションPNG（APNG）のファイルに保存します。
+///
+/// # Errors
+/// `write_apng` と同じ場合と、ファイルを作成できない場合
+pub fn save_apng(
+    frames: &[Frame],
+    playback: &PlaybackOptions,
+    path: impl AsRef<Path>,
+) -> Result<()> {
+    write_apng(frames, playback, BufWriter::new(File::create(path)?))
+}
@@ -0,0 +1,281 @@
+use crate::algorithm::Algorithm;
+use crate::cube::symmetry::StickerMap;
+use crate::cube::{Color, Corner, Cube, Face, Move};
+use crate::solver::is_redundant_after;
+
+/// 探索の既定の最大手数
+pub const DEFAULT_MAX_LENGTH: usize = 8;
+
+/// 既定の最大件数
+pub const DEFAULT_MAX_RESULTS: usize = 100;
+
+/// 探索の目標（ステッカーごとに必要な色。`None` は任意）
+///
+/// 完成状態に手順を適用した局面の色が、指定したステッカーですべて一致すれば目標達成です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SearchTarget {
+    pattern: [Option<Color>; 24],
+}
+
+impl SearchTarget {
+    /// ステッカーごとの必要な色から目標を作成
+    #[must_use]
+    pub fn new(pattern: [Option<Color>; 24]) -> Self {
+        Self { pattern }
+    }
+
+    /// すべてのステッカーが任意の目標（条件を追加して使います）
+    #[must_use]
+    pub fn any() -> Self {
+        Self {
+            pattern: [None; 24],
+        }
+    }
+
+    /// 局面の色にすべてのステッカーが一致する目標
+    #[must_use]
+    pub fn from_cube(cube: &Cube) -> Self {
+        Self {
+            pattern: cube.stickers.map(|s| Some(s.color)),
+        }
+    }
+
+    /// コーナーが完成状態のまま（位置もねじれも）である条件を追加
+    #[must_use]
+    pub fn preserve_corner(mut self, corner: Corner) -> Self {
+        let solved = Cube::new();
+        for i in corner.stickers() {
+            self.pattern[i] = Some(solved.stickers[i].color);
+        }
+        self
+    }
+
+    /// 指定した面の層（4個のコーナー）が完成状態のままである条件を追加
+    #[must_use]
+    pub fn preserve_layer(self, face: Face) -> Self {
+        layer_corners(face).fold(self, |target, corner| target.preserve_corner(corner))
+    }
+
+    /// 2つのコーナーのピースが入れ替わる（ねじれなし）条件を追加
+    #[must_use]
+    pub fn swap_corners(mut self, a: Corner, b: Corner) -> Self {
+        let solved = Cube::new();
+        for (i, j) in a.stickers().into_iter().zip(b.stickers()) {
+            self.pattern[i] = Some(solved.stickers[j].color);
+            self.pattern[j] = Some(solved.stickers[i].color);
+        }
+        self
+    }
+
+    /// 指定したステッカーを任意にする
+    #[must_use]
+    pub fn ignore_stickers(mut self, indices: &[usize]) -> Self {
+        for &i in indices {
+            self.pattern[i] = None;
+        }
+        self
+    }
+
+    /// 指定したステッカーに必要な色
+    #[must_use]
+    pub fn required(&self, index: usize) -> Option<Color> {
+        self.pattern[index]
+    }
+
+    /// 局面が目標に一致するか
+    #[must_use]
+    pub fn matches(&self, cube: &Cube) -> bool {
+        self.pattern
+            .iter()
+            .zip(&cube.stickers)
+            .all(|(required, sticker)| required.is_none_or(|c| c == sticker.color))
+    }
+}
+
+/// 指定した面の層のコーナー
+fn layer_corners(face: Face) -> impl Iterator<Item = Corner> {
+    Corner::ALL
+        .into_iter()
+        .filter(move |c| c.stickers().iter().any(|&i| i / 4 == face.index()))
+}
+
+/// 探索の設定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// 使う回転操作
+    pub moves: Vec<Move>,
+    /// 最大手数
+    pub max_length: usize,
+    /// 最大件数（この件数に達した手数で打ち切ります）
+    pub max_results: usize,
+}
+
+impl SearchOptions {
+    /// 指定した面の回転（90度・180度・逆回転）をすべて使う設定
+    #[must_use]
+    pub fn with_faces(faces: &[Face]) -> Self {
+        Self {
+            moves: Move::all_moves()
+                .into_iter()
+                .filter(|mv| faces.contains(&mv.face()))
+                .collect(),
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            moves: Move::all_moves(),
+            max_length: DEFAULT_MAX_LENGTH,
+            max_results: DEFAULT_MAX_RESULTS,
+        }
+    }
+}
+
+/// 目標の効果を持つ手順を、手数の短い順にすべて探します。
+///
+/// 同じ面の連続や、向かい合う面の入れ替え（順序だけが違う手順）は除きます。
+/// 同じ手数の中では、回しやすさ（`ergonomic_cost`）の順に並べます。
+/// 何も回さない手順は含みません。
+#[must_use]
+pub fn search(target: &SearchTarget, options: &SearchOptions) -> Vec<Algorithm> {
+    let pruning = PruningTable::new(target, &options.moves);
+    let mut results = Vec::new();
+
+    for length in 1..=options.max_length {
+        let mut found = Vec::new();
+        let mut path = Vec::with_capacity(length);
+        search_depth(
+            &Cube::new(),
+            target,
+            options,
+            &pruning,
+            &mut path,
+            length,
+            &mut found,
+        );
+        found.sort_by_cached_key(|alg| (ergonomic_cost(alg), alg.to_string()));
+        results.extend(found);
+        if results.len() >= options.max_results {
+            break;
+        }
+    }
+    results.truncate(options.max_results);
+    results
+}
+
+/// 残り `remaining` 手でちょうど目標に一致する手順を探す
+fn search_depth(
+    cube: &Cube,
+    target: &SearchTarget,
+    options: &SearchOptions,
+    pruning: &PruningTable,
+    path: &mut Vec<Move>,
+    remaining: usize,
+    found: &mut Vec<Algorithm>,
+) {
+    if remaining == 0 {
+        if target.matches(cube) {
+            found.push(Algorithm::new(path.clone()));
+        }
+        return;
+    }
+    if pruning.lower_bound(cube) > remaining {
+        return;
+    }
+
+    for &mv in &options.moves {
+        if path
+            .last()
+            .is_some_and(|&last| is_redundant_after(last, mv))
+        {
+            continue;
+        }
+        let mut next = cube.clone();
+        next.apply_move(mv);
+        path.push(mv);
+        search_depth(&next, target, options, pruning, path, remaining - 1, found);
+        path.pop();
+    }
+}
+
+/// 枝刈り用の表（ステッカーがある位置から別の位置へ移るのに必要な最小手数）
+struct PruningTable {
+    /// `distance[from][to]`（到達できない場合は `usize::MAX`）
+    distance: [[usize; 24]; 24],
+    /// 色が指定されているステッカーと、その色
+    required: Vec<(usize, Color)>,
+}
+
+impl PruningTable {
+    fn new(target: &SearchTarget, moves: &[Move]) -> Self {
+        let maps: Vec<StickerMap> = moves.iter().map(|&mv| StickerMap::from_move(mv)).collect();
+
+        // 各位置から幅優先探索
+        let mut distance = [[usize::MAX; 24]; 24];
+        for (from, row) in distance.iter_mut().enumerate() {
+            row[from] = 0;
+            let mut frontier = vec![from];
+            let mut depth = 0;
+            while !frontier.is_empty() {
+                depth += 1;
+                let mut next = Vec::new();
+                for &pos in &frontier {
+                    for map in &maps {
+                        let dst = map.destination(pos);
+                        if row[dst] == usize::MAX {
+                            row[dst] = depth;
+                            next.push(dst);
+                        }
+                    }
+                }
+                frontier = next;
+            }
+        }
+
+        let required = (0..24)
+            .filter_map(|i| target.required(i).map(|c| (i, c)))
+            .collect();
+        Self { distance, required }
+    }
+
+    /// 目標に一致するまでに必要な手数の下限
+    ///
+    /// 指定された色のステッカーが、その位置へ移るのに必要な手数の最大値です。
+    fn lower_bound(&self, cube: &Cube) -> usize {
+        self.required
+            .iter()
+            .map(|&(to, color)| {
+                (0..24)
+                    .filter(|&from| cube.stickers[from].color == color)
+                    .map(|from| self.distance[from][to])
+                    .min()
+                    .unwrap_or(usize::MAX)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// 手順の回しにくさ（小さいほど回しやすい）
+///
+/// 面ごとの重み（R・U: 2、L・D: 3、F: 4、B: 5）の合計に、180度回転ごとに1を加えます。
+/// 持ち替えずに回せるR・U中心の手順ほど小さくなります。
+#[must_use]
+pub fn ergonomic_cost(algorithm: &Algorithm) -> u32 {
+    algorithm
+        .moves
+        .iter()
+        .map(|mv| {
+            let face = match mv.face() {
+                Face::Right | Face::Up => 2,
+                Face::Left | Face::Down => 3,
+                Face::Front => 4,
+                Face::Back => 5,
+            };
+            face + u32::from(mv.quarter_turns() == 2)
+        })
+        .sum()
+}
@@ -0,0 +1,120 @@
+//! `serde` 機能で有効になるシリアライズの実装
+//!
+//! 表現は保存したデータを後の版でも読めるように固定しています。
+//! 書き出した値はそのまま読み戻せます（`Cube` は色の数やピースを検証しないため、入力途中の局面も保存できます）。
+//!
+//! | 型 | 表現 | 例 |
+//! |----|------|----|
+//! | `Color` | 色の文字（未設定は `-`） | `"W"` |
+//! | `Move` | 回転記号 | `"R'"` |
+//! | `Algorithm` | 空白区切りの回転記号 | `"R U R' U'"` |
+//! | `Cube` | URFDLB の順のステッカーの色の文字列（未設定は `-`）と、同じ順の向き（0-3）の文字列 | `{"colors": "WWWWBBBB…", "orientations": "1203…"}` |
+//! | `Sticker` | 色と向き | `{"color": "W", "orientation": 1}` |
+//! | `Solution` | 回転記号の列と、解が見つかったか | `{"moves": ["R", "U'"], "found": true}` |
+//! | `History` | Undo用・Redo用のスタック（末尾が次に取り出す操作）と最大サイズ | `{"undo_stack": ["R"], "redo_stack": [], "max_size": 100}` |
+//! | `Statistics` | 各項目（時間は serde の `Duration`・`SystemTime` の表現） | |
+
+use crate::algorithm::Algorithm;
+use crate::cube::{Color, Cube, FaceletOrder, Move, Sticker};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.letter().encode_utf8(&mut [0; 4]))
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Color::from_letter(c),
+            _ => None,
+        }
+        .ok_or_else(|| D::Error::custom(format!("色の文字ではありません: {s}")))
+    }
+}
+
+impl Serialize for Move {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Move {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Algorithm {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Algorithm {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Algorithm::parse(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// `Cube` の表現
+#[derive(Serialize, Deserialize)]
+struct CubeRepr {
+    /// URFDLB の順のステッカーの色の文字（未設定は `-`）
+    colors: String,
+    /// `colors` と同じ順の向き（0-3）
+    orientations: String,
+}
+
+impl Serialize for Cube {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let indices = FaceletOrder::urfdlb().sticker_indices();
+        CubeRepr {
+            colors: indices
+                .iter()
+                .map(|&index| self.stickers[index].color.letter())
+                .collect(),
+            orientations: indices
+                .iter()
+                .map(|&index| char::from(b'0' + self.stickers[index].orientation % 4))
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cube {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = CubeRepr::deserialize(deserializer)?;
+
+        let colors: Vec<Color> = repr
+            .colors
+            .chars()
+            .map(Color::from_letter)
+            .collect::<Option<_>>()
+            .filter(|c: &Vec<Color>| c.len() == 24)
+            .ok_or_else(|| {
+                D::Error::custom("色は色の文字（未設定は -）24文字で指定してください")
+            })?;
+        let orientations: Vec<u8> = repr
+            .orientations
+            .chars()
+            .map(|c| c.to_digit(10).filter(|&d| d < 4).map(|d| d as u8))
+            .collect::<Option<_>>()
+            .filter(|o: &Vec<u8>| o.len() == 24)
+            .ok_or_else(|| D::Error::custom("向きは0-3の数字24文字で指定してください"))?;
+
+        let mut cube = Cube::new();
+        let indices = FaceletOrder::urfdlb().sticker_indices();
+        for ((&index, color), orientation) in indices.iter().zip(colors).zip(orientations) {
+            cube.stickers[index] = Sticker { color, orientation };
+        }
+        Ok(cube)
+    }
+}
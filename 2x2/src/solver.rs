@@ -1,4 +1,4 @@
-use crate::cube::{Cube, Move};
+use crate::cube::{Color, ColorScheme, Cube, Move, Symmetry};
 use rustc_hash::FxHashMap;
 use std::collections::VecDeque;
 use std::sync::mpsc::Sender;
@@ -16,11 +16,119 @@ type StateQueue = VecDeque<Cube>;
 
 /// ソルバーの結果
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Solution {
     pub moves: Vec<Move>,
     pub found: bool,
 }
 
+impl Solution {
+    /// 解法を適用したときに到達する完成状態の向き（解が見つからなかった場合は `None`）
+    #[must_use]
+    pub fn reached_orientation(&self, start_cube: &Cube) -> Option<CubeOrientation> {
+        if !self.found {
+            return None;
+        }
+        let mut cube = start_cube.clone();
+        for &mv in &self.moves {
+            cube.apply_move(mv);
+        }
+        CubeOrientation::of(&cube)
+    }
+}
+
+/// 完成状態のキューブ全体の向き（U面とF面の色、内部表現の標準配色）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CubeOrientation {
+    pub up: Color,
+    pub front: Color,
+}
+
+impl CubeOrientation {
+    /// 初期状態（`Cube::new`）の向き
+    pub const HOME: CubeOrientation = CubeOrientation {
+        up: Color::White,
+        front: Color::Red,
+    };
+
+    /// 完成しているキューブの向きを取得（未完成の場合は `None`）
+    #[must_use]
+    pub fn of(cube: &Cube) -> Option<Self> {
+        cube.is_solved().then(|| Self {
+            up: cube.get_sticker(0).color,
+            front: cube.get_sticker(16).color,
+        })
+    }
+
+    /// D面の色
+    #[must_use]
+    pub fn down(self) -> Color {
+        ColorScheme::standard()
+            .opposite(self.up)
+            .unwrap_or(Color::Gray)
+    }
+}
+
+impl std::fmt::Display for CubeOrientation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "U面: {}、F面: {}、D面: {}",
+            self.up.label(),
+            self.front.label(),
+            self.down().label()
+        )
+    }
+}
+
+/// 完成とみなすキューブ全体の向き
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TargetOrientation {
+    /// 24通りのどの向きでもよい（カラーニュートラル）
+    #[default]
+    Any,
+    /// 指定した向きに揃える
+    Fixed(CubeOrientation),
+    /// D面がいずれかの色になる向きに揃える
+    BottomColors(Vec<Color>),
+}
+
+impl TargetOrientation {
+    /// 完成状態のキューブがこの向きの条件を満たすか
+    #[must_use]
+    pub fn accepts(&self, solved: &Cube) -> bool {
+        let Some(orientation) = CubeOrientation::of(solved) else {
+            return false;
+        };
+        match self {
+            TargetOrientation::Any => true,
+            TargetOrientation::Fixed(target) => orientation == *target,
+            TargetOrientation::BottomColors(colors) => colors.contains(&orientation.down()),
+        }
+    }
+}
+
+/// 探索の設定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveOptions {
+    /// 最大探索深度
+    pub max_depth: usize,
+    /// `true` の場合、色のみを考慮（向きは無視）
+    pub ignore_orientation: bool,
+    /// 完成とみなすキューブ全体の向き
+    pub target: TargetOrientation,
+}
+
+impl Default for SolveOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_DEPTH,
+            ignore_orientation: false,
+            target: TargetOrientation::Any,
+        }
+    }
+}
+
 static SOLVED_STATES: OnceLock<Vec<Cube>> = OnceLock::new();
 
 /// 全24通りの向きの完成状態を取得（キャッシュ）
@@ -29,41 +137,11 @@ pub fn get_solved_states() -> &'static [Cube] {
 }
 
 fn generate_all_solved_states() -> Vec<Cube> {
+    // 完成状態を24通りに持ち替え、向きを時計回りパターンにそろえる
     let base = Cube::new();
-    let mut states = Vec::new();
-    let mut queue = VecDeque::new();
-    let mut visited = FxHashMap::default();
-
-    let base_norm = base.normalized();
-    queue.push_back(base.clone());
-    visited.insert(base_norm, ());
-    states.push(base);
-
-    let rotations = vec![
-        vec![Move::U, Move::Dp],
-        vec![Move::R, Move::Lp],
-        vec![Move::F, Move::Bp],
-    ];
-
-    while let Some(current) = queue.pop_front() {
-        for rot_moves in &rotations {
-            let mut next = current.clone();
-            for &mv in rot_moves {
-                next.apply_move(mv);
-            }
-
-            let next_norm = next.normalized();
-            if let std::collections::hash_map::Entry::Vacant(e) = visited.entry(next_norm) {
-                e.insert(());
-                states.push(next.clone());
-                queue.push_back(next);
-            }
-        }
-    }
-    // 全ての完成状態のorientationを時計回りパターンにリセット
-    states
+    Symmetry::rotations()
         .iter()
-        .map(|cube| cube.with_clockwise_orientations())
+        .map(|rotation| rotation.transform(&base).with_clockwise_orientations())
         .collect()
 }
 
@@ -130,7 +208,13 @@ pub fn solve_with_progress(
     ignore_orientation: bool,
     progress_tx: Option<Sender<f32>>,
 ) -> Solution {
-    solve_internal(start_cube, max_depth, ignore_orientation, progress_tx)
+    solve_internal(
+        start_cube,
+        max_depth,
+        ignore_orientation,
+        &TargetOrientation::Any,
+        progress_tx,
+    )
 }
 
 /// 双方向BFSを使用して最短解を探索します。
@@ -164,16 +248,60 @@ pub fn solve_with_progress(
 /// println!("解法: {} 手", solution.moves.len());
 /// ```
 pub fn solve(start_cube: &Cube, max_depth: usize, ignore_orientation: bool) -> Solution {
-    solve_internal(start_cube, max_depth, ignore_orientation, None)
+    solve_internal(
+        start_cube,
+        max_depth,
+        ignore_orientation,
+        &TargetOrientation::Any,
+        None,
+    )
+}
+
+/// 設定を指定して最短解を探索します。
+///
+/// `options.target` で完成とみなす向きを制限できます（例: D面を黄色にする）。
+/// 到達した向きは `Solution::reached_orientation` で確認できます。
+///
+/// # 例
+///
+/// ```
+/// use rubiks_cube_2x2::cube::{Color, Cube, Move};
+/// use rubiks_cube_2x2::solver::{solve_with_options, SolveOptions, TargetOrientation};
+///
+/// let mut cube = Cube::new();
+/// cube.apply_move(Move::R);
+///
+/// let options = SolveOptions {
+///     target: TargetOrientation::BottomColors(vec![Color::Yellow]),
+///     ..SolveOptions::default()
+/// };
+/// let solution = solve_with_options(&cube, &options, None);
+/// let reached = solution.reached_orientation(&cube).unwrap();
+/// assert_eq!(reached.down(), Color::Yellow);
+/// ```
+pub fn solve_with_options(
+    start_cube: &Cube,
+    options: &SolveOptions,
+    progress_tx: Option<Sender<f32>>,
+) -> Solution {
+    solve_internal(
+        start_cube,
+        options.max_depth,
+        options.ignore_orientation,
+        &options.target,
+        progress_tx,
+    )
 }
 
 fn solve_internal(
     start_cube: &Cube,
     max_depth: usize,
     ignore_orientation: bool,
+    target: &TargetOrientation,
     progress_tx: Option<Sender<f32>>,
 ) -> Solution {
-    println!(
+    // 一括解法のレポートを標準出力に書けるよう、ログとして出力する
+    tracing::debug!(
         "高速化{}BFS探索開始: 最大深度={}",
         if ignore_orientation {
             "(向き無視) "
@@ -183,10 +311,22 @@ fn solve_internal(
         max_depth
     );
 
+    // 条件を満たす完成状態
+    let goals: Vec<&Cube> = get_solved_states()
+        .iter()
+        .filter(|solved| target.accepts(solved))
+        .collect();
+    if goals.is_empty() {
+        return Solution {
+            moves: vec![],
+            found: false,
+        };
+    }
+
     let is_goal = if ignore_orientation {
-        start_cube.is_solved()
+        target.accepts(start_cube)
     } else {
-        is_fully_solved(start_cube)
+        goals.contains(&start_cube)
     };
 
     if is_goal {
@@ -238,9 +378,9 @@ fn solve_internal(
                 .expect("forward_queue should not be empty during BFS iteration");
 
             for &mv in &all_moves {
-                // 枝刈り：直前の逆操作を回避
-                if let Some(&(last_mv, ref parent)) = forward_dist.get(&curr) {
-                    if parent.is_some() && last_mv == mv.inverse() {
+                // 枝刈り：直前の手と合わせて短くなる・順序だけが違う手を回避
+                if let Some(&(last_mv, Some(_))) = forward_dist.get(&curr) {
+                    if is_redundant_after(last_mv, mv) {
                         continue;
                     }
                 }
@@ -272,7 +412,7 @@ fn solve_internal(
 
     // 向き無視の場合も向きも揃える場合も、24通りの完成状態すべてを使用
     // ただし、キーの取り方が異なる
-    for solved in get_solved_states() {
+    for solved in goals {
         let s_key = if ignore_orientation {
             solved.normalized()
         } else {
@@ -326,8 +466,8 @@ fn solve_internal(
             }
 
             for &mv in &all_moves {
-                if let Some(&(last_mv, ref parent)) = backward_map.get(&curr) {
-                    if parent.is_some() && last_mv == mv.inverse() {
+                if let Some(&(last_mv, Some(_))) = backward_map.get(&curr) {
+                    if is_redundant_after(last_mv, mv) {
                         continue;
                     }
                 }
@@ -361,6 +501,15 @@ fn solve_internal(
     }
 }
 
+/// 直前の手 `last` の後に `mv` を回すのが無駄か
+///
+/// 同じ面の連続は1手にまとめられ、向かい合う面は順序を入れ替えても同じなので一方の順序だけを残します。
+/// どちらを除いても、最短の手順は必ず残ります（`search::search` と共有）。
+pub(crate) fn is_redundant_after(last: Move, mv: Move) -> bool {
+    let (face, last_face) = (mv.face(), last.face());
+    face == last_face || (face == last_face.opposite() && face.index() < last_face.index())
+}
+
 fn reconstruct_path_forward(dist: &StateMap, target: &Cube) -> Vec<Move> {
     let mut path = Vec::new();
     let mut curr = target;
@@ -2,6 +2,7 @@ use std::time::{Duration, SystemTime};
 
 /// アプリケーションの統計情報
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Statistics {
     /// 総解法試行回数
     pub total_solves: usize,
@@ -0,0 +1,392 @@
+//! 展開図と手順図のSVG出力
+//!
+//! 展開図はGUIの展開図と同じ配置・大きさ・矢印（`geometry`）で描きます。
+//! 手順図は上の層を上から見た図で、手順が揃える局面の色と、手順でコーナーが
+//! どこへ動くかの矢印を描きます。練習用の資料をコードから作るために使います。
+
+use crate::algorithm::Algorithm;
+use crate::cube::{ColorScheme, Corner, Cube, Face};
+use crate::geometry::{
+    self, net_cell, net_face_origin, ARROW_SCALE, NET_COLUMNS, NET_ROWS, STICKER_SCALE,
+};
+
+/// 強調する面の枠の色
+const HIGHLIGHT_COLOR: &str = "#ff8c00";
+
+/// 上から見た図のU面のコーナーと、そのマス（列, 行）。U面のステッカーは `行 * 2 + 列` です。
+const TOP_CORNERS: [(Corner, (usize, usize)); 4] = [
+    (Corner::Ubl, (0, 0)),
+    (Corner::Ubr, (1, 0)),
+    (Corner::Ufl, (0, 1)),
+    (Corner::Ufr, (1, 1)),
+];
+/// U面の奥の辺に並ぶB面のステッカー（上から見て左から）
+const BACK_ROW: [usize; 2] = [21, 20];
+/// U面の手前の辺に並ぶF面のステッカー（左から）
+const FRONT_ROW: [usize; 2] = [16, 17];
+/// U面の左の辺に並ぶL面のステッカー（奥から）
+const LEFT_COLUMN: [usize; 2] = [8, 9];
+/// U面の右の辺に並ぶR面のステッカー（奥から）
+const RIGHT_COLUMN: [usize; 2] = [13, 12];
+
+/// 展開図のSVGの設定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NetSvgOptions {
+    /// 1マスの大きさ（px）
+    pub cell_size: f32,
+    /// 表示に使う配色
+    pub scheme: ColorScheme,
+    /// ステッカーの向きの矢印を描く
+    pub arrows: bool,
+    /// 枠で強調する面
+    pub highlight_face: Option<Face>,
+}
+
+impl Default for NetSvgOptions {
+    fn default() -> Self {
+        Self {
+            cell_size: 40.0,
+            scheme: ColorScheme::standard(),
+            arrows: true,
+            highlight_face: None,
+        }
+    }
+}
+
+/// 手順図のSVGの設定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LastLayerSvgOptions {
+    /// U面の1ステッカーの大きさ（px）
+    pub cell_size: f32,
+    /// 表示に使う配色
+    pub scheme: ColorScheme,
+    /// 手順でのコーナーの動きを矢印で描く
+    pub movement: bool,
+}
+
+impl Default for LastLayerSvgOptions {
+    fn default() -> Self {
+        Self {
+            cell_size: 40.0,
+            scheme: ColorScheme::standard(),
+            movement: true,
+        }
+    }
+}
+
+/// 局面の展開図をSVGとして書き出します。
+///
+/// 各ステッカーは `class="sticker"` と、ステッカーのインデックスの `data-index` を持つ矩形です。
+#[must_use]
+pub fn net_svg(cube: &Cube, options: &NetSvgOptions) -> String {
+    let cell = options.cell_size;
+    // 強調する面の枠が外にはみ出さないための余白
+    let margin = cell * 0.1;
+    let sticker = cell * STICKER_SCALE;
+    let mut svg = svg_open(
+        NET_COLUMNS as f32 * cell + 2.0 * margin,
+        NET_ROWS as f32 * cell + 2.0 * margin,
+    );
+
+    for index in 0..24 {
+        let (col, row) = net_cell(index);
+        let cx = margin + (col as f32 + 0.5) * cell;
+        let cy = margin + (row as f32 + 0.5) * cell;
+        let s = cube.get_sticker(index);
+        write_sticker(
+            &mut svg,
+            index,
+            (cx - sticker / 2.0, cy - sticker / 2.0),
+            (sticker, sticker),
+            options.scheme.display_rgb(s.color),
+        );
+        if options.arrows {
+            write_arrow(&mut svg, (cx, cy), cell * ARROW_SCALE, s.orientation);
+        }
+    }
+
+    if let Some(face) = options.highlight_face {
+        let (col, row) = net_face_origin(face);
+        let padding = cell * 0.05;
+        svg.push_str(&format!(
+            "<rect class=\"highlight\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"5\" \
+             fill=\"none\" stroke=\"{HIGHLIGHT_COLOR}\" stroke-width=\"4\"/>\n",
+            num(margin + col as f32 * cell - padding),
+            num(margin + row as f32 * cell - padding),
+            num(2.0 * cell + 2.0 * padding),
+            num(2.0 * cell + 2.0 * padding),
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// 手順の上の層の図をSVGとして書き出します。
+///
+/// 描く色は手順で揃う局面（完成状態に逆手順を回した局面）の色です。
+/// 矢印は手順を回したときに上の層のコーナーが移る先を示し、その場でねじれるだけの
+/// コーナーには回る向き（`↻` 時計回り・`↺` 反時計回り）を書きます。
+#[must_use]
+pub fn last_layer_svg(algorithm: &Algorithm, options: &LastLayerSvgOptions) -> String {
+    let size = last_layer_size(options.cell_size);
+    let mut svg = svg_open(size, size);
+    svg.push_str(&movement_marker(options.cell_size));
+    write_last_layer(&mut svg, algorithm, options);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// 手順の一覧表（題名と手順の付いた上の層の図を `columns` 列に並べたもの）をSVGとして書き出します。
+#[must_use]
+pub fn alg_sheet_svg(
+    entries: &[(&str, &Algorithm)],
+    columns: usize,
+    options: &LastLayerSvgOptions,
+) -> String {
+    let cell = options.cell_size;
+    let diagram = last_layer_size(cell);
+    let tile_width = diagram + cell;
+    let tile_height = diagram + cell * 1.1;
+    let columns = columns.clamp(1, entries.len().max(1));
+    let rows = entries.len().div_ceil(columns);
+
+    let mut svg = svg_open(tile_width * columns as f32, tile_height * rows as f32);
+    svg.push_str(&movement_marker(cell));
+    for (i, (title, algorithm)) in entries.iter().enumerate() {
+        svg.push_str(&format!(
+            "<g class=\"case\" transform=\"translate({} {})\">\n",
+            num((i % columns) as f32 * tile_width),
+            num((i / columns) as f32 * tile_height),
+        ));
+        svg.push_str(&format!(
+            "<g transform=\"translate({} 0)\">\n",
+            num(cell * 0.5)
+        ));
+        write_last_layer(&mut svg, algorithm, options);
+        svg.push_str("</g>\n");
+        for (text, y, font_size, weight) in [
+            (
+                title.to_string(),
+                diagram + cell * 0.45,
+                cell * 0.35,
+                "bold",
+            ),
+            (
+                algorithm.to_string(),
+                diagram + cell * 0.85,
+                cell * 0.3,
+                "normal",
+            ),
+        ] {
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" \
+                 font-weight=\"{weight}\" text-anchor=\"middle\">{}</text>\n",
+                num(tile_width / 2.0),
+                num(y),
+                num(font_size),
+                escape_xml(&text),
+            ));
+        }
+        svg.push_str("</g>\n");
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// 上の層の図の寸法（余白・周りのステッカー・隙間の順に外側から並ぶ）
+struct LastLayerGeometry {
+    margin: f32,
+    strip: f32,
+    gap: f32,
+    inset: f32,
+}
+
+impl LastLayerGeometry {
+    fn new(cell: f32) -> Self {
+        Self {
+            margin: cell * 0.1,
+            strip: cell * 0.35,
+            gap: cell * 0.08,
+            inset: cell * 0.04,
+        }
+    }
+
+    /// U面の左上の座標（x, y とも同じ）
+    fn origin(&self) -> f32 {
+        self.margin + self.strip + self.gap
+    }
+}
+
+/// 上の層の図の幅（高さも同じ）
+fn last_layer_size(cell: f32) -> f32 {
+    2.0 * LastLayerGeometry::new(cell).origin() + 2.0 * cell
+}
+
+/// 上の層の図を (0, 0) を左上として書く
+fn write_last_layer(svg: &mut String, algorithm: &Algorithm, options: &LastLayerSvgOptions) {
+    let cell = options.cell_size;
+    let g = LastLayerGeometry::new(cell);
+    let origin = g.origin();
+    let case = algorithm.inverse().to_cube();
+    let fill = |index: usize| options.scheme.display_rgb(case.get_sticker(index).color);
+    let long = cell - 2.0 * g.inset;
+
+    for row in 0..2 {
+        for col in 0..2 {
+            let index = row * 2 + col;
+            let position = (
+                origin + col as f32 * cell + g.inset,
+                origin + row as f32 * cell + g.inset,
+            );
+            write_sticker(svg, index, position, (long, long), fill(index));
+        }
+    }
+    let far = origin + 2.0 * cell + g.gap;
+    for i in 0..2 {
+        let along = origin + i as f32 * cell + g.inset;
+        for (index, position, size) in [
+            (BACK_ROW[i], (along, g.margin), (long, g.strip)),
+            (FRONT_ROW[i], (along, far), (long, g.strip)),
+            (LEFT_COLUMN[i], (g.margin, along), (g.strip, long)),
+            (RIGHT_COLUMN[i], (far, along), (g.strip, long)),
+        ] {
+            write_sticker(svg, index, position, size, fill(index));
+        }
+    }
+
+    if options.movement {
+        write_movement(svg, algorithm, cell, origin);
+    }
+}
+
+/// コーナーの動きの矢印とねじれの印を書く
+fn write_movement(svg: &mut String, algorithm: &Algorithm, cell: f32, origin: f32) {
+    let center = |corner: Corner| {
+        TOP_CORNERS
+            .iter()
+            .find(|(c, _)| *c == corner)
+            .map(|&(_, (col, row))| {
+                (
+                    origin + (col as f32 + 0.5) * cell,
+                    origin + (row as f32 + 0.5) * cell,
+                )
+            })
+    };
+
+    let analysis = algorithm.analyze();
+    for cycle in &analysis.cycles {
+        let n = cycle.corners.len();
+        // 2点の入れ替えは両端に矢じりのある1本の線にする
+        let lines = if n == 2 { 1 } else { n };
+        for i in 0..lines {
+            let (from, to) = (cycle.corners[i], cycle.corners[(i + 1) % n]);
+            let (Some(start), Some(end)) = (center(from), center(to)) else {
+                continue;
+            };
+            // 矢じりがステッカーの中心に重ならないよう、両端を短くする
+            let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+            let length = (dx * dx + dy * dy).sqrt();
+            let trim = cell * 0.2 / length;
+            svg.push_str(&format!(
+                "<line class=\"movement\" x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"black\" \
+                 stroke-width=\"{}\" marker-end=\"url(#movement-head)\"{}/>\n",
+                num(start.0 + dx * trim),
+                num(start.1 + dy * trim),
+                num(end.0 - dx * trim),
+                num(end.1 - dy * trim),
+                num(cell * 0.06),
+                if n == 2 {
+                    " marker-start=\"url(#movement-head)\""
+                } else {
+                    ""
+                },
+            ));
+        }
+    }
+    for twisted in &analysis.twisted {
+        let Some((x, y)) = center(twisted.corner) else {
+            continue;
+        };
+        svg.push_str(&format!(
+            "<text class=\"twist\" x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" \
+             text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+            num(x),
+            num(y),
+            num(cell * 0.5),
+            if twisted.twist == 1 { "↻" } else { "↺" },
+        ));
+    }
+}
+
+/// SVGの開始タグ
+fn svg_open(width: f32, height: f32) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"0 0 {w} {h}\">\n",
+        w = num(width),
+        h = num(height),
+    )
+}
+
+/// コーナーの動きの矢印の矢じり
+fn movement_marker(cell: f32) -> String {
+    format!(
+        "<defs><marker id=\"movement-head\" viewBox=\"0 0 10 10\" refX=\"8\" refY=\"5\" \
+         markerWidth=\"{size}\" markerHeight=\"{size}\" orient=\"auto-start-reverse\">\
+         <path d=\"M0,0 L10,5 L0,10 z\"/></marker></defs>\n",
+        size = num((cell * 0.1).max(3.0)),
+    )
+}
+
+/// ステッカーを書く
+fn write_sticker(
+    svg: &mut String,
+    index: usize,
+    (x, y): (f32, f32),
+    (width, height): (f32, f32),
+    [r, g, b]: [u8; 3],
+) {
+    svg.push_str(&format!(
+        "<rect class=\"sticker\" data-index=\"{index}\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" \
+         rx=\"3\" fill=\"#{r:02x}{g:02x}{b:02x}\" stroke=\"black\" stroke-width=\"2\"/>\n",
+        num(x),
+        num(y),
+        num(width),
+        num(height),
+    ));
+}
+
+/// 向きの矢印を書く（GUIと同じく、上向きの矢印を向き×90度だけ時計回りに回す）
+fn write_arrow(svg: &mut String, (cx, cy): (f32, f32), length: f32, orientation: u8) {
+    let wing = length * 0.4;
+    let wing_angle = 30.0_f32.to_radians();
+    let tip = cy - length;
+    let wing_y = tip + wing * wing_angle.cos();
+    svg.push_str(&format!(
+        "<path class=\"arrow\" d=\"M{cx},{base} L{cx},{tip} M{left},{wing_y} L{cx},{tip} L{right},{wing_y}\" \
+         fill=\"none\" stroke=\"black\" stroke-opacity=\"0.7\" stroke-width=\"2\" \
+         transform=\"rotate({angle} {cx} {cy})\"/>\n",
+        cx = num(cx),
+        cy = num(cy),
+        base = num(cy + length * 0.3),
+        tip = num(tip),
+        left = num(cx - wing * wing_angle.sin()),
+        right = num(cx + wing * wing_angle.sin()),
+        wing_y = num(wing_y),
+        angle = u32::from(orientation % 4) * 90,
+    ));
+}
+
+/// 座標の数値（小数第2位まで、末尾の0は省く）
+fn num(value: f32) -> String {
+    geometry::format_number(value, 2)
+}
+
+/// テキストに書く文字列のエスケープ
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,95 @@
+use rubiks_cube_2x2::alg_file::{AlgFile, AlgStep};
+use rubiks_cube_2x2::algorithm::Algorithm;
+use rubiks_cube_2x2::error::{CubeError, ParseError};
+
+fn alg(s: &str) -> Algorithm {
+    Algorithm::parse(s).unwrap()
+}
+
+#[test]
+fn test_parse_alg_file() {
+    let text = "# 練習用\n2x2alg v1\ntitle: Sune\nsetup: R U2 R' U' R U' R'\n\nmoves:\nR U R' U   # 右手で\r\nR U2 R'\n";
+    let file = AlgFile::parse(text).unwrap();
+    assert_eq!(file.title, "Sune");
+    assert_eq!(file.setup, Some(alg("R U2 R' U' R U' R'")));
+    assert_eq!(
+        file.steps,
+        [
+            AlgStep {
+                algorithm: alg("R U R' U"),
+                comment: Some("右手で".to_string()),
+            },
+            AlgStep {
+                algorithm: alg("R U2 R'"),
+                comment: None,
+            },
+        ]
+    );
+    assert_eq!(file.algorithm(), alg("R U R' U R U2 R'"));
+    assert_eq!(file.step_at(3).map(|(i, _)| i), Some(0));
+    assert_eq!(file.step_at(4).map(|(i, _)| i), Some(1));
+    assert_eq!(file.step_at(7), None);
+
+    // 開始局面から手順を回すと完成する
+    let mut cube = file.start_cube().unwrap();
+    file.algorithm().apply_to(&mut cube);
+    assert!(cube.is_solved());
+}
+
+#[test]
+fn test_alg_file_round_trip() {
+    let file = AlgFile::new("T-perm")
+        .with_step(alg("R U R' U'"), Some("セクシームーブ"))
+        .with_step(alg("R' F R2 U' R' U' R U R' F'"), None);
+    let text = file.to_file_format();
+    assert!(text.starts_with("2x2alg v1\ntitle: T-perm\nmoves:\n"));
+    assert_eq!(AlgFile::parse(&text).unwrap(), file);
+
+    // ファイルへの保存と読み込み
+    let path = std::env::temp_dir().join(format!("alg_file_test_{}.alg", std::process::id()));
+    file.save(&path).unwrap();
+    let loaded = AlgFile::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.unwrap(), file);
+}
+
+#[test]
+fn test_multi_line_title_and_comment_round_trip() {
+    // 題名やコメントの改行は空白にして書き、手順の行として読まれないようにする
+    let file = AlgFile::new("T-perm\nR U R'")
+        .with_step(alg("R U R' U'"), Some("右手で\r\nU'"))
+        .with_step(alg("R' F R2 U' R' U' R U R' F'"), None);
+    let text = file.to_file_format();
+    assert!(text.starts_with("2x2alg v1\ntitle: T-perm R U R'\nmoves:\n"));
+
+    let parsed = AlgFile::parse(&text).unwrap();
+    assert_eq!(parsed.title, "T-perm R U R'");
+    assert_eq!(parsed.steps.len(), 2);
+    assert_eq!(parsed.steps[0].comment.as_deref(), Some("右手で  U'"));
+    assert_eq!(parsed.algorithm(), file.algorithm());
+}
+
+#[test]
+fn test_header_is_optional() {
+    let file = AlgFile::parse("moves: R U R' U'\n").unwrap();
+    assert_eq!(file.title, "");
+    assert_eq!(file.setup, None);
+    assert_eq!(file.algorithm(), alg("R U R' U'"));
+}
+
+#[test]
+fn test_alg_file_errors() {
+    let location = |text: &str| match AlgFile::parse(text) {
+        Err(CubeError::Parse(ParseError { line, column, .. })) => (line, column),
+        other => panic!("位置つきのエラーになりません: {other:?}"),
+    };
+    // 読めない回転記号はその記号の位置
+    assert_eq!(location("moves:\nR U X2 R'\n"), (2, 5));
+    assert_eq!(location("setup: R Q\nmoves:\n"), (1, 10));
+    // 未知のキー
+    assert_eq!(location("title: a\n  author: b\nmoves:\n"), (2, 3));
+    // `moves:` がない
+    assert_eq!(location("title: a\n"), (2, 1));
+    // 未対応のバージョン
+    assert_eq!(location("2x2alg v9\nmoves:\n"), (1, 1));
+}
@@ -0,0 +1,256 @@
+use rubiks_cube_2x2::algorithm::{Algorithm, EquivalenceOptions, MirrorPlane, Rotation};
+use rubiks_cube_2x2::cube::{Corner, Cube, Face, Move};
+
+#[test]
+fn test_parse_and_display() {
+    let algorithm = Algorithm::parse("R U R' U'").unwrap();
+    assert_eq!(algorithm.moves, vec![Move::R, Move::U, Move::Rp, Move::Up]);
+    assert_eq!(algorithm.to_string(), "R U R' U'");
+
+    let compact: Algorithm = "(RUR'U')R2’ F2'".parse().unwrap();
+    assert_eq!(compact.len(), 6);
+    assert_eq!(compact.moves[4], Move::R2);
+    assert_eq!(compact.moves[5], Move::F2);
+
+    assert!(Algorithm::parse("R x").is_err());
+    assert!(Algorithm::parse("").unwrap().is_empty());
+
+    // 記号は空白で終わるので、離れた `'`・`2` は前の記号に付かない
+    assert!(Algorithm::parse("R '").is_err());
+    assert!(Algorithm::parse("R 2").is_err());
+    assert!(Algorithm::parse("U R\t'").is_err());
+    assert!(Algorithm::parse("(R U)'").is_err());
+}
+
+#[test]
+fn test_order() {
+    assert_eq!(Algorithm::parse("").unwrap().order(), 1);
+    assert_eq!(Algorithm::parse("R").unwrap().order(), 4);
+    assert_eq!(Algorithm::parse("R2").unwrap().order(), 2);
+    assert_eq!(Algorithm::parse("R U R' U'").unwrap().order(), 6);
+
+    // 周期だけ繰り返すと完成状態に戻り、それより前には戻らない
+    let ru = Algorithm::parse("R U").unwrap();
+    let order = ru.order();
+    let mut cube = Cube::new();
+    for n in 1..=order {
+        ru.apply_to(&mut cube);
+        assert_eq!(cube.is_solved(), n == order);
+    }
+}
+
+#[test]
+fn test_single_move_is_four_cycle() {
+    let analysis = Algorithm::parse("U").unwrap().analyze();
+    assert_eq!(analysis.cycles.len(), 1);
+    assert_eq!(analysis.cycles[0].corners.len(), 4);
+    assert_eq!(analysis.cycles[0].twist, 0);
+    assert!(analysis.twisted.is_empty());
+    // U面の4枚と側面の8枚
+    assert_eq!(analysis.stickers.len(), 12);
+}
+
+#[test]
+fn test_three_cycle_with_twists() {
+    // Niklas: U面のコーナー3点巡回
+    let analysis = Algorithm::parse("R U' L' U R' U' L U").unwrap().analyze();
+    assert_eq!(analysis.order, 3);
+    assert_eq!(analysis.cycles.len(), 1);
+    assert_eq!(analysis.cycles[0].corners.len(), 3);
+    assert!(analysis.describe().starts_with("3点巡回 "));
+
+    // Sune: 3コーナーがねじれ、2点の入れ替えが2組
+    let sune = Algorithm::parse("R U R' U R U2 R'").unwrap().analyze();
+    assert_eq!(sune.stickers.len() % 3, 0);
+    assert!(sune.cycles.iter().all(|c| c.corners.len() >= 2));
+}
+
+#[test]
+fn test_twisted_corners_only() {
+    // 2つのコーナーだけをねじる手順
+    let algorithm = Algorithm::parse(
+        "(R' D' R D)(R' D' R D) U (R' D' R D)(R' D' R D)(R' D' R D)(R' D' R D) U'",
+    )
+    .unwrap();
+    let analysis = algorithm.analyze();
+    assert!(analysis.cycles.is_empty());
+    assert_eq!(analysis.twisted.len(), 2);
+    assert_eq!(analysis.stickers.len(), 6);
+    assert!(analysis.twisted.iter().any(|t| t.corner == Corner::Ufr));
+    assert_eq!(analysis.order, 3);
+    assert!(analysis.describe().contains("2個のコーナーがねじれ"));
+}
+
+#[test]
+fn test_identity_algorithm() {
+    let analysis = Algorithm::parse("R R'").unwrap().analyze();
+    assert_eq!(analysis.order, 1);
+    assert!(analysis.stickers.is_empty());
+    assert_eq!(analysis.describe(), "コーナーは動きません");
+}
+
+const SUNE: &str = "R U R' U R U2 R'";
+
+fn alg(s: &str) -> Algorithm {
+    Algorithm::parse(s).unwrap()
+}
+
+#[test]
+fn test_equivalent_identical_effect() {
+    let options = EquivalenceOptions::default();
+    let equivalence = alg("R2 U2")
+        .equivalent(&alg("R R U' U'"), &options)
+        .unwrap();
+    assert!(equivalence.is_identical());
+    assert!(alg("R U").equivalent(&alg("U R"), &options).is_none());
+}
+
+#[test]
+fn test_equivalent_up_to_auf() {
+    let shifted = alg(&format!("U {SUNE} U'"));
+    assert!(alg(SUNE)
+        .equivalent(&shifted, &EquivalenceOptions::default())
+        .is_none());
+
+    let options = EquivalenceOptions {
+        auf: true,
+        ..EquivalenceOptions::default()
+    };
+    let equivalence = alg(SUNE).equivalent(&shifted, &options).unwrap();
+    assert_eq!(equivalence.pre_auf, Some(Move::Up));
+    assert_eq!(equivalence.post_auf, Some(Move::U));
+    assert!(equivalence.describe().contains("前にU'"));
+}
+
+#[test]
+fn test_equivalent_up_to_rotation() {
+    let options = EquivalenceOptions {
+        rotation: true,
+        ..EquivalenceOptions::default()
+    };
+    // y2 で持ち替えた手順
+    let equivalence = alg("R U R'").equivalent(&alg("L U L'"), &options).unwrap();
+    assert!(equivalence.symmetry.has_rotation());
+    assert!(!equivalence.symmetry.is_mirror());
+    // 2x2では R と L は持ち替えを除いて同じ局面になる
+    assert!(alg("R").equivalent(&alg("L"), &options).is_some());
+    assert!(alg("R")
+        .equivalent(&alg("L"), &EquivalenceOptions::default())
+        .is_none());
+}
+
+#[test]
+fn test_equivalent_mirror_and_inverse() {
+    let mirror = EquivalenceOptions {
+        mirror: true,
+        ..EquivalenceOptions::default()
+    };
+    let equivalence = alg(SUNE)
+        .equivalent(&alg("L' U' L U' L' U2 L"), &mirror)
+        .unwrap();
+    assert!(equivalence.symmetry.is_mirror());
+    assert!(!equivalence.symmetry.has_rotation());
+
+    let antisune = alg("R U2 R' U' R U' R'");
+    assert!(alg(SUNE).equivalent(&antisune, &mirror).is_none());
+    let inverse = EquivalenceOptions {
+        inverse: true,
+        ..EquivalenceOptions::default()
+    };
+    let equivalence = alg(SUNE).equivalent(&antisune, &inverse).unwrap();
+    assert!(equivalence.inverse);
+    assert!(equivalence.describe().contains("逆手順"));
+}
+
+#[test]
+fn test_not_equivalent_even_with_all_options() {
+    assert!(alg(SUNE)
+        .equivalent(&alg("R U R' U'"), &EquivalenceOptions::all())
+        .is_none());
+}
+
+const SAMPLES: [&str; 4] = [SUNE, "R U' F2 D B' L", "F R U R' U' F'", "L2 D' B R2 U"];
+
+#[test]
+fn test_inverse_undoes_effect() {
+    for sample in SAMPLES {
+        let algorithm = alg(sample);
+        let inverse = algorithm.inverse();
+        assert_eq!(inverse.to_cube(), algorithm.to_cube().inverse().unwrap());
+        assert_eq!(inverse.inverse(), algorithm);
+    }
+    assert_eq!(alg("R U2 F'").inverse().to_string(), "F U2 R'");
+}
+
+#[test]
+fn test_mirror() {
+    assert_eq!(alg("R U R'").mirror(MirrorPlane::M).to_string(), "L' U' L");
+    assert_eq!(alg("F R2 U").mirror(MirrorPlane::S).to_string(), "B' R2 U'");
+    assert_eq!(alg("U R D'").mirror(MirrorPlane::E).to_string(), "D' R' U");
+
+    for plane in MirrorPlane::ALL {
+        let symmetry = plane.symmetry();
+        assert!(symmetry.is_mirror());
+        for sample in SAMPLES {
+            let algorithm = alg(sample);
+            // 鏡映した手順の効果は、元の効果を鏡映したもの
+            assert_eq!(
+                algorithm.mirror(plane).to_cube().normalized(),
+                symmetry.conjugate(&algorithm.to_cube())
+            );
+            assert_eq!(algorithm.mirror(plane).mirror(plane), algorithm);
+        }
+    }
+}
+
+#[test]
+fn test_rotated() {
+    assert_eq!(alg("R U R'").rotated(Rotation::Y).to_string(), "F U F'");
+    assert_eq!(alg("R U R'").rotated(Rotation::X).to_string(), "R B R'");
+    assert_eq!(alg("R U R'").rotated(Rotation::Z2).to_string(), "L D L'");
+
+    for rotation in Rotation::ALL {
+        let symmetry = rotation.symmetry();
+        assert!(!symmetry.is_mirror());
+        for sample in SAMPLES {
+            let algorithm = alg(sample);
+            assert_eq!(
+                algorithm.rotated(rotation).to_cube().normalized(),
+                symmetry.conjugate(&algorithm.to_cube())
+            );
+        }
+    }
+    // 逆向きの持ち替えで元に戻る
+    let algorithm = alg(SUNE);
+    assert_eq!(
+        algorithm.rotated(Rotation::Y).rotated(Rotation::Yp),
+        algorithm
+    );
+    assert_eq!(
+        algorithm.rotated(Rotation::X2),
+        algorithm.rotated(Rotation::X).rotated(Rotation::X)
+    );
+}
+
+#[test]
+fn test_relabel() {
+    let map = Rotation::Y.face_map();
+    for sample in SAMPLES {
+        assert_eq!(
+            alg(sample).relabel(&map).unwrap(),
+            alg(sample).rotated(Rotation::Y)
+        );
+    }
+
+    // U面とR面だけを入れ替える（回転ではない置き換え）
+    let swap = [
+        Face::Right,
+        Face::Down,
+        Face::Left,
+        Face::Up,
+        Face::Front,
+        Face::Back,
+    ];
+    assert_eq!(alg("R U R'").relabel(&swap).unwrap().to_string(), "U R U'");
+    assert!(alg("R").relabel(&[Face::Up; 6]).is_err());
+}
@@ -0,0 +1,134 @@
+use rubiks_cube_2x2::batch::{self, BatchOptions, ReportFormat};
+use rubiks_cube_2x2::cube::{Cube, Move};
+
+fn options(jobs: usize) -> BatchOptions {
+    BatchOptions {
+        max_depth: 4,
+        jobs,
+        ..BatchOptions::default()
+    }
+}
+
+fn multi_position_file() -> String {
+    let r = Cube::from_moves(&[Move::R]).to_file_format();
+    let ru = Cube::from_moves(&[Move::R, Move::U]).to_file_format();
+    format!("--- R\n{r}--- 壊れた局面\n     WWWW\n--- \n{ru}")
+}
+
+#[test]
+fn test_parse_multi_position_file() {
+    let inputs = batch::parse_positions("set.txt", &multi_position_file());
+    let names: Vec<&str> = inputs.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(names, ["set.txt#R", "set.txt#壊れた局面", "set.txt#3"]);
+    assert!(inputs[0].cube.is_ok());
+    assert!(inputs[1].cube.is_err());
+    assert!(inputs[2].cube.is_ok());
+
+    // 区切りのないファイルはファイル名のまま1局面
+    let single = batch::parse_positions("solved.txt", &Cube::new().to_file_format());
+    assert_eq!(single.len(), 1);
+    assert_eq!(single[0].name, "solved.txt");
+}
+
+#[test]
+fn test_batch_reports_invalid_positions_without_aborting() {
+    let mut inputs = batch::parse_positions("set.txt", &multi_position_file());
+    inputs.extend(batch::read_positions(std::path::Path::new(
+        "cubes/does_not_exist.txt",
+    )));
+
+    let results = batch::solve_batch(&inputs, &options(1));
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0].solution.as_ref().map(|s| s.len()), Some(1));
+    assert!(!results[1].valid && results[1].error.is_some());
+    assert_eq!(results[2].solution.as_ref().map(|s| s.len()), Some(2));
+    assert!(!results[3].valid);
+
+    // 並列でも同じ順に同じ結果
+    let parallel = batch::solve_batch(&inputs, &options(3));
+    let summary = |results: &[batch::BatchResult]| -> Vec<(String, bool, Option<usize>)> {
+        results
+            .iter()
+            .map(|r| {
+                (
+                    r.name.clone(),
+                    r.valid,
+                    r.solution.as_ref().map(|s| s.len()),
+                )
+            })
+            .collect()
+    };
+    assert_eq!(summary(&parallel), summary(&results));
+}
+
+#[test]
+fn test_write_report() {
+    let inputs = batch::parse_positions("a,b.txt", &Cube::from_moves(&[Move::R]).to_file_format());
+    let results = batch::solve_batch(&inputs, &options(1));
+
+    let mut csv = Vec::new();
+    batch::write_report(&results, ReportFormat::Csv, &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "file,valid,length,solution,seconds,error");
+    assert!(
+        lines[1].starts_with("\"a,b.txt\",true,1,R',"),
+        "{}",
+        lines[1]
+    );
+
+    let mut tsv = Vec::new();
+    batch::write_report(&results, ReportFormat::Tsv, &mut tsv).unwrap();
+    let tsv = String::from_utf8(tsv).unwrap();
+    assert!(tsv
+        .lines()
+        .nth(1)
+        .unwrap()
+        .starts_with("a,b.txt\ttrue\t1\tR'\t"));
+
+    assert_eq!("TSV".parse::<ReportFormat>().unwrap(), ReportFormat::Tsv);
+    assert_eq!(
+        ReportFormat::from_path(std::path::Path::new("report.tsv")),
+        ReportFormat::Tsv
+    );
+}
+
+#[test]
+fn test_batch_solves_color_only_positions_with_orientation() {
+    // v1形式（色だけ）の局面は向きを復元してから解くので、向きも揃えて解ける
+    let scrambles = [
+        [Move::F2, Move::Up, Move::Bp, Move::Rp, Move::L],
+        [Move::B, Move::Rp, Move::D, Move::L, Move::Fp],
+        [Move::Dp, Move::Rp, Move::Rp, Move::F2, Move::R],
+    ];
+    let text: String = scrambles
+        .iter()
+        .enumerate()
+        .map(|(i, moves)| format!("--- {i}\n{}", Cube::from_moves(moves).to_file_format()))
+        .collect();
+    let inputs = batch::parse_positions("set.txt", &text);
+
+    let options = BatchOptions {
+        max_depth: 5,
+        ignore_orientation: false,
+        jobs: 1,
+    };
+    for result in batch::solve_batch(&inputs, &options) {
+        assert!(result.valid, "{}", result.name);
+        assert!(result.solution.is_some(), "{}", result.name);
+    }
+}
+
+#[test]
+fn test_color_only_positions_are_restored_when_solving() {
+    // 向きの復元は探索を使うので、読み込み時ではなく向きも揃えて解くときに行う
+    let v1 = Cube::from_moves(&[Move::R, Move::U]).to_file_format();
+    let v2 = rubiks_cube_2x2::cube::CubeFile::new(Cube::from_moves(&[Move::R])).to_file_format();
+    let inputs = batch::parse_positions("set.txt", &format!("--- v1\n{v1}--- v2\n{v2}"));
+    assert!(inputs[0].restore_orientations);
+    assert!(!inputs[1].restore_orientations);
+    assert_eq!(
+        inputs[0].cube.as_ref().unwrap(),
+        &Cube::from_moves(&[Move::R, Move::U]).with_clockwise_orientations()
+    );
+}
@@ -0,0 +1,272 @@
+use rubiks_cube_2x2::algorithm::Algorithm;
+use rubiks_cube_2x2::cases::{self, CaseSet};
+use rubiks_cube_2x2::cube::symmetry::Symmetry;
+use rubiks_cube_2x2::cube::{Corner, Cube, Face};
+
+/// 手順を逆に適用して、その手順で揃う局面を作る
+fn case_state(alg: &str, extra: &str) -> Cube {
+    let mut cube = Algorithm::parse(alg).unwrap().inverse().to_cube();
+    Algorithm::parse(extra).unwrap().apply_to(&mut cube);
+    cube
+}
+
+fn solves(cube: &Cube, alg: &Algorithm) -> bool {
+    let mut cube = cube.clone();
+    alg.apply_to(&mut cube);
+    cube.is_solved()
+}
+
+#[test]
+fn test_case_table_sizes() {
+    let count = |set| cases::cases().iter().filter(|c| c.set == set).count();
+    assert_eq!(count(CaseSet::Cll), 42);
+    assert_eq!(count(CaseSet::Eg1), 43);
+    assert_eq!(count(CaseSet::Eg2), 43);
+    assert_eq!(count(CaseSet::OrtegaOll), 7);
+    assert_eq!(count(CaseSet::OrtegaPbl), 8);
+}
+
+#[test]
+fn test_every_case_is_recognized() {
+    for case in cases::cases() {
+        let cube = case.algorithm.inverse().to_cube();
+        let recognized = cases::recognize(&cube);
+        let found = recognized
+            .iter()
+            .find(|r| r.case.set == case.set)
+            .unwrap_or_else(|| panic!("{} {} を認識できません", case.set, case.name));
+        assert_eq!(found.case.name, case.name, "{}", case.algorithm);
+        assert_eq!(found.top, Face::Up);
+
+        if case.set == CaseSet::OrtegaOll {
+            // D面を保ったまま、U面の色だけが揃う
+            let mut oriented = cube.clone();
+            found.solution().apply_to(&mut oriented);
+            let top = oriented.get_sticker(0).color;
+            assert!((0..4).all(|i| oriented.get_sticker(i).color == top));
+            assert!((4..8).all(|i| oriented.get_sticker(i).color == cube.get_sticker(4).color));
+        } else {
+            assert!(solves(&cube, &found.solution()), "{}", found.describe());
+        }
+    }
+}
+
+/// 層の4つのスロットを、上から見て左前・右前・右奥・左奥の順に並べたもの
+const U_LAYER: [Corner; 4] = [Corner::Ufl, Corner::Ufr, Corner::Ubr, Corner::Ubl];
+const D_LAYER: [Corner; 4] = [Corner::Dfl, Corner::Dfr, Corner::Dbr, Corner::Dbl];
+
+/// 層の入れ替わり方（PBL のケース名と同じ `-`・`Adj`・`Diag`）
+///
+/// 層を回して揃うなら `-`、並びが逆回りなら対角の入れ替わり、それ以外は隣り合う2個の入れ替わりです。
+fn layer_swap(cube: &Cube, layer: [Corner; 4]) -> &'static str {
+    let state = cube.corner_state().unwrap();
+    let order: Vec<usize> = layer
+        .iter()
+        .map(|slot| {
+            let piece = state.pieces[slot.index()];
+            layer
+                .iter()
+                .position(|&c| c == piece)
+                .unwrap_or_else(|| panic!("{piece:?} が別の層にあります"))
+        })
+        .collect();
+    let steps: Vec<usize> = (0..4)
+        .map(|i| (order[(i + 1) % 4] + 4 - order[i]) % 4)
+        .collect();
+    if steps.iter().all(|&d| d == 1) {
+        "-"
+    } else if steps.iter().all(|&d| d == 3) {
+        "Diag"
+    } else {
+        "Adj"
+    }
+}
+
+/// 上の層の色の見え方（U面にあるU面の色の数と、側面の上の段にあるU面の色の数 `[L, R, F, B]`）
+fn top_pattern(cube: &Cube) -> (usize, [usize; 4]) {
+    let top = Cube::new().get_sticker(0).color;
+    let count = |indices: &[usize]| {
+        indices
+            .iter()
+            .filter(|&&i| cube.get_sticker(i).color == top)
+            .count()
+    };
+    (
+        count(&[0, 1, 2, 3]),
+        [
+            count(&[8, 9]),
+            count(&[12, 13]),
+            count(&[16, 17]),
+            count(&[20, 21]),
+        ],
+    )
+}
+
+/// ケース名の上の層の形（`Sune 3` なら `Sune`）が、上の層の色の見え方と合っているか
+fn matches_top_shape(shape: &str, cube: &Cube) -> bool {
+    let (on_top, [l, r, f, b]) = top_pattern(cube);
+    let mut sides = [l, r, f, b];
+    sides.sort_unstable();
+    match shape {
+        "Solved" | "Adj" | "Diag" => on_top == 4,
+        // 1個だけ向きが揃い、残りの3個のU面の色は別々の側面を向く
+        "Sune" | "Antisune" => on_top == 1 && sides == [0, 1, 1, 1],
+        // U面の色が向かい合う2つの側面に2個ずつ
+        "H" => on_top == 0 && (l == 2 && r == 2 || f == 2 && b == 2),
+        // U面の色が1つの側面に2個、その両隣に1個ずつ
+        "Pi" => on_top == 0 && sides == [0, 1, 1, 2],
+        // 隣り合う2個の向きが揃い、残りの2個のU面の色は同じ側面を向く
+        "U" => on_top == 2 && sides == [0, 0, 0, 2],
+        // 隣り合う2個の向きが揃い、残りの2個のU面の色は向かい合う側面を向く
+        "T" => on_top == 2 && (l == 1 && r == 1 || f == 1 && b == 1),
+        // 対角の2個の向きが揃い、残りの2個のU面の色は隣り合う側面を向く
+        "L" => on_top == 2 && sides == [0, 0, 1, 1] && !(l == r || f == b),
+        _ => panic!("知らない形 {shape}"),
+    }
+}
+
+#[test]
+fn test_case_states_match_known_patterns() {
+    // 認識の処理とは別に、ケースの定義（名前の形・下の層の入れ替わり方）と局面を照らし合わせる
+    let bottom = Cube::new().get_sticker(4).color;
+    let mut sune_twist = None;
+    for case in cases::cases() {
+        let cube = case.algorithm.inverse().to_cube();
+        let label = format!("{} {}: {}", case.set, case.name, case.algorithm);
+
+        if case.set == CaseSet::OrtegaPbl {
+            // PBL は「上の層/下の層」の入れ替わり方の名前で、両方の面はそろっている
+            let expected = format!(
+                "{}/{}",
+                layer_swap(&cube, U_LAYER),
+                layer_swap(&cube, D_LAYER)
+            );
+            assert_eq!(case.name, expected, "{label}");
+            assert_eq!(top_pattern(&cube).0, 4, "{label}");
+            continue;
+        }
+
+        // 下の面の色はそろっている
+        assert!(
+            (4..8).all(|i| cube.get_sticker(i).color == bottom),
+            "{label}"
+        );
+        let expected_bottom = match case.set {
+            CaseSet::Cll => Some("-"),
+            CaseSet::Eg1 => Some("Adj"),
+            CaseSet::Eg2 => Some("Diag"),
+            _ => None,
+        };
+        if let Some(expected) = expected_bottom {
+            assert_eq!(layer_swap(&cube, D_LAYER), expected, "{label}");
+        }
+
+        let shape = case.name.split(' ').next().unwrap();
+        assert!(
+            matches_top_shape(shape, &cube),
+            "{label}: {:?}",
+            top_pattern(&cube)
+        );
+
+        // Sune と Antisune では、向きの揃っていない3個が逆向きにねじれている
+        if shape == "Sune" || shape == "Antisune" {
+            let state = cube.corner_state().unwrap();
+            let twists: Vec<u8> = U_LAYER
+                .iter()
+                .map(|slot| state.twists[slot.index()])
+                .filter(|&t| t != 0)
+                .collect();
+            assert!(twists.iter().all(|&t| t == twists[0]), "{label}");
+            let twist = if shape == "Sune" {
+                twists[0]
+            } else {
+                3 - twists[0]
+            };
+            assert_eq!(*sune_twist.get_or_insert(twist), twist, "{label}");
+        }
+    }
+
+    // 名前の付いたケースの上の層の見え方（U面の色の数と、側面 [L, R, F, B] の上の段の数）
+    let state_of = |set: CaseSet, name: &str| {
+        let case = cases::cases()
+            .iter()
+            .find(|c| c.set == set && c.name == name)
+            .unwrap();
+        case.algorithm.inverse().to_cube()
+    };
+    assert_eq!(top_pattern(&state_of(CaseSet::Cll, "Sune 1")).0, 1);
+    assert_eq!(top_pattern(&state_of(CaseSet::Cll, "H 1")).0, 0);
+    assert_eq!(layer_swap(&state_of(CaseSet::Cll, "Adj"), U_LAYER), "Adj");
+    assert_eq!(layer_swap(&state_of(CaseSet::Cll, "Diag"), U_LAYER), "Diag");
+    assert_eq!(layer_swap(&state_of(CaseSet::Eg1, "Solved"), U_LAYER), "-");
+}
+
+#[test]
+fn test_recognize_with_auf() {
+    let cube = case_state("R U R' U R U2 R'", "U");
+    let recognized = cases::recognize(&cube);
+    assert_eq!(recognized[0].describe(), "CLL Sune 1, AUF U'");
+    assert!(solves(&cube, &recognized[0].solution()));
+    // 下の層が完成していれば EG にはならない
+    assert!(recognized
+        .iter()
+        .all(|r| !matches!(r.case.set, CaseSet::Eg1 | CaseSet::Eg2)));
+}
+
+#[test]
+fn test_recognize_any_hold_and_color_scheme() {
+    let case = cases::cases()
+        .iter()
+        .find(|c| c.set == CaseSet::Eg1 && c.name == "Pi 1")
+        .unwrap();
+    let cube = case_state(&case.algorithm.to_string(), "U2");
+
+    for hold in Symmetry::rotations() {
+        let held = hold.transform(&cube);
+        let recognized = cases::recognize(&held);
+        let eg1 = recognized
+            .iter()
+            .find(|r| r.case.set == CaseSet::Eg1)
+            .expect("EG-1 として認識できる");
+        assert_eq!(eg1.case.name, "Pi 1");
+        assert!(solves(&held, &eg1.solution()), "{}", eg1.describe());
+    }
+
+    // 上下を逆に持つと、手順はD面を上にして読み替えられる
+    let upside_down = Symmetry::rotations()
+        .iter()
+        .find(|s| s.face_map()[Face::Up.index()] == Face::Down)
+        .unwrap()
+        .transform(&cube);
+    let recognized = cases::recognize(&upside_down);
+    let eg1 = recognized
+        .iter()
+        .find(|r| r.case.set == CaseSet::Eg1)
+        .unwrap();
+    assert_eq!(eg1.top, Face::Down);
+}
+
+#[test]
+fn test_recognize_ortega() {
+    // 下の面はそろっているが対角の2個が入れ替わった Sune（EG-2 でもある）
+    let cube = case_state("R U R' U R U2 R'", "R2 F2 R2");
+    let recognized = cases::recognize(&cube);
+    let sets: Vec<CaseSet> = recognized.iter().map(|r| r.case.set).collect();
+    assert_eq!(sets, vec![CaseSet::Eg2, CaseSet::OrtegaOll]);
+    assert_eq!(recognized[1].case.name, "Sune");
+    assert!(recognized[0].case.name.starts_with("Sune"));
+
+    let cube = Algorithm::parse("R2 F2 R2").unwrap().to_cube();
+    let pbl = cases::recognize(&cube)
+        .into_iter()
+        .find(|r| r.case.set == CaseSet::OrtegaPbl)
+        .unwrap();
+    assert_eq!(pbl.case.name, "Diag/Diag");
+    assert!(solves(&cube, &pbl.solution()));
+}
+
+#[test]
+fn test_solved_cube_has_no_case() {
+    assert!(cases::recognize(&Cube::new()).is_empty());
+    assert!(cases::recognize(&Algorithm::parse("U").unwrap().to_cube()).is_empty());
+}
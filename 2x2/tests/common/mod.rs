@@ -0,0 +1,17 @@
+//! 複数の統合テストで使う共通の補助
+#![allow(dead_code)]
+
+use rubiks_cube_2x2::cube::{Color, Cube, Move};
+
+/// テストで使う崩した局面の手順
+pub const SCRAMBLE: [Move; 6] = [Move::R, Move::U, Move::Fp, Move::D, Move::L2, Move::B];
+
+/// `SCRAMBLE` を完成状態に適用した局面
+pub fn scrambled() -> Cube {
+    Cube::from_moves(&SCRAMBLE)
+}
+
+/// 局面のステッカーの色
+pub fn colors_of(cube: &Cube) -> [Color; 24] {
+    std::array::from_fn(|i| cube.get_sticker(i).color)
+}
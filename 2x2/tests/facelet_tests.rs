@@ -0,0 +1,124 @@
+mod common;
+
+use common::scrambled;
+use rubiks_cube_2x2::cube::{ColorScheme, Cube, Face, FaceletOrder, Move, ReadingDirection};
+use rubiks_cube_2x2::error::{CubeError, ParseError};
+
+#[test]
+fn test_solved_and_one_move_facelets() {
+    let order = FaceletOrder::default();
+    assert_eq!(Cube::new().to_facelets(&order), "UUUURRRRFFFFDDDDLLLLBBBB");
+    // 一般的なツールと同じ並び（U面はB面を上、D面はF面を上、B面は後ろから見る）
+    assert_eq!(
+        Cube::from_moves(&[Move::R]).to_facelets(&order),
+        "UFUFRRRRFDFDDBDBLLLLUBUB"
+    );
+}
+
+#[test]
+fn test_facelets_round_trip() {
+    let cube = scrambled();
+    let orders = [
+        FaceletOrder::default(),
+        FaceletOrder::from_face_letters("ULFRBD").unwrap(),
+        FaceletOrder::default()
+            .with_reading(Face::Down, ReadingDirection::Clockwise)
+            .with_reading(Face::Back, ReadingDirection::Columns),
+    ];
+    for order in orders {
+        let facelets = cube.to_facelets(&order);
+        let loaded = Cube::from_facelets(&facelets, &order).unwrap();
+        assert_eq!(
+            loaded.normalized(),
+            cube.normalized(),
+            "{}",
+            order.face_letters()
+        );
+    }
+}
+
+#[test]
+fn test_reading_direction() {
+    // D面だけ時計回りに読むと、右下と左下が入れ替わる
+    let cube = Cube::from_moves(&[Move::R]);
+    let rows = cube.to_facelets(&FaceletOrder::default());
+    let clockwise = cube.to_facelets(
+        &FaceletOrder::default().with_reading(Face::Down, ReadingDirection::Clockwise),
+    );
+    assert_eq!(&rows[12..16], "DBDB");
+    assert_eq!(&clockwise[12..16], "DBBD");
+}
+
+#[test]
+fn test_color_letters() {
+    let cube = Cube::from_moves(&[Move::R]);
+    // 標準配色: U=白 R=青 F=赤 D=黄 L=緑 B=橙
+    let loaded = Cube::from_facelets("wrwr bbbb ryry yoyo gggg wowo", &FaceletOrder::default());
+    assert_eq!(loaded.unwrap().normalized(), cube.normalized());
+
+    // 配色を指定すると、その配色の色として読む
+    let japanese = ColorScheme::japanese();
+    let colors: String = "WRWRYYYYRBRBBOBOGGGGWOWO".to_string();
+    let loaded =
+        Cube::from_facelets_with_scheme(&colors, &FaceletOrder::default(), &japanese).unwrap();
+    assert_eq!(loaded.normalized(), cube.normalized());
+}
+
+#[test]
+fn test_invalid_facelets() {
+    let order = FaceletOrder::default();
+    assert!(Cube::from_facelets("UUUU", &order).is_err());
+    assert!(Cube::from_facelets("UUUURRRRFFFFDDDDLLLLBBBX", &order).is_err());
+    // 面の文字と色の文字が混ざっている
+    assert!(Cube::from_facelets("UUUURRRRFFFFDDDDLLLLWWWW", &order).is_err());
+    // 色の数が合わない
+    assert!(Cube::from_facelets("UUUUURRRFFFFDDDDLLLLBBBB", &order).is_err());
+
+    assert!(FaceletOrder::from_face_letters("URFDL").is_err());
+    assert!(FaceletOrder::from_face_letters("URFDLU").is_err());
+    assert!(FaceletOrder::from_face_letters("URFDLX").is_err());
+}
+
+#[test]
+fn test_invalid_facelet_char_location() {
+    let order = FaceletOrder::default();
+    let error = |text: &str| match Cube::from_facelets(text, &order) {
+        Err(CubeError::Parse(ParseError {
+            line,
+            column,
+            found,
+            ..
+        })) => (line, column, found),
+        other => panic!("位置つきのエラーになりません: {other:?}"),
+    };
+    // 空白を含めた元の文字列での位置
+    assert_eq!(
+        error("UUUURRRRFFFFDDDDLLLLBBBX"),
+        (1, 24, "`X`".to_string())
+    );
+    assert_eq!(
+        error("UUUU RRRR FFFF\nDDDD LxLL BBBB"),
+        (2, 7, "`x`".to_string())
+    );
+    // 色の文字で書いた場合も同じ
+    assert_eq!(
+        error("WWWW BBBB RRRR YYYY GGGG OO?O"),
+        (1, 28, "`?`".to_string())
+    );
+}
+
+#[test]
+fn test_shared_color_letter_table() {
+    use rubiks_cube_2x2::cube::Color;
+
+    // 展開図のファイルとフェイスレットで共通の色の文字
+    for color in Color::ALL.into_iter().chain([Color::Gray]) {
+        assert_eq!(Color::from_letter(color.letter()), Some(color));
+        assert_eq!(
+            Color::from_letter(color.letter().to_ascii_lowercase()),
+            Some(color)
+        );
+    }
+    assert_eq!(Color::Gray.letter(), '-');
+    assert_eq!(Color::from_letter('X'), None);
+}
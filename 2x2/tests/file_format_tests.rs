@@ -172,3 +172,174 @@ fn test_to_file_format_structure() {
     assert!(lines[2].starts_with("     "));
     assert_eq!(lines[2].trim().len(), 4);
 }
+
+#[test]
+fn test_v2_round_trip_keeps_orientation_and_metadata() {
+    use rubiks_cube_2x2::cube::{CubeFile, Move};
+
+    let cube = Cube::from_moves(&[Move::R, Move::U, Move::Fp, Move::D]);
+    let mut file = CubeFile::new(cube.clone());
+    file.comments.push("練習用".to_string());
+    file.set_metadata("scramble", "R U F' D");
+    file.set_metadata("author", "katoy");
+    file.set_metadata("date", "2026-01-11");
+
+    let text = file.to_file_format();
+    assert!(text.starts_with("2x2cube v2\n# 練習用\n"));
+    assert!(text.contains("\norientation:\n"));
+
+    let loaded = CubeFile::parse(&text).unwrap();
+    assert_eq!(loaded, file);
+    // 向きも含めて同じ局面になる
+    assert_eq!(loaded.cube, cube);
+    assert_eq!(Cube::from_file_format(&text).unwrap(), cube);
+    assert_eq!(loaded.metadata("scramble"), Some("R U F' D"));
+    assert_eq!(loaded.metadata("unknown"), None);
+}
+
+#[test]
+fn test_v2_optional_sections() {
+    use rubiks_cube_2x2::cube::CubeFile;
+
+    let text = "# 先頭のコメント\n\n2x2cube v2\n     WWWW\n# 途中のコメント\nGGGG RRRR BBBB OOOO\n     YYYY\n\nauthor: katoy\n";
+    let file = CubeFile::parse(text).unwrap();
+    assert!(!file.has_orientations);
+    assert_eq!(file.comments, ["先頭のコメント", "途中のコメント"]);
+    assert_eq!(file.metadata("author"), Some("katoy"));
+    assert!(file.cube.is_solved());
+}
+
+#[test]
+fn test_v1_files_still_load() {
+    use rubiks_cube_2x2::cube::CubeFile;
+
+    for entry in std::fs::read_dir("cubes").unwrap() {
+        let path = entry.unwrap().path();
+        let content = std::fs::read_to_string(&path).unwrap();
+        let file = CubeFile::parse(&content).unwrap();
+        assert!(!file.has_orientations, "{}", path.display());
+        assert!(file.comments.is_empty() && file.metadata.is_empty());
+        assert_eq!(file.cube, Cube::from_file_format(&content).unwrap());
+    }
+}
+
+#[test]
+fn test_v2_errors() {
+    let net = "     WWWW\nGGGG RRRR BBBB OOOO\n     YYYY\n";
+    // 未対応のバージョン
+    assert!(Cube::from_file_format(&format!("2x2cube v9\n{net}")).is_err());
+    // 展開図が足りない
+    assert!(Cube::from_file_format("2x2cube v2\n     WWWW\n").is_err());
+    // 向きは0-3
+    let orientation = "orientation:\n     1204\n1203 1203 1203 1203\n     1203\n";
+    assert!(Cube::from_file_format(&format!("2x2cube v2\n{net}{orientation}")).is_err());
+    // メタデータは `キー: 値`
+    assert!(Cube::from_file_format(&format!("2x2cube v2\n{net}author katoy\n")).is_err());
+}
+
+#[test]
+fn test_parse_error_location() {
+    use rubiks_cube_2x2::error::{CubeError, ParseError};
+
+    // 2行目の6文字目（空白を含めて数える）に色でない文字
+    let err = Cube::from_file_format("     WWWW\nGGGG RXRR BBBB OOOO\n     YYYY\n").unwrap_err();
+    let CubeError::Parse(ref parse) = err else {
+        panic!("位置つきのエラーになりません: {err:?}");
+    };
+    assert_eq!(
+        *parse,
+        ParseError {
+            line: 2,
+            column: 7,
+            expected: "色の文字（W/Y/G/B/R/O）".to_string(),
+            found: "`X`".to_string(),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "ファイルの解析エラー: 2行7列目: 色の文字（W/Y/G/B/R/O）が必要ですが、`X`があります"
+    );
+
+    // 文字が足りない行は行末の次の列
+    let err = Cube::from_file_format("     WWW\nGGGG RRRR BBBB OOOO\n     YYYY\n").unwrap_err();
+    assert!(matches!(
+        err,
+        CubeError::Parse(ParseError {
+            line: 1,
+            column: 9,
+            ..
+        })
+    ));
+
+    // 行が足りないときはファイルの終わり
+    let err = Cube::from_file_format("     WWWW\nGGGG RRRR BBBB OOOO\n").unwrap_err();
+    assert!(
+        matches!(err, CubeError::Parse(ParseError { line: 3, ref found, .. }) if found == "ファイルの終わり")
+    );
+
+    // v2の向きの誤りは向きのブロック内の位置
+    let text = "2x2cube v2\n     WWWW\nGGGG RRRR BBBB OOOO\n     YYYY\norientation:\n     1204\n";
+    let err = Cube::from_file_format(text).unwrap_err();
+    assert!(matches!(
+        err,
+        CubeError::Parse(ParseError {
+            line: 6,
+            column: 9,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_lenient_whitespace_and_line_endings() {
+    let cube = Cube::from_moves(&[rubiks_cube_2x2::cube::Move::R]);
+    let text = cube.to_file_format();
+
+    let crlf = text.replace('\n', "  \r\n");
+    assert_eq!(
+        Cube::from_file_format(&crlf).unwrap().normalized(),
+        cube.normalized()
+    );
+
+    let tabs = text.replace("     ", "\t").replace(' ', "\t");
+    assert_eq!(
+        Cube::from_file_format(&tabs).unwrap().normalized(),
+        cube.normalized()
+    );
+}
+
+#[test]
+fn test_t_shape_layout() {
+    use rubiks_cube_2x2::cube::{CubeFile, Move, NetLayout};
+
+    let cube = Cube::from_moves(&[Move::R, Move::U, Move::Fp]);
+    let mut file = CubeFile::new(cube.clone());
+    file.layout = NetLayout::TShape;
+    let text = file.to_file_format();
+    assert!(text.contains("orientation:"));
+
+    let loaded = CubeFile::parse(&text).unwrap();
+    assert_eq!(loaded.layout, NetLayout::TShape);
+    assert_eq!(loaded.cube, cube);
+
+    // B面は十字形の並びを180度回したもの
+    let cross = cube.to_file_format();
+    let back: String = cross
+        .lines()
+        .nth(1)
+        .unwrap()
+        .split_whitespace()
+        .nth(3)
+        .unwrap()
+        .chars()
+        .rev()
+        .collect();
+    let t_rows: Vec<&str> = text.lines().skip(1).take(4).collect();
+    assert_eq!(t_rows[3].trim(), back);
+
+    // v1形式（先頭行なし）でもT字形を判別する
+    let v1: String = t_rows.iter().map(|row| format!("{row}\n")).collect();
+    let loaded = CubeFile::parse(&v1).unwrap();
+    assert_eq!(loaded.layout, NetLayout::TShape);
+    assert_eq!(loaded.cube.normalized(), cube.normalized());
+}
@@ -131,3 +131,76 @@ fn test_known_difficult_pattern() {
     println!("6 Spot パターンの解法手数: {} 手", solution.moves.len());
     println!("解法: {:?}", solution.moves);
 }
+
+#[test]
+fn test_solution_lengths_match_breadth_first_distances() {
+    // 枝刈りなしの幅優先探索で求めた距離と、ソルバーの最短手数が一致する
+    // （DBLを動かさない R・U・F の9種類の手で、各深さの局面数は既知の値）
+    use std::collections::hash_map::{Entry, HashMap};
+
+    let moves = [
+        Move::R,
+        Move::Rp,
+        Move::R2,
+        Move::U,
+        Move::Up,
+        Move::U2,
+        Move::F,
+        Move::Fp,
+        Move::F2,
+    ];
+    let key =
+        |cube: &Cube| -> [u8; 24] { std::array::from_fn(|i| cube.get_sticker(i).color as u8) };
+
+    let mut seen: HashMap<[u8; 24], usize> = HashMap::from([(key(&Cube::new()), 0)]);
+    let mut layer = vec![Cube::new()];
+    let mut counts = vec![1];
+    for depth in 1..=5 {
+        let mut next = Vec::new();
+        for cube in &layer {
+            for &mv in &moves {
+                let mut moved = cube.clone();
+                moved.apply_move(mv);
+                if let Entry::Vacant(entry) = seen.entry(key(&moved)) {
+                    entry.insert(depth);
+                    next.push(moved);
+                }
+            }
+        }
+        counts.push(next.len());
+
+        // 浅い局面はすべて、深い局面は一部を解いて比べる
+        let step = if depth <= 3 { 1 } else { 97 };
+        for cube in next.iter().step_by(step) {
+            let solution = solver::solve(cube, depth, true);
+            assert!(solution.found, "{}", cube.to_file_format());
+            assert_eq!(solution.moves.len(), depth, "{}", cube.to_file_format());
+        }
+        layer = next;
+    }
+    assert_eq!(counts, [1, 9, 54, 321, 1847, 9992]);
+}
+
+#[test]
+fn test_antipode_needs_eleven_moves() {
+    // 幅優先探索で11手目に初めて現れた局面（最も遠い局面の1つ）
+    let scramble = [
+        Move::R,
+        Move::U,
+        Move::R,
+        Move::U,
+        Move::R,
+        Move::F2,
+        Move::R,
+        Move::Fp,
+        Move::R,
+        Move::F2,
+        Move::U2,
+    ];
+    let cube = Cube::from_moves(&scramble);
+
+    assert!(!solver::solve(&cube, 10, true).found);
+    let solution = solver::solve(&cube, solver::DEFAULT_MAX_DEPTH, true);
+    assert!(solution.found);
+    assert_eq!(solution.moves.len(), 11);
+}
@@ -0,0 +1,121 @@
+use rubiks_cube_2x2::algorithm::Algorithm;
+use rubiks_cube_2x2::cube::{Color, Cube};
+use rubiks_cube_2x2::method::{self, Method};
+
+const SCRAMBLES: [&str; 6] = [
+    "R U F' D L2 B",
+    "F2 U' R2 B D' L U2",
+    "R U R' U R U2 R'",
+    "L' D2 B U F' R2 D",
+    "U2 F R' D' B2 L U' R",
+    "B L' U2 R D F2",
+];
+
+fn solves(cube: &Cube, alg: &Algorithm) -> bool {
+    let mut cube = cube.clone();
+    alg.apply_to(&mut cube);
+    cube.is_solved()
+}
+
+#[test]
+fn test_every_method_solves_scrambles() {
+    for scramble in SCRAMBLES {
+        let cube = Algorithm::parse(scramble).unwrap().to_cube();
+        for method in Method::ALL {
+            let solution = method::solve(&cube, method).unwrap();
+            assert!(
+                solves(&cube, &solution.algorithm()),
+                "{method}: {scramble} → {}",
+                solution.describe()
+            );
+            assert!(solution.steps.iter().all(|step| !step.algorithm.is_empty()));
+        }
+    }
+}
+
+#[test]
+fn test_ortega_steps() {
+    let cube = Algorithm::parse("F2 U' R2 B D' L U2").unwrap().to_cube();
+    let solution = method::solve(&cube, Method::Ortega).unwrap();
+    let labels: Vec<&str> = solution.steps.iter().map(|s| s.label.as_str()).collect();
+    assert_eq!(labels[0], "1面");
+    assert!(labels[1..]
+        .iter()
+        .all(|l| l.starts_with("OLL ") || l.starts_with("PBL ") || *l == "AUF"));
+
+    // 1ステップ目でいずれかの面の色がそろう
+    let mut built = cube.clone();
+    solution.steps[0].algorithm.apply_to(&mut built);
+    assert!((0..6).any(|face| {
+        (0..4).all(|i| built.get_sticker(face * 4 + i).color == built.get_sticker(face * 4).color)
+    }));
+    // 1面は最大でも6手で作れる
+    assert!(solution.steps[0].algorithm.len() <= 6);
+}
+
+#[test]
+fn test_cll_and_eg_labels() {
+    // 1層がそろった Sune は CLL の1ステップで揃う
+    let cube = Algorithm::parse("R U2 R' U' R U' R'").unwrap().to_cube();
+    let cll = method::solve(&cube, Method::Cll).unwrap();
+    assert_eq!(cll.steps.len(), 1);
+    assert!(cll.steps[0].label.starts_with("CLL Sune"));
+    assert!(cll.describe().starts_with("ステップ1: CLL Sune"));
+
+    let cube = Algorithm::parse("L' D2 B U F' R2 D").unwrap().to_cube();
+    let eg = method::solve(&cube, Method::Eg).unwrap();
+    assert_eq!(eg.steps[0].label, "1面");
+    assert!(["CLL ", "EG-1 ", "EG-2 "]
+        .iter()
+        .any(|set| eg.steps[1].label.starts_with(set)));
+}
+
+#[test]
+fn test_solved_cube_has_no_steps() {
+    let solution = method::solve(&Cube::new(), Method::Ortega).unwrap();
+    assert!(solution.steps.is_empty());
+}
+
+#[test]
+fn test_invalid_cube_is_rejected() {
+    let mut cube = Cube::new();
+    cube.set_sticker_color(0, Color::Yellow);
+    assert!(method::solve(&cube, Method::Cll).is_err());
+}
+
+#[test]
+fn test_beginner_steps_have_explanations() {
+    let cube = Algorithm::parse("U2 F R' D' B2 L U' R").unwrap().to_cube();
+    let solution = method::solve(&cube, Method::Beginner).unwrap();
+    assert!(solves(&cube, &solution.algorithm()));
+    assert!(solution.steps.iter().all(|s| !s.explanation.is_empty()));
+
+    // ステップは 1層目 → 上の面の色 → 上の層の並び → 仕上げ の順
+    let stage = |label: &str| {
+        ["1層目", "上の面の色", "上の層の並び", "仕上げ"]
+            .iter()
+            .position(|prefix| label.starts_with(prefix))
+            .unwrap()
+    };
+    let stages: Vec<usize> = solution.steps.iter().map(|s| stage(&s.label)).collect();
+    assert!(stages.windows(2).all(|w| w[0] <= w[1]));
+    assert!(stages.iter().filter(|&&s| s == 0).count() <= 4);
+
+    // 1層目のステップの後は、白がD面にそろう
+    let mut first_layer = cube.clone();
+    for step in solution.steps.iter().filter(|s| stage(&s.label) == 0) {
+        step.algorithm.apply_to(&mut first_layer);
+    }
+    assert!((4..8).all(|i| first_layer.get_sticker(i).color == Color::White));
+}
+
+#[test]
+fn test_step_at() {
+    let cube = Algorithm::parse("F2 U' R2 B D' L U2").unwrap().to_cube();
+    let solution = method::solve(&cube, Method::Ortega).unwrap();
+    let first = solution.steps[0].algorithm.len();
+    assert_eq!(solution.step_at(0).unwrap().0, 0);
+    assert_eq!(solution.step_at(first - 1).unwrap().0, 0);
+    assert_eq!(solution.step_at(first).unwrap().0, 1);
+    assert!(solution.step_at(solution.algorithm().len()).is_none());
+}
@@ -0,0 +1,147 @@
+use rubiks_cube_2x2::algorithm::Algorithm;
+use rubiks_cube_2x2::cube::{Corner, Cube};
+use rubiks_cube_2x2::model::{self, ModelOptions};
+
+/// OBJの頂点・法線・面（頂点番号と法線番号、1始まり）
+struct Mesh {
+    vertices: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    faces: Vec<(Vec<usize>, usize)>,
+}
+
+fn parse_obj(obj: &str) -> Mesh {
+    let mut mesh = Mesh {
+        vertices: Vec::new(),
+        normals: Vec::new(),
+        faces: Vec::new(),
+    };
+    let triple = |rest: &str| -> [f32; 3] {
+        let values: Vec<f32> = rest.split(' ').map(|v| v.parse().unwrap()).collect();
+        values.try_into().unwrap()
+    };
+    for line in obj.lines() {
+        if let Some(rest) = line.strip_prefix("v ") {
+            mesh.vertices.push(triple(rest));
+        } else if let Some(rest) = line.strip_prefix("vn ") {
+            mesh.normals.push(triple(rest));
+        } else if let Some(rest) = line.strip_prefix("f ") {
+            let refs: Vec<(usize, usize)> = rest
+                .split(' ')
+                .map(|r| {
+                    let (v, n) = r.split_once("//").unwrap();
+                    (v.parse().unwrap(), n.parse().unwrap())
+                })
+                .collect();
+            mesh.faces
+                .push((refs.iter().map(|r| r.0).collect(), refs[0].1));
+        }
+    }
+    mesh
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+#[test]
+fn test_obj_model_structure() {
+    let model = model::obj_model(&Cube::new(), &ModelOptions::default(), "cube.mtl");
+    assert!(model.obj.contains("\nmtllib cube.mtl\n"));
+    assert_eq!(model.obj.matches("\no cubie_").count(), 8);
+    assert!(model.obj.contains("\no cubie_UFR\n"));
+
+    // キューブごとに本体の6面とステッカー3枚
+    let mesh = parse_obj(&model.obj);
+    assert_eq!(mesh.faces.len(), 8 * (6 + 3));
+    assert_eq!(mesh.vertices.len(), 8 * (6 + 3) * 4);
+    assert!(mesh.faces.iter().all(
+        |(v, n)| v.iter().all(|&i| i >= 1 && i <= mesh.vertices.len())
+            && *n >= 1
+            && *n <= mesh.normals.len()
+    ));
+
+    // すべての面は法線の側から見て反時計回り
+    for (indices, normal) in &mesh.faces {
+        let [a, b, c] = [0, 1, 2].map(|i| mesh.vertices[indices[i] - 1]);
+        let (ab, ac) = (sub(b, a), sub(c, a));
+        let cross = [
+            ab[1] * ac[2] - ab[2] * ac[1],
+            ab[2] * ac[0] - ab[0] * ac[2],
+            ab[0] * ac[1] - ab[1] * ac[0],
+        ];
+        let n = mesh.normals[normal - 1];
+        assert!(cross[0] * n[0] + cross[1] * n[1] + cross[2] * n[2] > 0.0);
+    }
+
+    // マテリアルは本体と6色だけ（矢印はなし）
+    assert_eq!(model.mtl.matches("newmtl ").count(), 7);
+    for name in ["body", "white", "yellow", "green", "blue", "red", "orange"] {
+        assert!(model.mtl.contains(&format!("newmtl {name}\n")), "{name}");
+    }
+    assert!(model.mtl.contains("newmtl white\nKd 1 1 1\n"));
+    assert!(!model.obj.contains("usemtl arrow"));
+}
+
+#[test]
+fn test_obj_model_follows_state() {
+    let cube = Algorithm::parse("R U").unwrap().to_cube();
+    let model = model::obj_model(&cube, &ModelOptions::default(), "cube.mtl");
+
+    // 各キューブのステッカーのマテリアルは、`Corner::stickers` の順の局面の色
+    for corner in Corner::ALL {
+        let start = model.obj.find(&format!("o cubie_{corner}\n")).unwrap();
+        let section = &model.obj[start..];
+        let end = section[1..].find("\no ").map_or(section.len(), |i| i + 1);
+        let materials: Vec<&str> = section[..end]
+            .lines()
+            .filter_map(|line| line.strip_prefix("usemtl "))
+            .collect();
+        let expected: Vec<String> = corner
+            .stickers()
+            .iter()
+            .map(|&i| format!("{:?}", cube.get_sticker(i).color).to_lowercase())
+            .collect();
+        assert_eq!(materials[0], "body");
+        assert_eq!(materials[1..], expected);
+    }
+}
+
+#[test]
+fn test_obj_model_arrows_and_size() {
+    let options = ModelOptions {
+        size: 50.0,
+        arrows: true,
+        ..ModelOptions::default()
+    };
+    let model = model::obj_model(&Cube::new(), &options, "cube.mtl");
+    let mesh = parse_obj(&model.obj);
+    // 矢印はステッカーごとに軸と先端の2面
+    assert_eq!(mesh.faces.len(), 8 * (6 + 3) + 24 * 2);
+    assert_eq!(model.obj.matches("usemtl arrow").count(), 24);
+    assert!(model.mtl.contains("newmtl arrow\nKd 0 0 0\n"));
+
+    // 1辺50の大きさ（ステッカーと矢印は面から少し浮いている）
+    let extent = mesh
+        .vertices
+        .iter()
+        .flatten()
+        .fold(0.0_f32, |max, v| max.max(v.abs()));
+    assert!(extent > 25.0 && extent < 25.5, "{extent}");
+}
+
+#[test]
+fn test_save_obj_writes_mtl() {
+    let dir = std::env::temp_dir();
+    let path = dir.join(format!("model_test_{}.obj", std::process::id()));
+    let mtl_path = path.with_extension("mtl");
+    let result = model::save_obj(&Cube::new(), &ModelOptions::default(), &path);
+    let obj = std::fs::read_to_string(&path);
+    let mtl = std::fs::read_to_string(&mtl_path);
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(&mtl_path);
+    result.unwrap();
+
+    let mtl_name = mtl_path.file_name().unwrap().to_string_lossy();
+    assert!(obj.unwrap().contains(&format!("mtllib {mtl_name}\n")));
+    assert!(mtl.unwrap().contains("newmtl body\n"));
+}
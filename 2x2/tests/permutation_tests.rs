@@ -0,0 +1,182 @@
+mod common;
+
+use common::SCRAMBLE;
+use rubiks_cube_2x2::cube::{Color, Cube, Move};
+use rubiks_cube_2x2::solver;
+
+fn inverse_moves(moves: &[Move]) -> Vec<Move> {
+    moves.iter().rev().map(|mv| mv.inverse()).collect()
+}
+
+#[test]
+fn test_compose_matches_move_sequence() {
+    let a = Cube::from_moves(&SCRAMBLE[..3]);
+    let b = Cube::from_moves(&SCRAMBLE[3..]);
+    assert_eq!(a.compose(&b).unwrap(), Cube::from_moves(&SCRAMBLE));
+    assert_eq!(&a * &b, Cube::from_moves(&SCRAMBLE));
+    assert_eq!(a * b, Cube::from_moves(&SCRAMBLE));
+}
+
+#[test]
+fn test_compose_keeps_orientation_arrows() {
+    // 4回転すると色は戻るが、矢印も含めて完成状態に戻ることを確認
+    let r = Cube::from_moves(&[Move::R]);
+    let r4 = &(&r * &r) * &(&r * &r);
+    assert_eq!(r4, Cube::new());
+    assert!(solver::is_fully_solved(&r4));
+}
+
+#[test]
+fn test_identity_and_inverse() {
+    let cube = Cube::from_moves(&SCRAMBLE);
+    let solved = Cube::new();
+    assert_eq!(&solved * &cube, cube);
+    assert_eq!(&cube * &solved, cube);
+
+    let inverse = cube.inverse().unwrap();
+    assert_eq!(inverse, Cube::from_moves(&inverse_moves(&SCRAMBLE)));
+    assert_eq!(&cube * &inverse, solved);
+    assert_eq!(&inverse * &cube, solved);
+    assert_eq!(inverse.inverse().unwrap(), cube);
+}
+
+#[test]
+fn test_compose_is_associative() {
+    let a = Cube::from_moves(&[Move::R, Move::U]);
+    let b = Cube::from_moves(&[Move::F2, Move::Dp]);
+    let c = Cube::from_moves(&[Move::B, Move::L]);
+    assert_eq!(&(&a * &b) * &c, &a * &(&b * &c));
+}
+
+#[test]
+fn test_conjugate() {
+    let a = Cube::from_moves(&[Move::U]);
+    let setup = Cube::from_moves(&[Move::R, Move::F]);
+    let expected = Cube::from_moves(&[Move::R, Move::F, Move::U, Move::Fp, Move::Rp]);
+    assert_eq!(a.conjugate(&setup).unwrap(), expected);
+}
+
+#[test]
+fn test_compose_applies_effect_to_any_coloring() {
+    // 色の並びは左側の局面から取るため、手入力した状態にも効果を適用できる
+    let mut cube = Cube::from_moves(&SCRAMBLE);
+    let effect = Cube::from_moves(&[Move::R, Move::U]);
+    let composed = cube.compose(&effect).unwrap();
+    cube.apply_move(Move::R);
+    cube.apply_move(Move::U);
+    assert_eq!(composed, cube);
+}
+
+#[test]
+fn test_invalid_pieces_are_rejected() {
+    let mut invalid = Cube::new();
+    invalid.set_sticker_color(3, Color::Yellow);
+    assert!(invalid.inverse().is_err());
+    assert!(Cube::new().compose(&invalid).is_err());
+}
+
+#[test]
+fn test_restore_orientation_with_compose() {
+    // 手入力で向きがリセットされた局面から、正しい向きを復元できる
+    let original = Cube::from_moves(&SCRAMBLE);
+    let mut cube = original.normalized().with_clockwise_orientations();
+    let solution = solver::Solution {
+        moves: inverse_moves(&SCRAMBLE),
+        found: true,
+    };
+    cube.apply_orientation_solution(&solution).unwrap();
+    assert_eq!(cube, original);
+}
+
+#[test]
+fn test_restore_orientations_directly() {
+    // 1手の局面は、向きも含めて1手で解ける向きに復元される
+    for mv in Move::all_moves() {
+        let cube = Cube::from_moves(&[mv]).with_clockwise_orientations();
+        let restored = cube.with_restored_orientations().unwrap();
+        assert!(solver::solve(&restored, 1, false).found, "{mv}");
+    }
+    assert_eq!(
+        Cube::new()
+            .normalized()
+            .with_restored_orientations()
+            .unwrap(),
+        Cube::new()
+    );
+
+    // 色は変えず、入力の向きには依存しない
+    let original = Cube::from_moves(&SCRAMBLE);
+    let restored = original.with_restored_orientations().unwrap();
+    for i in 0..24 {
+        assert_eq!(original.get_sticker(i).color, restored.get_sticker(i).color);
+    }
+    assert_eq!(
+        original
+            .with_clockwise_orientations()
+            .with_restored_orientations()
+            .unwrap(),
+        restored
+    );
+
+    let mut invalid = Cube::new();
+    invalid.set_sticker_color(3, Color::Yellow);
+    assert!(invalid.with_restored_orientations().is_err());
+}
+
+#[test]
+fn test_restored_orientations_are_solvable() {
+    // 表引きで向きを決めていたときに、向きも含めると解けなかった局面
+    let scrambles = [
+        [Move::F2, Move::Up, Move::Bp, Move::Rp, Move::L],
+        [Move::B, Move::Rp, Move::D, Move::L, Move::Fp],
+        [Move::Dp, Move::Rp, Move::Rp, Move::F2, Move::R],
+    ];
+    let mut cubes: Vec<Cube> = scrambles
+        .iter()
+        .map(|moves| Cube::from_moves(moves))
+        .collect();
+    for _ in 0..5 {
+        let mut cube = Cube::new();
+        cube.scramble(5);
+        cubes.push(cube);
+    }
+
+    // 5手で作った局面は、向きも含めて5手以内で解ける
+    for cube in cubes {
+        let restored = cube
+            .with_clockwise_orientations()
+            .with_restored_orientations()
+            .unwrap();
+        let solution = solver::solve(&restored, 5, false);
+        assert!(solution.found, "{:?}", cube.to_file_format());
+    }
+}
+
+#[test]
+fn test_restore_orientations_f2_u_prime_b_prime_r_prime_l() {
+    // コーナーの配置からの表引きでは、向きも含めると5手で解けなかった局面
+    let cube = Cube::from_moves(&[Move::F2, Move::Up, Move::Bp, Move::Rp, Move::L]);
+    let restored = cube
+        .with_clockwise_orientations()
+        .with_restored_orientations()
+        .unwrap();
+    assert!(solver::solve(&restored, 5, false).found);
+}
+
+#[test]
+fn test_orientations_depend_on_the_path() {
+    // 同じ色の並びでも、回した経路によって矢印が180度ずれる。
+    // そのため向きはコーナーの配置だけからは決まらず、復元には探索が要る
+    let a = Cube::from_moves(&[Move::R, Move::B, Move::L]);
+    let b = Cube::from_moves(&[Move::L, Move::U, Move::R]);
+    let turns: Vec<u8> = (0..24)
+        .inspect(|&i| assert_eq!(a.get_sticker(i).color, b.get_sticker(i).color))
+        .map(|i| (a.get_sticker(i).orientation + 4 - b.get_sticker(i).orientation) % 4)
+        .collect();
+    assert!(turns.iter().all(|&t| t % 2 == 0));
+    assert!(turns.contains(&2));
+
+    // 復元した向きは、どちらかの経路を逆にたどれば揃う
+    let restored = a.with_restored_orientations().unwrap();
+    assert!(solver::solve(&restored, 3, false).found);
+}
@@ -0,0 +1,284 @@
+use rubiks_cube_2x2::algorithm::Algorithm;
+use rubiks_cube_2x2::cube::{ColorScheme, Cube, Face};
+use rubiks_cube_2x2::geometry::View3D;
+use rubiks_cube_2x2::raster::{self, Frame, Image, PlaybackOptions, RasterOptions};
+use std::io::Cursor;
+
+const WHITE: [u8; 4] = [255, 255, 255, 255];
+
+/// 820x620の展開図（1マス100px、左上の余白10px）
+fn net_options() -> RasterOptions {
+    RasterOptions {
+        width: 820,
+        height: 620,
+        ..RasterOptions::default()
+    }
+}
+
+/// 展開図のステッカーの中心から (dx, dy) だけずらしたピクセル
+fn net_pixel(image: &Image, (col, row): (u32, u32), (dx, dy): (i32, i32)) -> [u8; 4] {
+    let x = 10 + col * 100 + 50;
+    let y = 10 + row * 100 + 50;
+    image.pixel(x.saturating_add_signed(dx), y.saturating_add_signed(dy))
+}
+
+fn opaque([r, g, b]: [u8; 3]) -> [u8; 4] {
+    [r, g, b, 255]
+}
+
+#[test]
+fn test_render_net_colors_and_arrows() {
+    let scheme = ColorScheme::standard();
+    let image = raster::render_net(&Cube::new(), &net_options());
+    assert_eq!((image.width(), image.height()), (820, 620));
+    assert_eq!(image.pixels().len(), 820 * 620 * 4);
+
+    // U面（左から3列目・1行目）は白、F面（3列目・3行目）は赤、B面（7列目・3行目）は青
+    let up = scheme.display_rgb(Cube::new().get_sticker(0).color);
+    let front = scheme.display_rgb(Cube::new().get_sticker(16).color);
+    let back = scheme.display_rgb(Cube::new().get_sticker(20).color);
+    assert_eq!(net_pixel(&image, (2, 0), (30, 20)), opaque(up));
+    assert_eq!(net_pixel(&image, (2, 2), (30, 20)), opaque(front));
+    assert_eq!(net_pixel(&image, (6, 2), (30, 20)), opaque(back));
+
+    // ステッカーの中心には向きの矢印、ステッカーの間と展開図の外は背景
+    assert_eq!(net_pixel(&image, (2, 2), (0, 0)), [0, 0, 0, 255]);
+    assert_eq!(net_pixel(&image, (2, 2), (48, 0)), WHITE);
+    assert_eq!(image.pixel(0, 0), WHITE);
+    assert_eq!(net_pixel(&image, (0, 0), (0, 0)), WHITE);
+
+    let plain = raster::render_net(
+        &Cube::new(),
+        &RasterOptions {
+            arrows: false,
+            ..net_options()
+        },
+    );
+    assert_eq!(net_pixel(&plain, (2, 2), (0, 0)), opaque(front));
+}
+
+#[test]
+fn test_render_net_highlight_and_scheme() {
+    let image = raster::render_net(
+        &Cube::new(),
+        &RasterOptions {
+            highlight_face: Some(Face::Front),
+            background: [0, 0, 0, 0],
+            ..net_options()
+        },
+    );
+    // F面の枠（左上の外側5px）はオレンジ、背景は透明
+    assert_eq!(image.pixel(205, 250), [255, 140, 0, 255]);
+    assert_eq!(image.pixel(0, 0), [0, 0, 0, 0]);
+
+    let japanese = ColorScheme::japanese();
+    let image = raster::render_net(
+        &Cube::new(),
+        &RasterOptions {
+            scheme: japanese,
+            ..net_options()
+        },
+    );
+    let up = japanese.display_rgb(Cube::new().get_sticker(0).color);
+    assert_eq!(net_pixel(&image, (2, 0), (30, 20)), opaque(up));
+}
+
+#[test]
+fn test_render_3d_shows_three_faces() {
+    let scheme = ColorScheme::standard();
+    let cube = Cube::new();
+    let options = RasterOptions {
+        width: 300,
+        height: 300,
+        background: [0, 0, 0, 0],
+        ..RasterOptions::default()
+    };
+    let image = raster::render_3d(&cube, &View3D::default(), &options);
+    assert_eq!(image.pixel(0, 0), [0, 0, 0, 0]);
+
+    // 裏を向いた面は描かないので、見える面の色はちょうど3色
+    let visible = Face::ALL
+        .iter()
+        .filter(|face| {
+            let color = opaque(scheme.display_rgb(cube.get_sticker(face.index() * 4).color));
+            image.pixels().chunks(4).any(|pixel| pixel == color)
+        })
+        .count();
+    assert_eq!(visible, 3);
+
+    // 強調の枠で絵が変わるのも見える3面だけ
+    let highlighted = Face::ALL
+        .iter()
+        .filter(|&&face| {
+            let options = RasterOptions {
+                highlight_face: Some(face),
+                ..options
+            };
+            raster::render_3d(&cube, &View3D::default(), &options) != image
+        })
+        .count();
+    assert_eq!(highlighted, 3);
+}
+
+#[test]
+fn test_render_3d_follows_camera_and_state() {
+    let options = RasterOptions {
+        width: 200,
+        height: 200,
+        ..RasterOptions::default()
+    };
+    let background = |image: &Image| {
+        image
+            .pixels()
+            .chunks(4)
+            .filter(|pixel| *pixel == WHITE)
+            .count()
+    };
+    let solved = raster::render_3d(&Cube::new(), &View3D::default(), &options);
+    let zoomed = raster::render_3d(
+        &Cube::new(),
+        &View3D {
+            scale: 1.5,
+            ..View3D::default()
+        },
+        &options,
+    );
+    assert!(background(&zoomed) < background(&solved));
+
+    let turned = raster::render_3d(
+        &Algorithm::parse("R U").unwrap().to_cube(),
+        &View3D::default(),
+        &options,
+    );
+    assert_ne!(turned, solved);
+}
+
+#[test]
+fn test_write_png_round_trip() {
+    let image = raster::render_net(
+        &Cube::new(),
+        &RasterOptions {
+            width: 82,
+            height: 62,
+            ..RasterOptions::default()
+        },
+    );
+    let mut bytes = Vec::new();
+    image.write_png(&mut bytes).unwrap();
+    assert!(bytes.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+    let mut reader = png::Decoder::new(Cursor::new(bytes)).read_info().unwrap();
+    let mut buffer = vec![0; reader.output_buffer_size().unwrap()];
+    let info = reader.next_frame(&mut buffer).unwrap();
+    assert_eq!((info.width, info.height), (82, 62));
+    assert_eq!(info.color_type, png::ColorType::Rgba);
+    assert_eq!(&buffer[..info.buffer_size()], image.pixels());
+}
+
+#[test]
+fn test_image_minimum_size() {
+    let image = Image::new(0, 0, WHITE);
+    assert_eq!((image.width(), image.height()), (1, 1));
+    assert_eq!(image.pixel(0, 0), WHITE);
+}
+
+fn small_options() -> RasterOptions {
+    RasterOptions {
+        width: 120,
+        height: 120,
+        ..RasterOptions::default()
+    }
+}
+
+fn playback(notation: &str) -> Vec<Frame> {
+    raster::render_playback(
+        &Cube::new(),
+        &Algorithm::parse(notation).unwrap().moves,
+        &View3D::default(),
+        &small_options(),
+        &PlaybackOptions {
+            frame_rate: 10,
+            move_duration: 0.3,
+            pause: 1.0,
+            loops: 0,
+        },
+    )
+}
+
+#[test]
+fn test_render_playback_frames() {
+    // 1手3コマ（途中の2コマと回した後の局面）と、最初の局面
+    let frames = playback("R U");
+    assert_eq!(frames.len(), 7);
+    let delays: Vec<f32> = frames.iter().map(|frame| frame.delay).collect();
+    assert_eq!(delays, [1.0, 0.1, 0.1, 0.1, 0.1, 0.1, 1.0]);
+
+    let view = View3D::default();
+    let render = |notation: &str| {
+        raster::render_3d(
+            &Algorithm::parse(notation).unwrap().to_cube(),
+            &view,
+            &small_options(),
+        )
+    };
+    assert_eq!(
+        frames[0].image,
+        raster::render_3d(&Cube::new(), &view, &small_options())
+    );
+    assert_eq!(frames[3].image, render("R"));
+    assert_eq!(frames[6].image, render("R U"));
+    // 回転の途中のコマは、前後のどちらの局面とも違う
+    assert_ne!(frames[1].image, frames[0].image);
+    assert_ne!(frames[1].image, frames[2].image);
+    assert_ne!(frames[2].image, frames[3].image);
+
+    // 手順がなければ最初の局面の1コマだけ
+    let still = playback("");
+    assert_eq!(still.len(), 1);
+    assert_eq!(still[0].delay, 1.0);
+}
+
+#[test]
+fn test_write_apng_round_trip() {
+    let frames = playback("R");
+    let options = PlaybackOptions {
+        loops: 2,
+        ..PlaybackOptions::default()
+    };
+    let mut bytes = Vec::new();
+    raster::write_apng(&frames, &options, &mut bytes).unwrap();
+
+    let mut reader = png::Decoder::new(Cursor::new(bytes)).read_info().unwrap();
+    let control = reader.info().animation_control.unwrap();
+    assert_eq!((control.num_frames, control.num_plays), (4, 2));
+
+    let mut buffer = vec![0; reader.output_buffer_size().unwrap()];
+    let mut delays = Vec::new();
+    for frame in &frames {
+        let info = reader.next_frame(&mut buffer).unwrap();
+        let control = reader.info().frame_control.unwrap();
+        delays.push((control.delay_num, control.delay_den));
+        assert_eq!(&buffer[..info.buffer_size()], frame.image.pixels());
+    }
+    assert_eq!(
+        delays,
+        [(1000, 1000), (100, 1000), (100, 1000), (1000, 1000)]
+    );
+}
+
+#[test]
+fn test_write_apng_rejects_bad_frames() {
+    assert!(raster::write_apng(&[], &PlaybackOptions::default(), Vec::new()).is_err());
+
+    let frames = [
+        Frame {
+            image: Image::new(10, 10, WHITE),
+            delay: 0.1,
+        },
+        Frame {
+            image: Image::new(20, 10, WHITE),
+            delay: 0.1,
+        },
+    ];
+    assert!(raster::write_apng(&frames, &PlaybackOptions::default(), Vec::new()).is_err());
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use common::{colors_of, scrambled};
+use rubiks_cube_2x2::cube::repair::{self, RepairAction};
+use rubiks_cube_2x2::cube::{Color, Corner, Cube};
+
+fn scrambled_colors() -> [Color; 24] {
+    colors_of(&scrambled())
+}
+
+fn is_valid(colors: &[Color; 24]) -> bool {
+    Cube::validate_colors(colors).is_ok()
+        && Cube::from_colors(colors)
+            .map(|cube| cube.is_valid_state().is_ok())
+            .unwrap_or(false)
+}
+
+#[test]
+fn test_valid_state_needs_no_repair() {
+    assert!(repair::find_repairs(&scrambled_colors()).is_empty());
+}
+
+#[test]
+fn test_repair_swapped_stickers() {
+    let original = scrambled_colors();
+    let mut colors = original;
+    // 異なるコーナーの、色の異なる2枚を入れ替える
+    let (a, b) = (0..24)
+        .flat_map(|a| (a + 1..24).map(move |b| (a, b)))
+        .find(|&(a, b)| {
+            Corner::from_sticker(a).map(|(c, _)| c) != Corner::from_sticker(b).map(|(c, _)| c)
+                && original[a] != original[b]
+        })
+        .unwrap();
+    colors.swap(a, b);
+    assert!(!is_valid(&colors));
+
+    let repairs = repair::find_repairs(&colors);
+    assert!(!repairs.is_empty());
+    assert!(repairs.iter().all(|r| r.sticker_count() == 2));
+    assert!(repairs.iter().all(|r| is_valid(&r.colors)));
+    assert!(repairs.iter().any(|r| r.colors == original));
+}
+
+#[test]
+fn test_repair_twisted_corner() {
+    let original = scrambled_colors();
+    let mut colors = original;
+    RepairAction::Twist {
+        corner: Corner::Dbl,
+        clockwise: true,
+    }
+    .apply(&mut colors);
+
+    let repairs = repair::find_repairs(&colors);
+    // ねじれは1つのコーナーを戻すだけで直せるため、どのコーナーを回しても有効になる
+    assert!(!repairs.is_empty());
+    assert!(repairs.iter().all(|r| r.actions.len() == 1));
+    assert!(repairs.iter().all(|r| is_valid(&r.colors)));
+    assert!(repairs.iter().any(|r| {
+        r.actions
+            == vec![RepairAction::Twist {
+                corner: Corner::Dbl,
+                clockwise: false,
+            }]
+    }));
+    assert!(repairs[0].describe().contains("まわしてください"));
+}
+
+#[test]
+fn test_repair_wrong_color() {
+    let original = scrambled_colors();
+    let mut colors = original;
+    colors[5] = if original[5] == Color::Red {
+        Color::Orange
+    } else {
+        Color::Red
+    };
+    assert!(Cube::validate_colors(&colors).is_err());
+
+    let repairs = repair::find_repairs(&colors);
+    assert!(repairs.iter().all(|r| is_valid(&r.colors)));
+    assert!(repairs.iter().any(|r| {
+        r.colors == original
+            && r.actions
+                == vec![RepairAction::Recolor {
+                    index: 5,
+                    color: original[5],
+                }]
+    }));
+}
+
+#[test]
+fn test_repair_mirrored_corner() {
+    let original = scrambled_colors();
+    let mut colors = original;
+    // 同じコーナー内の2枚を入れ替えると鏡像のピースになる
+    colors.swap(3, 12);
+
+    let repairs = repair::find_repairs(&colors);
+    assert!(repairs.iter().any(|r| r.colors == original));
+    assert!(repairs.iter().all(|r| r.sticker_count() == 2));
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use common::{colors_of, scrambled};
+use rubiks_cube_2x2::cube::scan::{self, FaceEntryError, FaceTransform};
+use rubiks_cube_2x2::cube::{Corner, Cube, Face};
+use rubiks_cube_2x2::error::CubeError;
+
+#[test]
+fn test_scrambled_states_are_valid() {
+    for _ in 0..50 {
+        let mut cube = Cube::new();
+        cube.scramble(20);
+        assert!(cube.is_valid_state().is_ok());
+    }
+}
+
+#[test]
+fn test_sample_cube_files_are_valid() {
+    for name in [
+        "cube_normalwy.txt",
+        "cube_god.txt",
+        "cube_ex001.txt",
+        "cube_diff2.txt",
+    ] {
+        let content = std::fs::read_to_string(format!("cubes/{name}")).unwrap();
+        let cube = Cube::from_file_format(&content).unwrap();
+        assert!(cube.is_valid_state().is_ok(), "{name} は有効な状態のはず");
+    }
+}
+
+#[test]
+fn test_mirrored_color_scheme_is_invalid() {
+    // cube_normal.txt は白と黄色だけを入れ替えた配色で、鏡像のキューブになっている
+    let content = std::fs::read_to_string("cubes/cube_normal.txt").unwrap();
+    let cube = Cube::from_file_format(&content).unwrap();
+    assert!(cube.is_solved());
+    assert!(matches!(
+        cube.is_valid_state(),
+        Err(CubeError::InvalidState(_))
+    ));
+}
+
+#[test]
+fn test_twisted_corner_is_invalid() {
+    let cube = scrambled();
+    let mut colors = colors_of(&cube);
+    // UFRのピースだけを時計回りにねじる
+    let [a, b, c] = Corner::Ufr.stickers();
+    (colors[a], colors[b], colors[c]) = (colors[c], colors[a], colors[b]);
+
+    let twisted = Cube::from_colors(&colors).unwrap();
+    assert!(matches!(
+        twisted.is_valid_state(),
+        Err(CubeError::CornerParity(_))
+    ));
+}
+
+#[test]
+fn test_swapped_stickers_are_invalid() {
+    let mut colors = colors_of(&Cube::new());
+    // 同じコーナー内の2枚を入れ替えると鏡像のピースになる
+    colors.swap(3, 12);
+    let cube = Cube::from_colors(&colors).unwrap();
+    assert!(matches!(
+        cube.is_valid_state(),
+        Err(CubeError::InvalidState(_))
+    ));
+}
+
+#[test]
+fn test_corner_state_of_solved_cube() {
+    let state = Cube::new().corner_state().unwrap();
+    assert_eq!(state.pieces, Corner::ALL);
+    assert_eq!(state.twists, [0; 8]);
+}
+
+#[test]
+fn test_valid_input_needs_no_fix() {
+    let colors = colors_of(&scrambled());
+    assert!(scan::find_orientation_fixes(&colors).is_empty());
+}
+
+#[test]
+fn test_detect_rotated_face() {
+    let original = colors_of(&scrambled());
+    let error = FaceEntryError {
+        face: Face::Right,
+        entered_as: FaceTransform {
+            quarter_turns: 1,
+            mirrored: false,
+        },
+    };
+    let entered = error.apply(&original);
+    assert!(Cube::from_colors(&entered)
+        .unwrap()
+        .is_valid_state()
+        .is_err());
+
+    let fixes = scan::find_orientation_fixes(&entered);
+    assert_eq!(fixes.len(), 1);
+    assert_eq!(fixes[0].faces, vec![error]);
+    assert_eq!(fixes[0].colors, original);
+    assert!(fixes[0]
+        .describe()
+        .contains("Right (右面)が時計回りに90°回転"));
+}
+
+#[test]
+fn test_detect_mirrored_face() {
+    let original = colors_of(&scrambled());
+    let error = FaceEntryError {
+        face: Face::Front,
+        entered_as: FaceTransform {
+            quarter_turns: 0,
+            mirrored: true,
+        },
+    };
+    let entered = error.apply(&original);
+
+    let fixes = scan::find_orientation_fixes(&entered);
+    assert!(fixes.iter().any(|fix| fix.colors == original));
+}
+
+#[test]
+fn test_apply_and_undo_are_inverse() {
+    let original = colors_of(&scrambled());
+    for quarter_turns in 0..4 {
+        for mirrored in [false, true] {
+            let error = FaceEntryError {
+                face: Face::Up,
+                entered_as: FaceTransform {
+                    quarter_turns,
+                    mirrored,
+                },
+            };
+            assert_eq!(error.undo(&error.apply(&original)), original);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::{colors_of, scrambled};
+use rubiks_cube_2x2::cube::{Color, ColorScheme, Cube};
+
+#[test]
+fn test_standard_scheme_matches_new_cube() {
+    let scheme = ColorScheme::standard();
+    assert_eq!(scheme.solved_colors(), colors_of(&Cube::new()));
+    assert_eq!(scheme.name(), "標準配色");
+    assert_eq!(scheme.opposite(Color::White), Some(Color::Yellow));
+    assert_eq!(scheme.opposite(Color::Gray), None);
+}
+
+#[test]
+fn test_japanese_opposite_pairs() {
+    let scheme = ColorScheme::japanese();
+    assert_eq!(scheme.opposite(Color::White), Some(Color::Blue));
+    assert_eq!(scheme.opposite(Color::Green), Some(Color::Yellow));
+    assert_eq!(scheme.opposite(Color::Red), Some(Color::Orange));
+    assert_eq!(scheme.name(), "日本配色");
+}
+
+#[test]
+fn test_new_rejects_duplicate_colors() {
+    assert!(ColorScheme::new([
+        Color::White,
+        Color::White,
+        Color::Green,
+        Color::Blue,
+        Color::Red,
+        Color::Orange,
+    ])
+    .is_err());
+}
+
+#[test]
+fn test_standard_round_trip() {
+    let scheme = ColorScheme::japanese();
+    let colors = colors_of(&scrambled());
+    assert_eq!(scheme.to_standard(&scheme.from_standard(&colors)), colors);
+}
+
+#[test]
+fn test_file_format_with_scheme() {
+    let scheme = ColorScheme::japanese();
+    let cube = scrambled();
+    let content = cube.to_file_format_with_scheme(&scheme);
+    assert_ne!(content, cube.to_file_format());
+
+    let loaded = Cube::from_file_format_with_scheme(&content, &scheme).unwrap();
+    assert_eq!(colors_of(&loaded), colors_of(&cube));
+    assert!(loaded.is_valid_state().is_ok());
+}
+
+#[test]
+fn test_infer_standard_scheme() {
+    let colors = colors_of(&scrambled());
+    assert_eq!(
+        ColorScheme::infer(&colors).unwrap(),
+        ColorScheme::standard()
+    );
+}
+
+#[test]
+fn test_infer_japanese_scheme() {
+    let scanned = ColorScheme::japanese().from_standard(&colors_of(&scrambled()));
+    let scheme = ColorScheme::infer(&scanned).unwrap();
+    assert_eq!(scheme, ColorScheme::japanese());
+    assert!(Cube::from_colors(&scheme.to_standard(&scanned))
+        .unwrap()
+        .is_valid_state()
+        .is_ok());
+}
+
+#[test]
+fn test_infer_custom_scheme() {
+    // cube_normal.txt は白と黄色を入れ替えた（鏡像の）配色
+    let content = std::fs::read_to_string("cubes/cube_normal.txt").unwrap();
+    let colors = colors_of(&Cube::from_file_format(&content).unwrap());
+    let scheme = ColorScheme::infer(&colors).unwrap();
+    assert_eq!(scheme.name(), "カスタム配色");
+    assert_eq!(scheme.opposite(Color::White), Some(Color::Yellow));
+}
+
+#[test]
+fn test_infer_rejects_twisted_corner() {
+    let mut colors = colors_of(&scrambled());
+    let (a, b, c) = (3, 12, 17);
+    (colors[a], colors[b], colors[c]) = (colors[c], colors[a], colors[b]);
+    assert!(ColorScheme::infer(&colors).is_err());
+}
@@ -0,0 +1,98 @@
+use rubiks_cube_2x2::algorithm::Algorithm;
+use rubiks_cube_2x2::cube::{Corner, Cube, Face, Move};
+use rubiks_cube_2x2::search::{self, SearchOptions, SearchTarget};
+
+#[test]
+fn test_search_exact_target() {
+    let target = SearchTarget::from_cube(&Algorithm::parse("R U").unwrap().to_cube());
+    let options = SearchOptions {
+        max_length: 4,
+        ..SearchOptions::with_faces(&[Face::Right, Face::Up])
+    };
+    let results = search::search(&target, &options);
+    assert_eq!(results[0].to_string(), "R U");
+    assert!(results.iter().all(|alg| target.matches(&alg.to_cube())));
+}
+
+#[test]
+fn test_search_results_sorted_by_length_and_ergonomics() {
+    // 右面の層だけを動かす（他のコーナーは元のまま）手順
+    let target = SearchTarget::any().preserve_layer(Face::Left);
+    let options = SearchOptions {
+        max_length: 2,
+        max_results: 100,
+        moves: Move::all_moves(),
+    };
+    let results = search::search(&target, &options);
+    // 1手: R, R', R2 のみ（L面の層を保つ）
+    let one_move: Vec<String> = results
+        .iter()
+        .filter(|alg| alg.len() == 1)
+        .map(|alg| alg.to_string())
+        .collect();
+    assert_eq!(one_move, vec!["R", "R'", "R2"]);
+    assert!(results.windows(2).all(|w| w[0].len() <= w[1].len()));
+}
+
+#[test]
+fn test_search_corner_swap_preserving_first_layer() {
+    let target = SearchTarget::any()
+        .preserve_layer(Face::Down)
+        .swap_corners(Corner::Ufr, Corner::Ubr);
+    let options = SearchOptions {
+        max_length: 7,
+        max_results: 5,
+        ..SearchOptions::with_faces(&[Face::Right, Face::Up, Face::Front])
+    };
+    let results = search::search(&target, &options);
+    assert!(!results.is_empty());
+    for alg in &results {
+        let cube = alg.to_cube();
+        assert!(target.matches(&cube));
+        let state = cube.corner_state().unwrap();
+        assert_eq!(state.pieces[Corner::Ufr.index()], Corner::Ubr);
+        assert_eq!(state.pieces[Corner::Ubr.index()], Corner::Ufr);
+    }
+}
+
+#[test]
+fn test_search_respects_max_results() {
+    let target = SearchTarget::any().preserve_layer(Face::Down);
+    let options = SearchOptions {
+        max_length: 3,
+        max_results: 2,
+        ..SearchOptions::with_faces(&[Face::Right, Face::Up])
+    };
+    let results = search::search(&target, &options);
+    assert_eq!(results.len(), 2);
+    // U面だけを回す1手が最も短い
+    assert!(results.iter().all(|alg| alg.moves[0].face() == Face::Up));
+}
+
+#[test]
+fn test_target_matches() {
+    let target = SearchTarget::any().preserve_corner(Corner::Dbl);
+    let mut cube = Cube::new();
+    cube.apply_move(Move::R);
+    assert!(target.matches(&cube));
+    cube.apply_move(Move::L);
+    assert!(!target.matches(&cube));
+}
+
+#[test]
+fn test_ergonomic_cost() {
+    let ru = Algorithm::parse("R U R'").unwrap();
+    let fb = Algorithm::parse("F B F'").unwrap();
+    assert!(search::ergonomic_cost(&ru) < search::ergonomic_cost(&fb));
+    assert_eq!(
+        search::ergonomic_cost(&Algorithm::parse("R2").unwrap()),
+        search::ergonomic_cost(&Algorithm::parse("R").unwrap()) + 1
+    );
+}
+
+#[test]
+fn test_corner_from_str() {
+    assert_eq!("UFR".parse::<Corner>().unwrap(), Corner::Ufr);
+    assert_eq!("rfu".parse::<Corner>().unwrap(), Corner::Ufr);
+    assert!("UFX".parse::<Corner>().is_err());
+}
@@ -0,0 +1,500 @@
+#![cfg(feature = "serde")]
+
+use rubiks_cube_2x2::algorithm::Algorithm;
+use rubiks_cube_2x2::cube::{Color, Cube, Move, Sticker};
+use rubiks_cube_2x2::history::History;
+use rubiks_cube_2x2::solver::Solution;
+use rubiks_cube_2x2::statistics::Statistics;
+use serde::de::value::{Error, MapDeserializer, SeqDeserializer, StrDeserializer};
+use serde::de::{self, IntoDeserializer, Unexpected, Visitor};
+use serde::{ser, Deserialize, Serialize};
+use std::time::Duration;
+
+/// 完成状態の URFDLB の順の色
+const SOLVED_COLORS: &str = "WWWWBBBBRRRRYYYYGGGGOOOO";
+
+fn from_str<'de, T: Deserialize<'de>>(s: &'de str) -> Result<T, Error> {
+    let deserializer: StrDeserializer<'de, Error> = s.into_deserializer();
+    T::deserialize(deserializer)
+}
+
+fn cube_from(colors: &str, orientations: &str) -> Result<Cube, Error> {
+    Cube::deserialize(MapDeserializer::new(
+        [("colors", colors), ("orientations", orientations)].into_iter(),
+    ))
+}
+
+/// シリアライズした値（文字と文字列を区別して記録する）
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Bool(bool),
+    Unsigned(u64),
+    Signed(i64),
+    Float(f64),
+    Char(char),
+    Str(String),
+    Unit,
+    None,
+    Some(Box<Value>),
+    Seq(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+}
+
+/// 値を `Value` として記録するシリアライザ
+struct Recorder;
+
+/// 列・構造体などの要素を集める
+#[derive(Default)]
+struct Compound {
+    variant: Option<&'static str>,
+    items: Vec<Value>,
+    entries: Vec<(Value, Value)>,
+    key: Option<Value>,
+}
+
+impl Compound {
+    fn variant(variant: &'static str) -> Self {
+        Self {
+            variant: Some(variant),
+            ..Self::default()
+        }
+    }
+
+    fn wrap(&self, value: Value) -> Value {
+        match self.variant {
+            Some(variant) => Value::Map(vec![(Value::Str(variant.to_string()), value)]),
+            None => value,
+        }
+    }
+
+    fn seq(self) -> Result<Value, Error> {
+        Ok(self.wrap(Value::Seq(self.items.clone())))
+    }
+
+    fn map(self) -> Result<Value, Error> {
+        Ok(self.wrap(Value::Map(self.entries.clone())))
+    }
+
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), Error> {
+        let value = value.serialize(Recorder)?;
+        self.entries.push((Value::Str(key.to_string()), value));
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for Compound {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.items.push(value.serialize(Recorder)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Value, Error> {
+        self.seq()
+    }
+}
+
+impl ser::SerializeTuple for Compound {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Value, Error> {
+        self.seq()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Value, Error> {
+        self.seq()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Value, Error> {
+        self.seq()
+    }
+}
+
+impl ser::SerializeMap for Compound {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key.serialize(Recorder)?);
+        Ok(())
+    }
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self.key.take().expect("キーの後に値が来る");
+        self.entries.push((key, value.serialize(Recorder)?));
+        Ok(())
+    }
+    fn end(self) -> Result<Value, Error> {
+        self.map()
+    }
+}
+
+impl ser::SerializeStruct for Compound {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+    fn end(self) -> Result<Value, Error> {
+        self.map()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+    fn end(self) -> Result<Value, Error> {
+        self.map()
+    }
+}
+
+impl ser::Serializer for Recorder {
+    type Ok = Value;
+    type Error = Error;
+    type SerializeSeq = Compound;
+    type SerializeTuple = Compound;
+    type SerializeTupleStruct = Compound;
+    type SerializeTupleVariant = Compound;
+    type SerializeMap = Compound;
+    type SerializeStruct = Compound;
+    type SerializeStructVariant = Compound;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, Error> {
+        Ok(Value::Bool(v))
+    }
+    fn serialize_i8(self, v: i8) -> Result<Value, Error> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_i16(self, v: i16) -> Result<Value, Error> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_i32(self, v: i32) -> Result<Value, Error> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_i64(self, v: i64) -> Result<Value, Error> {
+        Ok(Value::Signed(v))
+    }
+    fn serialize_u8(self, v: u8) -> Result<Value, Error> {
+        self.serialize_u64(v.into())
+    }
+    fn serialize_u16(self, v: u16) -> Result<Value, Error> {
+        self.serialize_u64(v.into())
+    }
+    fn serialize_u32(self, v: u32) -> Result<Value, Error> {
+        self.serialize_u64(v.into())
+    }
+    fn serialize_u64(self, v: u64) -> Result<Value, Error> {
+        Ok(Value::Unsigned(v))
+    }
+    fn serialize_f32(self, v: f32) -> Result<Value, Error> {
+        self.serialize_f64(v.into())
+    }
+    fn serialize_f64(self, v: f64) -> Result<Value, Error> {
+        Ok(Value::Float(v))
+    }
+    fn serialize_char(self, v: char) -> Result<Value, Error> {
+        Ok(Value::Char(v))
+    }
+    fn serialize_str(self, v: &str) -> Result<Value, Error> {
+        Ok(Value::Str(v.to_string()))
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, Error> {
+        Ok(Value::Seq(
+            v.iter().map(|&b| Value::Unsigned(b.into())).collect(),
+        ))
+    }
+    fn serialize_none(self) -> Result<Value, Error> {
+        Ok(Value::None)
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, Error> {
+        Ok(Value::Some(Box::new(value.serialize(self)?)))
+    }
+    fn serialize_unit(self) -> Result<Value, Error> {
+        Ok(Value::Unit)
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, Error> {
+        Ok(Value::Unit)
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Value, Error> {
+        Ok(Value::Str(variant.to_string()))
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        Ok(Compound::variant(variant).wrap(value.serialize(self)?))
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound, Error> {
+        Ok(Compound::default())
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Compound, Error> {
+        Ok(Compound::default())
+    }
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Compound, Error> {
+        Ok(Compound::default())
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound, Error> {
+        Ok(Compound::variant(variant))
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound, Error> {
+        Ok(Compound::default())
+    }
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Compound, Error> {
+        Ok(Compound::default())
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound, Error> {
+        Ok(Compound::variant(variant))
+    }
+}
+
+impl Value {
+    fn unexpected(&self) -> Unexpected<'_> {
+        match self {
+            Value::Char(c) => Unexpected::Char(*c),
+            Value::Str(s) => Unexpected::Str(s),
+            _ => Unexpected::Other("文字・文字列以外の値"),
+        }
+    }
+}
+
+/// 記録した値を読み戻すデシリアライザ
+///
+/// 自己記述的でない形式と同じく、文字を求められたら文字だけ、文字列を求められたら文字列だけを受け付けます。
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Bool(v) => visitor.visit_bool(v),
+            Value::Unsigned(v) => visitor.visit_u64(v),
+            Value::Signed(v) => visitor.visit_i64(v),
+            Value::Float(v) => visitor.visit_f64(v),
+            Value::Char(v) => visitor.visit_char(v),
+            Value::Str(v) => visitor.visit_string(v),
+            Value::Unit => visitor.visit_unit(),
+            Value::None => visitor.visit_none(),
+            Value::Some(v) => visitor.visit_some(*v),
+            Value::Seq(items) => visitor.visit_seq(SeqDeserializer::new(items.into_iter())),
+            Value::Map(entries) => visitor.visit_map(MapDeserializer::new(entries.into_iter())),
+        }
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Char(v) => visitor.visit_char(v),
+            other => Err(de::Error::invalid_type(other.unexpected(), &"文字")),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Str(v) => visitor.visit_string(v),
+            other => Err(de::Error::invalid_type(other.unexpected(), &"文字列")),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 bytes byte_buf option unit
+        unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+fn record<T: Serialize>(value: &T) -> Value {
+    value.serialize(Recorder).unwrap()
+}
+
+/// 書き出した値を読み戻し、書き出した値が同じになることを確かめる
+fn round_trip<T: Serialize + for<'de> Deserialize<'de>>(value: &T) -> T {
+    let recorded = record(value);
+    let restored = T::deserialize(recorded.clone()).unwrap();
+    assert_eq!(record(&restored), recorded);
+    restored
+}
+
+#[test]
+fn test_core_types_implement_serde() {
+    fn assert_serde<T: Serialize + for<'de> Deserialize<'de>>() {}
+    assert_serde::<Cube>();
+    assert_serde::<rubiks_cube_2x2::cube::Sticker>();
+    assert_serde::<Color>();
+    assert_serde::<Move>();
+    assert_serde::<Algorithm>();
+    assert_serde::<rubiks_cube_2x2::solver::Solution>();
+    assert_serde::<rubiks_cube_2x2::history::History>();
+    assert_serde::<rubiks_cube_2x2::statistics::Statistics>();
+}
+
+#[test]
+fn test_move_and_algorithm_use_notation() {
+    assert_eq!(from_str::<Move>("R'").unwrap(), Move::Rp);
+    assert_eq!(from_str::<Move>("U2").unwrap(), Move::U2);
+    assert!(from_str::<Move>("X").is_err());
+    assert_eq!(
+        from_str::<Algorithm>("R U R' U'").unwrap(),
+        Algorithm::new(vec![Move::R, Move::U, Move::Rp, Move::Up])
+    );
+}
+
+#[test]
+fn test_color_uses_letters() {
+    assert_eq!(from_str::<Color>("W").unwrap(), Color::White);
+    assert_eq!(from_str::<Color>("o").unwrap(), Color::Orange);
+    assert_eq!(from_str::<Color>("-").unwrap(), Color::Gray);
+    assert!(from_str::<Color>("white").is_err());
+}
+
+#[test]
+fn test_cube_uses_colors_and_orientations() {
+    let solved = cube_from(SOLVED_COLORS, &"1203".repeat(6)).unwrap();
+    assert_eq!(solved, Cube::new());
+
+    // 向きは24文字の0-3、色は24文字の色の文字
+    assert!(cube_from(SOLVED_COLORS, "1203").is_err());
+    assert!(cube_from(SOLVED_COLORS, &"4".repeat(24)).is_err());
+    assert!(cube_from("WWWW", &"0".repeat(24)).is_err());
+    assert!(cube_from(&SOLVED_COLORS.replace('W', "U"), &"0".repeat(24)).is_err());
+
+    // 色の数やピースは検証しないので、入力途中の局面も読み込める
+    let partial = cube_from(&SOLVED_COLORS.replace('W', "-"), &"0".repeat(24)).unwrap();
+    assert_eq!(partial.get_sticker(0).color, Color::Gray);
+}
+
+#[test]
+fn test_color_serializes_as_string() {
+    assert_eq!(record(&Color::White), Value::Str("W".to_string()));
+    assert_eq!(record(&Color::Gray), Value::Str("-".to_string()));
+    for color in Color::ALL.into_iter().chain([Color::Gray]) {
+        assert_eq!(round_trip(&color), color);
+    }
+    // 文字（char）として書かれた値は、文字列を読む形式では読めない
+    assert!(Color::deserialize(Value::Char('W')).is_err());
+}
+
+#[test]
+fn test_round_trip_core_types() {
+    for mv in Move::all_moves() {
+        assert_eq!(round_trip(&mv), mv);
+    }
+    let algorithm = Algorithm::parse("R U R' U' F2").unwrap();
+    assert_eq!(round_trip(&algorithm), algorithm);
+
+    let sticker = Sticker {
+        color: Color::Orange,
+        orientation: 3,
+    };
+    assert_eq!(round_trip(&sticker), sticker);
+
+    let scrambled = Cube::from_moves(&[Move::R, Move::Up, Move::F2]);
+    assert_eq!(round_trip(&scrambled), scrambled);
+    assert_eq!(
+        record(&Cube::new()),
+        Value::Map(vec![
+            (
+                Value::Str("colors".to_string()),
+                Value::Str(SOLVED_COLORS.to_string())
+            ),
+            (
+                Value::Str("orientations".to_string()),
+                Value::Str("1203".repeat(6))
+            ),
+        ])
+    );
+
+    // 未設定の色や色の数が合わない局面も、そのまま書き出して読み戻せる
+    let mut partial = Cube::new();
+    partial.set_sticker_color(3, Color::Gray);
+    partial.set_sticker_color(5, Color::White);
+    assert_eq!(round_trip(&partial), partial);
+}
+
+#[test]
+fn test_round_trip_solution_history_statistics() {
+    let solution = Solution {
+        moves: vec![Move::R, Move::Up],
+        found: true,
+    };
+    let restored = round_trip(&solution);
+    assert_eq!((restored.moves, restored.found), (solution.moves, true));
+
+    let mut history = History::with_capacity(10);
+    for mv in [Move::R, Move::U, Move::F] {
+        history.push(mv);
+    }
+    history.undo();
+    let restored = round_trip(&history);
+    assert_eq!(restored.undo_moves(), history.undo_moves());
+    assert_eq!(restored.redo_moves(), history.redo_moves());
+
+    let mut statistics = Statistics::new();
+    statistics.record_solve(Duration::from_millis(1500));
+    statistics.record_manual_move();
+    let restored = round_trip(&statistics);
+    assert_eq!(restored.total_solves, 1);
+    assert_eq!(restored.best_solve_time, Some(Duration::from_millis(1500)));
+    assert_eq!(restored.session_start, statistics.session_start);
+}
@@ -0,0 +1,159 @@
+use rubiks_cube_2x2::alg_file::AlgFile;
+use rubiks_cube_2x2::algorithm::Algorithm;
+use rubiks_cube_2x2::cube::{Color, ColorScheme, Cube, Move};
+use rubiks_cube_2x2::error::{CubeError, ParseError};
+use rubiks_cube_2x2::gui::app::ViewMode;
+use rubiks_cube_2x2::gui::renderer_3d::View3D;
+use rubiks_cube_2x2::gui::session::Session;
+use rubiks_cube_2x2::history::History;
+use rubiks_cube_2x2::method::{self, Method};
+use rubiks_cube_2x2::solver::{CubeOrientation, TargetOrientation};
+use rubiks_cube_2x2::statistics::Statistics;
+use std::time::{Duration, UNIX_EPOCH};
+
+fn sample_session() -> Session {
+    let cube = Cube::from_moves(&[Move::R, Move::U, Move::Fp]);
+    let mut history = History::new();
+    for mv in [Move::R, Move::U, Move::Fp, Move::D2] {
+        history.push(mv);
+    }
+    history.undo();
+
+    let mut statistics = Statistics::new();
+    statistics.record_solve(Duration::new(1, 234_567_891));
+    statistics.record_solve_failure();
+    statistics.record_manual_move();
+    statistics.session_start = UNIX_EPOCH + Duration::new(1_768_000_000, 5);
+
+    let mut color_scheme = ColorScheme::japanese();
+    color_scheme.set_rgb(Color::Red, [200, 10, 20]);
+
+    let method_solution = method::solve(&cube, Method::Ortega).unwrap();
+    let alg_file = AlgFile::new("Sune")
+        .with_step(Algorithm::parse("R U R' U").unwrap(), Some("右手で # 3手"))
+        .with_step(Algorithm::parse("R U2 R'").unwrap(), None);
+
+    Session {
+        solution_cube_state: Some(cube.clone()),
+        cube,
+        history,
+        solution: Some(method_solution.algorithm().moves),
+        solution_step: 2,
+        solution_text: "解法: 3 手\n向き: C:\\cube".to_string(),
+        statistics,
+        view_mode: ViewMode::ThreeD,
+        view_3d: View3D {
+            yaw: 0.1 + 0.2,
+            pitch: -1.0 / 3.0,
+            scale: 1.75,
+        },
+        animation_speed: 0.05,
+        ignore_orientation: true,
+        solve_target: TargetOrientation::Fixed(CubeOrientation {
+            up: Color::Yellow,
+            front: Color::Green,
+        }),
+        method: Method::Cll,
+        method_solution: Some(method_solution),
+        alg_file: Some(alg_file),
+        color_scheme,
+        autosave: false,
+    }
+}
+
+#[test]
+fn test_session_round_trip_is_exact() {
+    let session = sample_session();
+    let text = session.to_file_format();
+    assert!(text.starts_with("2x2session v1\n"));
+
+    let restored = Session::parse(&text).unwrap();
+    assert_eq!(restored.cube, session.cube);
+    assert_eq!(restored.solution, session.solution);
+    assert_eq!(restored.solution_cube_state, session.solution_cube_state);
+    assert_eq!(restored.solution_step, 2);
+    assert_eq!(restored.solution_text, session.solution_text);
+    assert_eq!(restored.view_mode, ViewMode::ThreeD);
+    assert_eq!(restored.view_3d, session.view_3d);
+    assert_eq!(restored.animation_speed, session.animation_speed);
+    assert!(restored.ignore_orientation);
+    assert_eq!(restored.solve_target, session.solve_target);
+    assert_eq!(restored.method, Method::Cll);
+    assert_eq!(restored.method_solution, session.method_solution);
+    assert_eq!(restored.alg_file, session.alg_file);
+    assert_eq!(restored.color_scheme, session.color_scheme);
+    assert!(!restored.autosave);
+
+    let (stats, expected) = (&restored.statistics, &session.statistics);
+    assert_eq!(stats.total_solves, 2);
+    assert_eq!(stats.successful_solves, 1);
+    assert_eq!(stats.total_solve_time, expected.total_solve_time);
+    assert_eq!(stats.best_solve_time, expected.best_solve_time);
+    assert_eq!(stats.total_manual_moves, 1);
+    assert_eq!(stats.session_start, expected.session_start);
+
+    // もう一度書き出しても同じ
+    assert_eq!(restored.to_file_format(), text);
+}
+
+#[test]
+fn test_session_restores_undo_and_redo() {
+    let mut restored = Session::parse(&sample_session().to_file_format())
+        .unwrap()
+        .history;
+    assert_eq!(restored.undo_moves(), [Move::R, Move::U, Move::Fp]);
+    assert_eq!(restored.redo_moves(), [Move::D2]);
+    assert_eq!(restored.redo(), Some(Move::D2));
+    assert_eq!(restored.undo(), Some(Move::D2.inverse()));
+    assert_eq!(restored.undo(), Some(Move::F));
+}
+
+#[test]
+fn test_session_solve_settings() {
+    for target in [
+        TargetOrientation::Any,
+        TargetOrientation::BottomColors(vec![Color::Yellow, Color::White]),
+    ] {
+        let session = Session {
+            solve_target: target.clone(),
+            ..Session::default()
+        };
+        let restored = Session::parse(&session.to_file_format()).unwrap();
+        assert_eq!(restored.solve_target, target);
+    }
+
+    // 手法の解法は開始局面から求め直し、保存した解法と違えば使わない
+    let mut session = sample_session();
+    session.solution = Some(vec![Move::R]);
+    session.solution_step = 0;
+    let restored = Session::parse(&session.to_file_format()).unwrap();
+    assert_eq!(restored.method_solution, None);
+    assert_eq!(restored.solution, Some(vec![Move::R]));
+}
+
+#[test]
+fn test_session_defaults_and_unknown_keys() {
+    let session = Session::parse("2x2session v1\n# コメント\nfuture_key: 1\nundo: R U\n").unwrap();
+    assert_eq!(session.cube, Cube::new());
+    assert_eq!(session.history.undo_moves(), [Move::R, Move::U]);
+    assert_eq!(session.solution, None);
+    assert_eq!(session.view_mode, ViewMode::Both);
+    assert_eq!(session.solve_target, TargetOrientation::Any);
+    assert_eq!(session.method, Method::Beginner);
+    assert!(session.autosave);
+}
+
+#[test]
+fn test_session_errors() {
+    let location = |text: &str| match Session::parse(text) {
+        Err(CubeError::Parse(ParseError { line, column, .. })) => (line, column),
+        other => panic!("位置つきのエラーになりません: {other:?}"),
+    };
+    assert_eq!(location("2x2cube v2\n"), (1, 1));
+    assert_eq!(location("2x2session v1\nundo: R X\n"), (2, 7));
+    assert_eq!(location("2x2session v1\nview_mode:  4d\n"), (2, 13));
+    assert_eq!(location("2x2session v1\ncube: WWWW\n"), (2, 1));
+    assert_eq!(location("2x2session v1\nsolve_target: fixed W\n"), (2, 15));
+    assert_eq!(location("2x2session v1\nmethod: roux\n"), (2, 9));
+    assert_eq!(location("2x2session v1\nautosave: yes\n"), (2, 11));
+}
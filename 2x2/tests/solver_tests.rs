@@ -1,5 +1,5 @@
-use rubiks_cube_2x2::cube::{Cube, Move};
-use rubiks_cube_2x2::solver;
+use rubiks_cube_2x2::cube::{Color, Cube, Move};
+use rubiks_cube_2x2::solver::{self, CubeOrientation, SolveOptions, TargetOrientation};
 
 #[test]
 fn test_solve_already_solved() {
@@ -406,3 +406,70 @@ fn test_end_to_end_specific_scramble() {
         "完全に初期状態に戻るべき、または24通りの完成状態のいずれか"
     );
 }
+
+#[test]
+fn test_solve_any_orientation_reports_reached_orientation() {
+    let mut cube = Cube::new();
+    cube.apply_move(Move::R);
+
+    let solution = solver::solve_with_options(&cube, &SolveOptions::default(), None);
+    assert!(solution.found);
+    assert_eq!(solution.moves, vec![Move::Rp]);
+    assert_eq!(
+        solution.reached_orientation(&cube),
+        Some(CubeOrientation::HOME)
+    );
+}
+
+#[test]
+fn test_solve_with_bottom_color() {
+    let mut cube = Cube::new();
+    cube.apply_move(Move::R);
+    cube.apply_move(Move::U);
+
+    for color in [Color::Yellow, Color::White, Color::Green] {
+        let options = SolveOptions {
+            ignore_orientation: true,
+            target: TargetOrientation::BottomColors(vec![color]),
+            ..SolveOptions::default()
+        };
+        let solution = solver::solve_with_options(&cube, &options, None);
+        assert!(solution.found);
+        let reached = solution.reached_orientation(&cube).unwrap();
+        assert_eq!(reached.down(), color);
+    }
+}
+
+#[test]
+fn test_solve_with_fixed_orientation() {
+    // 完成状態でも、別の向きを指定すると持ち替えに相当する手順が必要
+    let cube = Cube::new();
+    let target = CubeOrientation {
+        up: Color::Yellow,
+        front: Color::Red,
+    };
+    let options = SolveOptions {
+        ignore_orientation: true,
+        target: TargetOrientation::Fixed(target),
+        ..SolveOptions::default()
+    };
+    let solution = solver::solve_with_options(&cube, &options, None);
+    assert!(solution.found);
+    assert!(!solution.moves.is_empty());
+    assert_eq!(solution.reached_orientation(&cube), Some(target));
+}
+
+#[test]
+fn test_solve_with_impossible_orientation() {
+    // 反対色同士はU面とF面に同時に来ない
+    let options = SolveOptions {
+        target: TargetOrientation::Fixed(CubeOrientation {
+            up: Color::White,
+            front: Color::Yellow,
+        }),
+        ..SolveOptions::default()
+    };
+    let solution = solver::solve_with_options(&Cube::new(), &options, None);
+    assert!(!solution.found);
+    assert_eq!(solution.reached_orientation(&Cube::new()), None);
+}
@@ -0,0 +1,119 @@
+use rubiks_cube_2x2::algorithm::Algorithm;
+use rubiks_cube_2x2::cube::{Cube, Face};
+use rubiks_cube_2x2::svg::{self, LastLayerSvgOptions, NetSvgOptions};
+
+fn last_layer(notation: &str) -> String {
+    svg::last_layer_svg(
+        &Algorithm::parse(notation).unwrap(),
+        &LastLayerSvgOptions::default(),
+    )
+}
+
+#[test]
+fn test_net_svg_stickers_and_arrows() {
+    let svg = svg::net_svg(&Cube::new(), &NetSvgOptions::default());
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(svg.matches("class=\"sticker\"").count(), 24);
+    assert_eq!(svg.matches("class=\"arrow\"").count(), 24);
+    // U面は白、F面は赤
+    assert_eq!(svg.matches("fill=\"#ffffff\"").count(), 4);
+    assert!(svg.contains("data-index=\"16\" x=\"87.85\" y=\"87.85\""));
+    assert!(svg.contains("fill=\"#ff3232\""));
+    assert!(!svg.contains("class=\"highlight\""));
+
+    // 向き1（右向き）の矢印は90度回す
+    assert!(svg.contains("rotate(90 "));
+
+    let plain = svg::net_svg(
+        &Cube::new(),
+        &NetSvgOptions {
+            arrows: false,
+            ..NetSvgOptions::default()
+        },
+    );
+    assert_eq!(plain.matches("class=\"arrow\"").count(), 0);
+}
+
+#[test]
+fn test_net_svg_highlight_face() {
+    let svg = svg::net_svg(
+        &Cube::new(),
+        &NetSvgOptions {
+            cell_size: 10.0,
+            highlight_face: Some(Face::Back),
+            ..NetSvgOptions::default()
+        },
+    );
+    assert!(svg.contains("width=\"82\" height=\"62\""));
+    // B面は左から7列目、上から3行目（余白1・枠の外側0.5）
+    assert!(
+        svg.contains("<rect class=\"highlight\" x=\"60.5\" y=\"20.5\" width=\"21\" height=\"21\"")
+    );
+}
+
+#[test]
+fn test_last_layer_movement_arrows() {
+    // U: 4点巡回
+    assert_eq!(last_layer("U").matches("class=\"movement\"").count(), 4);
+    // Niklas: 3点巡回
+    let niklas = last_layer("R U' L' U R' U' L U");
+    assert_eq!(niklas.matches("class=\"movement\"").count(), 3);
+    assert!(!niklas.contains("marker-start"));
+    // Sune: 2点の入れ替えは両端に矢じりのある1本の線
+    let sune = last_layer("R U R' U R U2 R'");
+    assert_eq!(
+        sune.matches("class=\"movement\"").count(),
+        sune.matches("marker-start").count()
+    );
+    // その場でねじれるだけのコーナー
+    let twists =
+        last_layer("(R' D' R D)(R' D' R D) U (R' D' R D)(R' D' R D)(R' D' R D)(R' D' R D) U'");
+    assert_eq!(twists.matches("class=\"movement\"").count(), 0);
+    assert_eq!(twists.matches("class=\"twist\"").count(), 2);
+
+    let still = svg::last_layer_svg(
+        &Algorithm::parse("U").unwrap(),
+        &LastLayerSvgOptions {
+            movement: false,
+            ..LastLayerSvgOptions::default()
+        },
+    );
+    assert_eq!(still.matches("class=\"movement\"").count(), 0);
+}
+
+#[test]
+fn test_last_layer_shows_solved_case() {
+    // U面の4枚と周りの8枚
+    let svg = last_layer("");
+    assert_eq!(svg.matches("class=\"sticker\"").count(), 12);
+    assert_eq!(svg.matches("fill=\"#ffffff\"").count(), 4);
+
+    // 手順で揃う局面の色を描く（U2 ならU面は白のまま、F面の位置にB面の橙が来る）
+    let u2 = last_layer("U2");
+    assert_eq!(u2.matches("fill=\"#ffffff\"").count(), 4);
+    let front = u2
+        .lines()
+        .find(|line| line.contains("data-index=\"16\""))
+        .unwrap();
+    assert!(front.contains("fill=\"#ffa500\""));
+}
+
+#[test]
+fn test_alg_sheet_layout() {
+    let sune = Algorithm::parse("R U R' U R U2 R'").unwrap();
+    let niklas = Algorithm::parse("R U' L' U R' U' L U").unwrap();
+    let svg = svg::alg_sheet_svg(
+        &[("Sune", &sune), ("<Niklas & co>", &niklas), ("U", &sune)],
+        2,
+        &LastLayerSvgOptions::default(),
+    );
+    assert_eq!(svg.matches("<g class=\"case\"").count(), 3);
+    assert_eq!(svg.matches("<marker ").count(), 1);
+    assert!(svg.contains("&lt;Niklas &amp; co&gt;"));
+    assert!(svg.contains(">R U R' U R U2 R'</text>"));
+    // 2列目の図は右に、3つ目は2行目に並ぶ
+    assert!(svg.contains("class=\"case\" transform=\"translate(0 0)\""));
+    assert!(svg.contains("class=\"case\" transform=\"translate(162.4 0)\""));
+    assert!(svg.contains("class=\"case\" transform=\"translate(0 166.4)\""));
+}
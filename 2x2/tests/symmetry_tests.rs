@@ -0,0 +1,129 @@
+use rubiks_cube_2x2::cube::{Cube, Move, Symmetry};
+use rubiks_cube_2x2::solver;
+
+fn apply(moves: &[Move]) -> Cube {
+    let mut cube = Cube::new();
+    for &mv in moves {
+        cube.apply_move(mv);
+    }
+    cube
+}
+
+/// M面での鏡像に対応する手
+fn mirrored(mv: Move) -> Move {
+    match mv {
+        Move::R => Move::Lp,
+        Move::Rp => Move::L,
+        Move::R2 => Move::L2,
+        Move::L => Move::Rp,
+        Move::Lp => Move::R,
+        Move::L2 => Move::R2,
+        other => other.inverse(),
+    }
+}
+
+#[test]
+fn test_symmetry_counts() {
+    assert_eq!(Symmetry::all().len(), 48);
+    assert_eq!(Symmetry::rotations().len(), 24);
+    assert!(Symmetry::rotations().iter().all(|s| !s.is_mirror()));
+    assert!(Symmetry::all()[24..].iter().all(|s| s.is_mirror()));
+    assert_eq!(Symmetry::identity().index(), 0);
+}
+
+#[test]
+fn test_conjugate_keeps_solved_state() {
+    for symmetry in Symmetry::all() {
+        assert_eq!(symmetry.conjugate(&Cube::new()), Cube::new().normalized());
+    }
+}
+
+#[test]
+fn test_inverse_symmetry() {
+    let cube = apply(&[Move::R, Move::U, Move::Fp, Move::D2]);
+    for symmetry in Symmetry::all() {
+        let back = symmetry.inverse().conjugate(&symmetry.conjugate(&cube));
+        assert_eq!(back, cube.normalized());
+    }
+}
+
+#[test]
+fn test_mirror_matches_mirrored_moves() {
+    let moves = [Move::R, Move::U, Move::Fp, Move::D, Move::B2, Move::Lp];
+    let mirror_moves: Vec<Move> = moves.iter().map(|&mv| mirrored(mv)).collect();
+    let mirror = &Symmetry::all()[24];
+
+    assert_eq!(
+        mirror.conjugate(&apply(&moves)),
+        apply(&mirror_moves).normalized()
+    );
+}
+
+#[test]
+fn test_conjugates_are_valid() {
+    let cube = apply(&[Move::R, Move::U2, Move::F, Move::Dp, Move::L]);
+    for (_, image) in cube.symmetries() {
+        assert!(image.is_valid_state().is_ok());
+    }
+}
+
+#[test]
+fn test_canonical_is_shared_by_class() {
+    let cube = apply(&[Move::R, Move::U, Move::Rp, Move::Up]);
+    let canonical = cube.canonical();
+    for (_, image) in cube.symmetries() {
+        assert_eq!(image.canonical(), canonical);
+    }
+    // 持ち替えても代表は同じ
+    for rotation in Symmetry::rotations() {
+        assert_eq!(rotation.transform(&cube).canonical(), canonical);
+    }
+    // 別の局面とは異なる
+    assert_ne!(apply(&[Move::R, Move::U]).canonical(), canonical);
+}
+
+#[test]
+fn test_symmetry_class() {
+    let solved = Cube::new().symmetry_class();
+    assert_eq!(solved.size, 1);
+    assert!(solved.mirror_symmetric);
+
+    // 持ち替えを同一視すると R と L は同じ局面なので、90°回転の局面は6通り
+    // R の鏡像は L' (= R') で、回転だけでは R に移らない
+    let quarter = apply(&[Move::R]).symmetry_class();
+    assert_eq!(quarter.size, 6);
+    assert!(!quarter.mirror_symmetric);
+    assert_eq!(quarter.canonical, apply(&[Move::Up]).canonical());
+    assert_eq!(
+        quarter.symmetry.conjugate(&apply(&[Move::R])).canonical(),
+        quarter.canonical
+    );
+
+    let half = apply(&[Move::R2]).symmetry_class();
+    assert_eq!(half.size, 3);
+    assert!(half.mirror_symmetric);
+}
+
+#[test]
+fn test_symmetry_to_detects_mirror() {
+    let original = apply(&[Move::R, Move::U, Move::F]);
+    let mirror = apply(&[Move::Lp, Move::Up, Move::Fp]);
+
+    let symmetry = mirror.symmetry_to(&original).unwrap();
+    assert_eq!(symmetry.conjugate(&original), mirror.normalized());
+    assert!(original.symmetry_to(&original).unwrap().index() == 0);
+    assert!(original.symmetry_to(&apply(&[Move::R, Move::U])).is_none());
+}
+
+#[test]
+fn test_solved_states_are_rotations() {
+    let states = solver::get_solved_states();
+    assert_eq!(states.len(), 24);
+    assert_eq!(states[0], Cube::new());
+    for (i, a) in states.iter().enumerate() {
+        assert!(a.is_solved());
+        assert!(states[i + 1..]
+            .iter()
+            .all(|b| b.normalized() != a.normalized()));
+    }
+}